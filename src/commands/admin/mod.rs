@@ -1,89 +1,185 @@
-use std::borrow::Cow;
-use std::sync::Arc;
+#![allow(clippy::too_many_arguments)]
 
+use chrono::Utc;
 use poise::CreateReply;
-use serenity::all::{Channel, GetMessages, PrivateChannel};
-use tokio::sync::RwLock;
+use serenity::all::{
+    ButtonStyle, Channel, ChannelId, CreateActionRow, CreateButton, CreateInteractionResponse,
+    GetMessages, Message, MessageId, User, UserId,
+};
 
-use crate::shared::structs::config::channel_control::ChannelControl;
-use crate::shared::structs::{Context, ContextError};
+use crate::shared::services::moderation_service::{log_case, new_case};
+use crate::shared::services::rate_limit_service::apply_command_cooldowns;
+use crate::shared::structs::config::channel_control::{
+    CommandOptions, RESPONSES_CATEGORY, RESTRICTED_ALWAYS_ALLOWED,
+};
+use crate::shared::structs::config::rate_limit::CommandCooldown;
+use crate::shared::structs::moderation::moderation_case::{CaseAction, CaseTarget};
+use crate::shared::structs::{Context, ContextData, ContextError};
+use crate::shared::utility::format_duration;
+
+const DEFAULT_PURGE_AMOUNT: usize = 10;
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+const MAX_SCANNED_MESSAGES: usize = 5000;
+const MESSAGES_PER_PAGE: u8 = 100;
+const PURGE_CONFIRM_TIMEOUT: u64 = 30;
+/// Discord only bulk deletes messages younger than 14 days. Keep a margin for the time the purge takes.
+const BULK_DELETE_MAX_AGE_MINUTES: i64 = 14 * 24 * 60 - 10;
+
+struct PurgeFilter {
+    user_id: Option<UserId>,
+    bots_only: bool,
+    contains: Option<String>,
+    attachments_only: bool,
+    after: Option<MessageId>,
+}
+
+impl PurgeFilter {
+    fn matches(&self, message: &Message) -> bool {
+        self.user_id
+            .is_none_or(|user_id| message.author.id == user_id)
+            && (!self.bots_only || message.author.bot)
+            && self
+                .contains
+                .as_ref()
+                .is_none_or(|text| message.content.to_lowercase().contains(text))
+            && (!self.attachments_only || !message.attachments.is_empty())
+    }
+
+    fn describe(&self) -> String {
+        let mut filters = vec![];
+        if let Some(user_id) = self.user_id {
+            filters.push(format!("from <@{user_id}>"));
+        }
+        if self.bots_only {
+            filters.push("from bots".to_string());
+        }
+        if let Some(text) = self.contains.as_ref() {
+            filters.push(format!("containing \"{text}\""));
+        }
+        if self.attachments_only {
+            filters.push("with attachments".to_string());
+        }
+        if let Some(after) = self.after {
+            filters.push(format!("after {after}"));
+        }
+
+        if filters.is_empty() {
+            String::new()
+        } else {
+            format!(" Filters: {}.", filters.join(", "))
+        }
+    }
+}
 
 /// Administrative commands.
 #[poise::command(
     slash_command,
-    subcommands("enable", "disable", "allow", "disallow", "purge"),
-    subcommand_required
+    subcommands(
+        "enable", "disable", "allow", "disallow", "channels", "purge", "cooldown", "mod_log"
+    ),
+    subcommand_required,
+    category = "Admin",
+    custom_data = "RESTRICTED_ALWAYS_ALLOWED"
 )]
 pub async fn admin(_: Context<'_>) -> Result<(), ContextError> {
     Ok(())
 }
 
-/// Enable a specific channel for bot usage.
-#[poise::command(
-    slash_command,
-    required_permissions = "ADMINISTRATOR",
-    category = "Admin"
-)]
+/// Enable a specific channel for bot usage, or for a category of commands only.
+#[poise::command(slash_command, category = "Admin")]
 pub async fn enable(
     ctx: Context<'_>,
     #[description = "The channel to enable for bot usage."] channel: Channel,
+    #[description = "Only enable this category, which then can only be used in its enabled channels."]
+    #[autocomplete = "autocomplete_category"]
+    category: Option<String>,
 ) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
     let channel_id = channel.id().get();
-    let channel_control = ctx.data().channel_control.clone();
+    let Some(category) = validate_category(ctx, category).await? else {
+        return Ok(());
+    };
+    let category_text = category
+        .as_ref()
+        .map(|category| format!(" for `{category}`"))
+        .unwrap_or_default();
 
-    if check_if_channel_id_exists_in_enabled(&channel_control, channel_id).await {
-        ctx.send(
-            CreateReply::default()
-                .content(format!("The channel <#{channel_id}> is already enabled!")),
-        )
-        .await?;
-    } else {
-        {
-            let mut channel_control_write_lock = channel_control.write().await;
-            channel_control_write_lock.enabled_channels.push(channel_id);
+    let enabled = {
+        let mut channel_control_write_lock = ctx.data().channel_control.write().await;
+        let enabled = channel_control_write_lock.enable(guild_id, channel_id, category.as_deref());
+        if enabled {
             channel_control_write_lock.write_channel_control()?;
         }
-        ctx.send(
-            CreateReply::default()
-                .content(format!("Successfully enabled channel <#{channel_id}>!")),
+        enabled
+    };
+
+    if !enabled {
+        ctx.send(CreateReply::default().content(format!(
+            "The channel <#{channel_id}> is already enabled{category_text}!"
+        )))
+        .await?;
+    } else {
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::EnableChannel)
+                .with_target(CaseTarget::Channel(channel_id))
+                .with_details(format!("Enabled{category_text}.")),
         )
+        .await;
+        ctx.send(CreateReply::default().content(format!(
+            "Successfully enabled channel <#{channel_id}>{category_text}!"
+        )))
         .await?;
     }
 
     Ok(())
 }
 
-/// Disable a specific channel for bot usage.
-#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+/// Disable a specific channel for bot usage, or for a category of commands only.
+#[poise::command(slash_command)]
 pub async fn disable(
     ctx: Context<'_>,
     #[description = "The channel to disable for bot usage."] channel: Channel,
+    #[description = "Only disable this category."]
+    #[autocomplete = "autocomplete_category"]
+    category: Option<String>,
 ) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
     let channel_id = channel.id().get();
-    let channel_control = ctx.data().channel_control.clone();
+    let Some(category) = validate_category(ctx, category).await? else {
+        return Ok(());
+    };
+    let category_text = category
+        .as_ref()
+        .map(|category| format!(" for `{category}`"))
+        .unwrap_or_default();
 
-    if !check_if_channel_id_exists_in_enabled(&channel_control, channel_id).await {
-        ctx.send(
-            CreateReply::default()
-                .content(format!("The channel <#{channel_id}> is not yet enabled!")),
-        )
-        .await?;
-    } else {
-        {
-            let mut channel_control_write_lock = channel_control.write().await;
-            let filtered_channels = channel_control_write_lock
-                .enabled_channels
-                .iter()
-                .filter(|id| **id != channel_id)
-                .copied()
-                .collect::<Vec<_>>();
-            channel_control_write_lock.enabled_channels = filtered_channels;
+    let disabled = {
+        let mut channel_control_write_lock = ctx.data().channel_control.write().await;
+        let disabled =
+            channel_control_write_lock.disable(guild_id, channel_id, category.as_deref());
+        if disabled {
             channel_control_write_lock.write_channel_control()?;
         }
-        ctx.send(
-            CreateReply::default()
-                .content(format!("Successfully disabled channel <#{channel_id}>!")),
+        disabled
+    };
+
+    if !disabled {
+        ctx.send(CreateReply::default().content(format!(
+            "The channel <#{channel_id}> is not yet enabled{category_text}!"
+        )))
+        .await?;
+    } else {
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::DisableChannel)
+                .with_target(CaseTarget::Channel(channel_id))
+                .with_details(format!("Disabled{category_text}.")),
         )
+        .await;
+        ctx.send(CreateReply::default().content(format!(
+            "Successfully disabled channel <#{channel_id}>{category_text}!"
+        )))
         .await?;
     }
 
@@ -91,31 +187,34 @@ pub async fn disable(
 }
 
 /// Allow a specific channel for random responses of bot.
-#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+#[poise::command(slash_command)]
 pub async fn allow(
     ctx: Context<'_>,
     #[description = "The channel to allow for random responses."] channel: Channel,
 ) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
     let channel_id = channel.id().get();
-    let channel_control = ctx.data().channel_control.clone();
 
-    if !check_if_channel_id_exists_in_ignored(&channel_control, channel_id).await {
+    let allowed = {
+        let mut channel_control_write_lock = ctx.data().channel_control.write().await;
+        let allowed = channel_control_write_lock.unignore(guild_id, channel_id);
+        if allowed {
+            channel_control_write_lock.write_channel_control()?;
+        }
+        allowed
+    };
+
+    if !allowed {
         ctx.send(CreateReply::default().content(format!(
             "The channel <#{channel_id}> is not yet disallowed!"
         )))
         .await?;
     } else {
-        {
-            let mut channel_control_write_lock = channel_control.write().await;
-            let filtered_channels = channel_control_write_lock
-                .ignored_channels
-                .iter()
-                .filter(|id| **id != channel_id)
-                .copied()
-                .collect::<Vec<_>>();
-            channel_control_write_lock.ignored_channels = filtered_channels;
-            channel_control_write_lock.write_channel_control()?;
-        }
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::AllowChannel).with_target(CaseTarget::Channel(channel_id)),
+        )
+        .await;
         ctx.send(CreateReply::default().content(format!(
             "Successfully allowed channel <#{channel_id}> for bot responses!"
         )))
@@ -126,25 +225,34 @@ pub async fn allow(
 }
 
 /// Disallow a specific channel for random responses of bot.
-#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+#[poise::command(slash_command)]
 pub async fn disallow(
     ctx: Context<'_>,
     #[description = "The channel to disallow for random responses."] channel: Channel,
 ) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
     let channel_id = channel.id().get();
-    let channel_control = ctx.data().channel_control.clone();
 
-    if check_if_channel_id_exists_in_ignored(&channel_control, channel_id).await {
+    let disallowed = {
+        let mut channel_control_write_lock = ctx.data().channel_control.write().await;
+        let disallowed = channel_control_write_lock.ignore(guild_id, channel_id);
+        if disallowed {
+            channel_control_write_lock.write_channel_control()?;
+        }
+        disallowed
+    };
+
+    if !disallowed {
         ctx.send(CreateReply::default().content(format!(
             "The channel <#{channel_id}> is already disallowed!"
         )))
         .await?;
     } else {
-        {
-            let mut channel_control_write_lock = channel_control.write().await;
-            channel_control_write_lock.ignored_channels.push(channel_id);
-            channel_control_write_lock.write_channel_control()?;
-        }
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::DisallowChannel).with_target(CaseTarget::Channel(channel_id)),
+        )
+        .await;
         ctx.send(CreateReply::default().content(format!(
             "Successfully disallowed channel <#{channel_id}> for bot responses!"
         )))
@@ -154,103 +262,521 @@ pub async fn disallow(
     Ok(())
 }
 
-/// Purge messages from this channel. Default to 10 most recent messages. Maximum 100 messages.
-#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+/// Show where each category of commands and the bot's responses can be used in this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn channels(ctx: Context<'_>) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default();
+    let guild_channel_ids = guild_id
+        .channels(ctx.http())
+        .await?
+        .into_keys()
+        .map(|channel_id| channel_id.get())
+        .collect::<Vec<_>>();
+    let commands = &ctx.framework().options().commands;
+    let categories = command_categories(commands);
+
+    let content = {
+        let channel_control = ctx.data().channel_control.read().await;
+        let guild_channel_control = channel_control.get(guild_id.get());
+        let mut channel_ids = channel_control
+            .enabled_channels
+            .iter()
+            .chain(guild_channel_control.enabled_channels.iter())
+            .chain(guild_channel_control.category_channels.values().flatten())
+            .filter(|channel_id| guild_channel_ids.contains(channel_id))
+            .copied()
+            .collect::<Vec<_>>();
+        channel_ids.sort();
+        channel_ids.dedup();
+
+        let mut lines = vec![format!(
+            "**Usable everywhere:** {}",
+            always_allowed_commands(commands)
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )];
+
+        if channel_ids.is_empty() {
+            lines.push("No channel is enabled for other commands yet.".to_string());
+        }
+        for channel_id in channel_ids.iter() {
+            let cells = categories
+                .iter()
+                .map(|category| {
+                    let allowed = if category == RESPONSES_CATEGORY {
+                        channel_control.is_category_allowed(guild_id.get(), *channel_id, category)
+                            && !channel_control.is_ignored(guild_id.get(), *channel_id)
+                    } else {
+                        channel_control.is_command_allowed(
+                            guild_id.get(),
+                            *channel_id,
+                            Some(category.as_str()),
+                        )
+                    };
+                    format!("{} {category}", if allowed { "✅" } else { "⛔" })
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(format!("<#{channel_id}>: {cells}"));
+        }
+
+        let response_channels = guild_channel_control
+            .category_channels
+            .get(RESPONSES_CATEGORY)
+            .cloned()
+            .unwrap_or_default();
+        let ignored_channels = channel_control
+            .ignored_channels
+            .iter()
+            .chain(guild_channel_control.ignored_channels.iter())
+            .filter(|channel_id| guild_channel_ids.contains(channel_id))
+            .map(|channel_id| format!("<#{channel_id}>"))
+            .collect::<Vec<_>>();
+        lines.push(if response_channels.is_empty() {
+            "**Responses:** everywhere".to_string()
+        } else {
+            format!(
+                "**Responses:** only in {}",
+                response_channels
+                    .iter()
+                    .map(|channel_id| format!("<#{channel_id}>"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        });
+        if !ignored_channels.is_empty() {
+            lines.push(format!(
+                "**Random responses disallowed in:** {}",
+                ignored_channels.join(", ")
+            ));
+        }
+        lines.join("\n")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Purge messages from this channel. Default to 10 most recent messages. Maximum 1000 messages.
+#[poise::command(slash_command)]
 pub async fn purge(
     ctx: Context<'_>,
-    #[description = "The amount of messages to purge."] amount: Option<i32>,
+    #[description = "The amount of messages to purge. Default to 10."]
+    #[min = 1]
+    #[max = 1000]
+    amount: Option<usize>,
+    #[description = "Only purge messages from this user."] user: Option<User>,
+    #[description = "Only purge messages from bots."] bots_only: Option<bool>,
+    #[description = "Only purge messages containing this text."] contains: Option<String>,
+    #[description = "Only purge messages with attachments."] attachments_only: Option<bool>,
+    #[description = "Only purge messages before this message ID."] before: Option<String>,
+    #[description = "Only purge messages after this message ID."] after: Option<String>,
 ) -> Result<(), ContextError> {
+    let (before, after) = match (parse_message_id(before), parse_message_id(after)) {
+        (Ok(before), Ok(after)) => (before, after),
+        _ => {
+            ctx.send(
+                CreateReply::default()
+                    .content("That's not a valid message ID!")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let amount = amount.unwrap_or(DEFAULT_PURGE_AMOUNT);
+    let filter = PurgeFilter {
+        user_id: user.map(|user| user.id),
+        bots_only: bots_only.unwrap_or_default(),
+        contains: contains.map(|text| text.to_lowercase()),
+        attachments_only: attachments_only.unwrap_or_default(),
+        after,
+    };
+
     let reply_handle = ctx
-        .send(CreateReply::default().content("Okay. Hold on..."))
+        .send(
+            CreateReply::default()
+                .content("Retrieving messages...")
+                .ephemeral(true),
+        )
         .await?;
 
-    let guild_id = ctx.guild_id().unwrap_or_default();
     let channel_id = ctx.channel_id();
-    let guild_channel = ctx
-        .cache()
-        .guild(guild_id)
-        .and_then(|g| g.channels.get(&channel_id).cloned());
+    let (messages, scanned_count) =
+        fetch_purge_candidates(ctx, channel_id, before, &filter, amount).await?;
+    if messages.is_empty() {
+        reply_handle
+            .edit(
+                ctx,
+                CreateReply::default().content(format!(
+                    "None of the last {scanned_count} messages match the filters."
+                )),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let bulk_delete_threshold = bulk_delete_threshold();
+    let old_message_count = messages
+        .iter()
+        .filter(|message| message.timestamp.unix_timestamp() < bulk_delete_threshold)
+        .count();
+    let old_message_notice = if old_message_count > 0 {
+        format!(
+            "\n{old_message_count} of them are older than 14 days and will be deleted one by one, which takes a while."
+        )
+    } else {
+        String::new()
+    };
 
+    let confirm_button_id = format!("purge_confirm_{}", ctx.id());
+    let cancel_button_id = format!("purge_cancel_{}", ctx.id());
     reply_handle
         .edit(
             ctx,
-            CreateReply::default().content("Retrieving messages..."),
+            CreateReply::default()
+                .content(format!(
+                    "Found {} messages to purge among the last {scanned_count} messages.{old_message_notice}\nDo you want to delete them?",
+                    messages.len()
+                ))
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(&confirm_button_id)
+                        .label("Delete")
+                        .style(ButtonStyle::Danger),
+                    CreateButton::new(&cancel_button_id)
+                        .label("Cancel")
+                        .style(ButtonStyle::Secondary),
+                ])]),
         )
         .await?;
 
-    let amount = amount.unwrap_or(10);
-
     let sent_message = reply_handle.message().await?;
-    let sent_message = match sent_message {
-        Cow::Borrowed(m) => m.clone(),
-        Cow::Owned(m) => m,
+    let interaction = sent_message
+        .await_component_interaction(ctx)
+        .author_id(ctx.author().id)
+        .timeout(std::time::Duration::from_secs(PURGE_CONFIRM_TIMEOUT))
+        .await;
+    let confirmed = match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(ctx.http(), CreateInteractionResponse::Acknowledge)
+                .await?;
+            interaction.data.custom_id == confirm_button_id
+        }
+        None => false,
     };
 
-    let mut dm_channel: Option<PrivateChannel> = None;
-
-    let messages = if let Some(ref guild_channel) = guild_channel {
-        guild_channel
-            .messages(
-                ctx.http(),
-                GetMessages::new()
-                    .limit(amount as u8)
-                    .before(sent_message.id),
-            )
-            .await?
-    } else {
-        let channel = ctx.author().create_dm_channel(ctx.http()).await?;
-        let messages = channel
-            .messages(
-                ctx.http(),
-                GetMessages::new()
-                    .limit(amount as u8)
-                    .before(sent_message.id),
+    if !confirmed {
+        reply_handle
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content("Purge cancelled.")
+                    .components(vec![]),
             )
             .await?;
-        dm_channel = Some(channel);
-        messages
-    };
+        return Ok(());
+    }
 
     reply_handle
         .edit(
             ctx,
-            CreateReply::default().content(format!(
-                "The last {amount} messages in this channel will be deleted in 5 seconds."
+            CreateReply::default()
+                .content(format!("Deleting {} messages...", messages.len()))
+                .components(vec![]),
+        )
+        .await?;
+
+    let matched_count = messages.len();
+    let deleted_count =
+        delete_purged_messages(ctx, channel_id, messages, ctx.guild_id().is_some()).await;
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default().content(if deleted_count == matched_count {
+                format!("Deleted {deleted_count} messages.")
+            } else {
+                format!("Deleted {deleted_count} of {matched_count} messages. The rest couldn't be deleted.")
+            }),
+        )
+        .await?;
+
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::Purge)
+            .with_target(CaseTarget::Channel(channel_id.get()))
+            .with_details(format!(
+                "Deleted {deleted_count} messages.{}",
+                filter.describe()
             )),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Set the cooldowns of a command in seconds. Use 0 to remove a cooldown, or leave all empty to show them.
+#[poise::command(slash_command, owners_only)]
+pub async fn cooldown(
+    ctx: Context<'_>,
+    #[description = "A command such as `image` or `game hangman`."]
+    #[autocomplete = "autocomplete_command_name"]
+    command: String,
+    #[description = "Seconds before the same user can use the command again."] user: Option<u64>,
+    #[description = "Seconds before the command can be used in the same channel again."]
+    channel: Option<u64>,
+    #[description = "Seconds before the command can be used in the same server again."]
+    guild: Option<u64>,
+) -> Result<(), ContextError> {
+    let command_name = command.trim().to_lowercase();
+    let commands = &ctx.framework().options().commands;
+    if !command_names(commands).contains(&command_name) {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("There is no command called `{command_name}`!"))
+                .ephemeral(true),
         )
         .await?;
+        return Ok(());
+    }
 
-    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    let cooldown = {
+        let mut rate_limits_write_lock = ctx.data().rate_limits.write().await;
+        if user.is_some() || channel.is_some() || guild.is_some() {
+            let cooldown = rate_limits_write_lock
+                .cooldowns
+                .entry(command_name.clone())
+                .or_default();
+            let to_seconds = |seconds: u64| Some(seconds).filter(|seconds| *seconds > 0);
+            if let Some(seconds) = user {
+                cooldown.user = to_seconds(seconds);
+            }
+            if let Some(seconds) = channel {
+                cooldown.channel = to_seconds(seconds);
+            }
+            if let Some(seconds) = guild {
+                cooldown.guild = to_seconds(seconds);
+            }
+            if *cooldown == CommandCooldown::default() {
+                rate_limits_write_lock.cooldowns.remove(&command_name);
+            }
+            rate_limits_write_lock.write_rate_limits()?;
+            apply_command_cooldowns(commands, &rate_limits_write_lock);
+        }
+        rate_limits_write_lock
+            .cooldowns
+            .get(&command_name)
+            .cloned()
+            .unwrap_or_default()
+    };
 
-    if let Some(guild_channel) = guild_channel {
-        guild_channel.delete_messages(ctx.http(), messages).await?;
-    } else if let Some(dm_channel) = dm_channel {
-        dm_channel.delete_messages(ctx.http(), messages).await?;
+    let describe = |seconds: Option<u64>| {
+        seconds
+            .map(|seconds| format_duration(chrono::Duration::seconds(seconds as i64)))
+            .unwrap_or_else(|| "none".to_string())
+    };
+    ctx.send(CreateReply::default().content(format!(
+        "Cooldowns of `{command_name}`: per user {}, per channel {}, per server {}.",
+        describe(cooldown.user),
+        describe(cooldown.channel),
+        describe(cooldown.guild)
+    )))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the channel where moderation cases are posted. Leave empty to stop posting them.
+#[poise::command(slash_command, guild_only, rename = "mod-log")]
+pub async fn mod_log(
+    ctx: Context<'_>,
+    #[description = "The channel to post moderation cases to."] channel: Option<Channel>,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let channel_id = channel.map(|channel| channel.id().get());
+    {
+        let mut settings_write_lock = ctx.data().moderation.settings.write().await;
+        settings_write_lock.get_mut(guild_id).mod_log_channel_id = channel_id;
+        settings_write_lock.write_moderation_settings()?;
     }
 
-    reply_handle.delete(ctx).await?;
+    let content = match channel_id {
+        Some(channel_id) => format!("Moderation cases will now be posted to <#{channel_id}>."),
+        None => "Moderation cases will no longer be posted.".to_string(),
+    };
+    ctx.send(CreateReply::default().content(content)).await?;
 
     Ok(())
 }
 
-async fn check_if_channel_id_exists_in_enabled(
-    channel_control: &Arc<RwLock<ChannelControl>>,
-    channel_id: u64,
-) -> bool {
-    channel_control
-        .read()
-        .await
-        .enabled_channels
-        .contains(&channel_id)
+/// The lowercase categories of all commands, plus the pseudo category for the bot's responses.
+fn command_categories(commands: &[poise::Command<ContextData, ContextError>]) -> Vec<String> {
+    let mut categories = commands
+        .iter()
+        .filter_map(|command| command.category.as_ref())
+        .map(|category| category.to_lowercase())
+        .chain(std::iter::once(RESPONSES_CATEGORY.to_string()))
+        .collect::<Vec<_>>();
+    categories.sort();
+    categories.dedup();
+    categories
+}
+
+fn always_allowed_commands(commands: &[poise::Command<ContextData, ContextError>]) -> Vec<String> {
+    commands
+        .iter()
+        .filter(|command| {
+            command
+                .custom_data
+                .downcast_ref::<CommandOptions>()
+                .is_some_and(|options| options.always_allowed)
+        })
+        .map(|command| command.qualified_name.clone())
+        .collect()
+}
+
+/// The qualified names of all commands and subcommands.
+fn command_names(commands: &[poise::Command<ContextData, ContextError>]) -> Vec<String> {
+    let mut names = vec![];
+    for command in commands.iter() {
+        names.push(command.qualified_name.to_lowercase());
+        names.extend(command_names(&command.subcommands));
+    }
+    names
+}
+
+async fn autocomplete_command_name(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+    command_names(&ctx.framework().options().commands)
+        .into_iter()
+        .filter(|name| name.contains(&partial))
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .collect()
+}
+
+/// Normalizes the category and tells the user if it doesn't exist.
+/// Returns `None` if the command should stop, otherwise the normalized category, if any.
+async fn validate_category(
+    ctx: Context<'_>,
+    category: Option<String>,
+) -> Result<Option<Option<String>>, ContextError> {
+    let Some(category) = category.map(|category| category.trim().to_lowercase()) else {
+        return Ok(Some(None));
+    };
+
+    if command_categories(&ctx.framework().options().commands).contains(&category) {
+        Ok(Some(Some(category)))
+    } else {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("There is no category called `{category}`!"))
+                .ephemeral(true),
+        )
+        .await?;
+        Ok(None)
+    }
+}
+
+async fn autocomplete_category(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+    command_categories(&ctx.framework().options().commands)
+        .into_iter()
+        .filter(|category| category.contains(&partial))
+        .collect()
+}
+
+fn parse_message_id(id: Option<String>) -> Result<Option<MessageId>, std::num::ParseIntError> {
+    id.map(|id| id.trim().parse::<u64>().map(MessageId::new))
+        .transpose()
+}
+
+fn bulk_delete_threshold() -> i64 {
+    (Utc::now() - chrono::Duration::minutes(BULK_DELETE_MAX_AGE_MINUTES)).timestamp()
 }
 
-async fn check_if_channel_id_exists_in_ignored(
-    channel_control: &Arc<RwLock<ChannelControl>>,
-    channel_id: u64,
-) -> bool {
-    channel_control
-        .read()
-        .await
-        .ignored_channels
-        .contains(&channel_id)
+/// Pages backwards through the channel history until enough matching messages are found,
+/// the `after` boundary is reached, or too many messages have been scanned.
+/// Returns the matching messages and the number of scanned messages.
+async fn fetch_purge_candidates(
+    ctx: Context<'_>,
+    channel_id: ChannelId,
+    before: Option<MessageId>,
+    filter: &PurgeFilter,
+    amount: usize,
+) -> anyhow::Result<(Vec<Message>, usize)> {
+    let mut cursor = before;
+    let mut scanned_count = 0;
+    let mut candidates = vec![];
+
+    while scanned_count < MAX_SCANNED_MESSAGES {
+        let mut request = GetMessages::new().limit(MESSAGES_PER_PAGE);
+        if let Some(cursor) = cursor {
+            request = request.before(cursor);
+        }
+        let page = channel_id.messages(ctx.http(), request).await?;
+        let page_size = page.len();
+        cursor = page.last().map(|message| message.id);
+
+        for message in page.into_iter() {
+            if filter.after.is_some_and(|after| message.id <= after) {
+                return Ok((candidates, scanned_count));
+            }
+
+            scanned_count += 1;
+            if filter.matches(&message) {
+                candidates.push(message);
+                if candidates.len() >= amount {
+                    return Ok((candidates, scanned_count));
+                }
+            }
+        }
+
+        if page_size < MESSAGES_PER_PAGE as usize {
+            break;
+        }
+    }
+
+    Ok((candidates, scanned_count))
+}
+
+/// Bulk deletes the messages younger than 14 days and deletes older ones one by one.
+/// Bulk deletion is unavailable in DMs. Returns the number of deleted messages.
+async fn delete_purged_messages(
+    ctx: Context<'_>,
+    channel_id: ChannelId,
+    messages: Vec<Message>,
+    bulk_allowed: bool,
+) -> usize {
+    let bulk_delete_threshold = bulk_delete_threshold();
+    let (recent_messages, old_messages): (Vec<_>, Vec<_>) =
+        messages.into_iter().partition(|message| {
+            bulk_allowed && message.timestamp.unix_timestamp() >= bulk_delete_threshold
+        });
+
+    let mut deleted_count = 0;
+    for chunk in recent_messages.chunks(MESSAGES_PER_PAGE as usize) {
+        let result = if let [message] = chunk {
+            channel_id.delete_message(ctx.http(), message.id).await
+        } else {
+            channel_id.delete_messages(ctx.http(), chunk).await
+        };
+        match result {
+            Ok(_) => deleted_count += chunk.len(),
+            Err(e) => tracing::error!("Failed to bulk delete messages: {}", e),
+        }
+    }
+
+    for message in old_messages.iter() {
+        match channel_id.delete_message(ctx.http(), message.id).await {
+            Ok(_) => deleted_count += 1,
+            Err(e) => tracing::error!("Failed to delete message {}: {}", message.id, e),
+        }
+    }
+
+    deleted_count
 }
@@ -1,6 +1,4 @@
 pub mod hangman;
 pub mod quiz;
-pub mod tictactoe;
 pub use hangman::*;
 pub use quiz::*;
-pub use tictactoe::*;
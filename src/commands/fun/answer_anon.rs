@@ -7,6 +7,7 @@ use serenity::all::{
 };
 use serenity::builder::CreateMessage;
 
+use crate::shared::structs::config::channel_control::ALWAYS_ALLOWED;
 use crate::shared::structs::{Context, ContextError};
 
 #[derive(Debug, Clone, Default, poise::Modal)]
@@ -19,7 +20,7 @@ struct AnswerAnonModal {
 }
 
 /// Answer question of the day anonymously. Note you won't get any credits when answering anonymously.
-#[poise::command(slash_command, dm_only)]
+#[poise::command(slash_command, dm_only, custom_data = "ALWAYS_ALLOWED")]
 pub async fn answer_anon(ctx: Context<'_>) -> Result<(), ContextError> {
     ctx.defer().await?;
 
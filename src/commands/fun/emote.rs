@@ -9,7 +9,9 @@ use crate::shared::constants::{
     EMOTE_BASE_LINK, EMOTE_ID_REGEX, EMOTE_IS_ANIMATED_REGEX, EMOTE_REGEX, KOU_COLOR,
     SHIBA_KEK_ICON, TAIGA_COLOR,
 };
+use crate::shared::services::moderation_service::{log_case, new_case};
 use crate::shared::structs::fun::emote::Emote;
+use crate::shared::structs::moderation::moderation_case::{CaseAction, CaseTarget};
 use crate::shared::structs::{Context, ContextError};
 use crate::shared::utility::{get_author_avatar, get_author_name};
 
@@ -93,13 +95,20 @@ pub async fn add(
     {
         let mut emote_list_write_lock = emote_list.write().await;
         emote_list_write_lock.emotes.push(Emote {
-            name: emote_name,
+            name: emote_name.clone(),
             id: emote_id,
             link,
-            raw: emote,
+            raw: emote.clone(),
         });
         emote_list_write_lock.write_emote_list()?;
     }
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::AddEmote)
+            .with_target(CaseTarget::Emote(emote_name))
+            .with_details(emote),
+    )
+    .await;
 
     ctx.send(CreateReply::default().content("Successfully added the emote!"))
         .await?;
@@ -183,6 +192,13 @@ pub async fn remove(
             emote_list_write_lock.emotes = filtered_emote_list;
             emote_list_write_lock.write_emote_list()?;
         }
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::RemoveEmote)
+                .with_target(CaseTarget::Emote(emote.name))
+                .with_details(emote.raw),
+        )
+        .await;
 
         ctx.send(CreateReply::default().content("Successfully removed the emote!"))
             .await?;
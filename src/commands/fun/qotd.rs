@@ -7,6 +7,7 @@ use serenity::builder::CreateEmbed;
 use serenity::model::channel::ChannelType;
 
 use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::services::scheduler_service::{ScheduledAction, schedule_job};
 use crate::shared::structs::fun::qotd::QotdInfo;
 use crate::shared::structs::{Context, ContextError};
 
@@ -79,6 +80,7 @@ pub async fn qotd(
                     )
                     .await?;
 
+                let expiry = Utc::now() + chrono::Duration::days(1);
                 let qotd_infos = ctx.data().qotd_infos.clone();
                 let mut qotd_infos_write_lock = qotd_infos.write().await;
                 qotd_infos_write_lock.qotd_infos.insert(
@@ -86,11 +88,17 @@ pub async fn qotd(
                     QotdInfo {
                         thread_channel_id: thread.id.get(),
                         question: question.to_string(),
-                        expiry: Utc::now() + chrono::Duration::days(1),
+                        expiry,
                         participated_members: vec![],
                     },
                 );
                 qotd_infos_write_lock.write_qotd_infos()?;
+                schedule_job(
+                    ScheduledAction::ExpireQotd {
+                        thread_channel_id: thread.id.get(),
+                    },
+                    expiry,
+                );
             }
         }
     }
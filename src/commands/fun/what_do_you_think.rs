@@ -1,4 +1,5 @@
 use crate::shared::services::open_router_service::opine_specific;
+use crate::shared::services::rate_limit_service::throttled_message;
 use crate::shared::structs::{ContextData, ContextError};
 use crate::shared::utility::get_author_name;
 use poise::CreateReply;
@@ -33,8 +34,9 @@ pub async fn what_do_you_think(
             ctx.send(CreateReply::default().content(response)).await?;
         }
         Err(e) => {
-            let error_message =
-                format!("An error occurred when answering what do you think: {e:?}");
+            let error_message = throttled_message(&e).unwrap_or_else(|| {
+                format!("An error occurred when answering what do you think: {e:?}")
+            });
             tracing::error!("{}", &error_message);
             ctx.send(CreateReply::default().content(error_message))
                 .await?;
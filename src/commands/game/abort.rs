@@ -0,0 +1,17 @@
+use poise::CreateReply;
+
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::game::game_session::abort_game_session;
+use crate::shared::structs::{Context, ContextError};
+
+/// Abort the game running in this channel.
+#[poise::command(slash_command, guild_only, custom_data = "RESTRICTED")]
+pub async fn abort(ctx: Context<'_>) -> Result<(), ContextError> {
+    let content = match abort_game_session(ctx.channel_id().get()) {
+        Some(game) => format!("The {game} game in this channel has been aborted."),
+        None => "There is no game running in this channel!".to_string(),
+    };
+    ctx.send(CreateReply::default().content(content)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,602 @@
+use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::structs::game::game_session::{start_game_session, touch_game_session};
+use crate::shared::structs::record::game_stats::{BoardGameStats, GameStats, update_game_stats};
+use crate::shared::structs::{Context, ContextError};
+use poise::{CreateReply, ReplyHandle};
+use rand::prelude::*;
+use serenity::all::{
+    ButtonStyle, Color, ComponentInteraction, ComponentInteractionDataKind, CreateActionRow,
+    CreateButton, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, Mentionable, User,
+};
+use serenity::builder::CreateEmbed;
+use std::time::{Duration, Instant};
+
+const CHALLENGE_TIMEOUT: u64 = 60;
+const MOVE_TIMEOUT: u64 = 60;
+const REMATCH_TIMEOUT: u64 = 30;
+const NORMAL_MISTAKE_CHANCE: f64 = 0.4;
+const MAX_SELECT_OPTIONS: usize = 25;
+const SELECT_MENU_SUFFIX: &str = "select";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, poise::ChoiceParameter)]
+pub enum BotDifficulty {
+    #[name = "Easy"]
+    Easy,
+    #[name = "Normal"]
+    Normal,
+    #[name = "Hard"]
+    Hard,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameOutcome {
+    Ongoing,
+    Win(usize),
+    Draw,
+}
+
+/// A two-player turn-based game. Players are addressed by their index, 0 moving first.
+pub trait TurnBasedGame: Send + Sync {
+    /// The name shown in the title of the board.
+    const NAME: &'static str;
+    /// The prefix of the IDs of every component of the game.
+    const ID: &'static str;
+    const FOOTER: Option<&'static str> = None;
+
+    /// The piece shown next to the name of the player.
+    fn piece(player: usize) -> &'static str;
+    /// The record of this game in the user's game stats.
+    fn stats(stats: &mut GameStats) -> &mut BoardGameStats;
+
+    fn current_player(&self) -> usize;
+    fn outcome(&self) -> GameOutcome;
+    fn valid_moves(&self) -> Vec<usize>;
+    fn play(&mut self, game_move: usize);
+    fn best_move(&self) -> Option<usize>;
+    /// The board drawn in the embed. Can be empty if the components already show the board.
+    fn render(&self) -> String;
+    /// The components used to pick a move, built with [`move_button`] or [`move_select_menu`].
+    fn components(&self, move_id_prefix: &str, finished: bool) -> Vec<CreateActionRow>;
+}
+
+#[derive(Debug, Copy, Clone)]
+enum RoundResult {
+    Finished(GameOutcome),
+    Forfeited { loser: usize },
+}
+
+struct ComponentIds {
+    move_prefix: String,
+    forfeit: String,
+    rematch: String,
+}
+
+/// The first player moves first. `None` is Taiga or Kou.
+type Players = [Option<User>; 2];
+
+/// Starts a game against the opponent, or against Taiga or Kou if there is no opponent.
+pub async fn start_board_game<G, F>(
+    ctx: Context<'_>,
+    opponent: Option<User>,
+    difficulty: Option<BotDifficulty>,
+    new_game: F,
+) -> Result<(), ContextError>
+where
+    G: TurnBasedGame,
+    F: Fn() -> G + Send + Sync,
+{
+    let Some(session) = start_game_session(
+        ctx.channel_id().get(),
+        ctx.guild_id().map(|id| id.get()),
+        G::NAME,
+    ) else {
+        ctx.send(CreateReply::default().content("A game is already running in this channel!"))
+            .await?;
+        return Ok(());
+    };
+
+    let difficulty = difficulty.unwrap_or(BotDifficulty::Normal);
+    tokio::select! {
+        result = run_board_game(ctx, opponent, difficulty, new_game) => result,
+        _ = session.cancelled() => Ok(()),
+    }
+}
+
+/// Builds a button that plays the move when pressed.
+pub fn move_button(
+    move_id_prefix: &str,
+    game_move: usize,
+    label: &str,
+    style: ButtonStyle,
+    disabled: bool,
+) -> CreateButton {
+    CreateButton::new(format!("{move_id_prefix}{game_move}"))
+        .label(label)
+        .style(style)
+        .disabled(disabled)
+}
+
+/// Builds a select menu of moves, for games with more moves than fit in buttons.
+pub fn move_select_menu(
+    move_id_prefix: &str,
+    moves: Vec<(usize, String)>,
+    placeholder: &str,
+) -> CreateActionRow {
+    let options = moves
+        .into_iter()
+        .take(MAX_SELECT_OPTIONS)
+        .map(|(game_move, label)| CreateSelectMenuOption::new(label, game_move.to_string()))
+        .collect();
+    CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            format!("{move_id_prefix}{SELECT_MENU_SUFFIX}"),
+            CreateSelectMenuKind::String { options },
+        )
+        .placeholder(placeholder),
+    )
+}
+
+async fn run_board_game<G, F>(
+    ctx: Context<'_>,
+    opponent: Option<User>,
+    difficulty: BotDifficulty,
+    new_game: F,
+) -> Result<(), ContextError>
+where
+    G: TurnBasedGame,
+    F: Fn() -> G + Send + Sync,
+{
+    let Some((mut players, reply_handle)) = gather_players::<G>(ctx, opponent).await? else {
+        return Ok(());
+    };
+
+    let ids = ComponentIds {
+        move_prefix: format!("{}_move_{}_", G::ID, ctx.id()),
+        forfeit: format!("{}_forfeit_{}", G::ID, ctx.id()),
+        rematch: format!("{}_rematch_{}", G::ID, ctx.id()),
+    };
+
+    loop {
+        let mut game = new_game();
+        let result = play_round(ctx, &reply_handle, &players, difficulty, &mut game, &ids).await?;
+        let winner = match result {
+            RoundResult::Finished(GameOutcome::Win(winner)) => Some(winner),
+            RoundResult::Forfeited { loser } => Some(1 - loser),
+            _ => None,
+        };
+        record_stats::<G>(ctx, &players, winner).await;
+
+        if !await_rematch(ctx, &reply_handle, &players, &game, result, &ids).await? {
+            return Ok(());
+        }
+
+        players.swap(0, 1);
+    }
+}
+
+async fn gather_players<'a, G: TurnBasedGame>(
+    ctx: Context<'a>,
+    opponent: Option<User>,
+) -> anyhow::Result<Option<(Players, ReplyHandle<'a>)>> {
+    let author = ctx.author().clone();
+    let opponent = opponent.filter(|user| user.id != ctx.framework().bot_id);
+
+    match opponent {
+        Some(opponent) if opponent.id == author.id => {
+            ctx.send(CreateReply::default().content("You can't challenge yourself!"))
+                .await?;
+            Ok(None)
+        }
+        Some(opponent) if opponent.bot => {
+            ctx.send(CreateReply::default().content(format!(
+                "I'm the only bot here who knows how to play {}!",
+                G::NAME
+            )))
+            .await?;
+            Ok(None)
+        }
+        Some(opponent) => {
+            let accept_button_id = format!("{}_accept_{}", G::ID, ctx.id());
+            let decline_button_id = format!("{}_decline_{}", G::ID, ctx.id());
+            let reply_handle = ctx
+                .send(
+                    CreateReply::default()
+                        .content(format!(
+                            "{}, {} challenged you to a game of {}! Do you accept?",
+                            opponent.mention(),
+                            author.mention(),
+                            G::NAME
+                        ))
+                        .components(vec![CreateActionRow::Buttons(vec![
+                            CreateButton::new(&accept_button_id)
+                                .label("Accept")
+                                .style(ButtonStyle::Success),
+                            CreateButton::new(&decline_button_id)
+                                .label("Decline")
+                                .style(ButtonStyle::Danger),
+                        ])]),
+                )
+                .await?;
+
+            if await_challenge(ctx, &reply_handle, &opponent, &accept_button_id).await? {
+                Ok(Some(([Some(author), Some(opponent)], reply_handle)))
+            } else {
+                Ok(None)
+            }
+        }
+        None => {
+            let players = if rand::random() {
+                [Some(author), None]
+            } else {
+                [None, Some(author)]
+            };
+            let reply_handle = ctx
+                .send(CreateReply::default().content(format!("Let's play {}!", G::NAME)))
+                .await?;
+            Ok(Some((players, reply_handle)))
+        }
+    }
+}
+
+async fn await_challenge(
+    ctx: Context<'_>,
+    reply_handle: &ReplyHandle<'_>,
+    opponent: &User,
+    accept_button_id: &str,
+) -> anyhow::Result<bool> {
+    let sent_msg = reply_handle.message().await?;
+    let challenge_end_time = Instant::now() + Duration::from_secs(CHALLENGE_TIMEOUT);
+
+    loop {
+        let remaining = challenge_end_time.saturating_duration_since(Instant::now());
+        let Some(interaction) = sent_msg
+            .await_component_interaction(ctx)
+            .timeout(remaining)
+            .await
+        else {
+            reply_handle
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content(format!(
+                            "{} didn't respond to the challenge...",
+                            opponent.mention()
+                        ))
+                        .components(vec![]),
+                )
+                .await?;
+            return Ok(false);
+        };
+
+        if interaction.user.id != opponent.id {
+            respond_ephemeral(ctx, &interaction, "This challenge isn't for you!").await?;
+            continue;
+        }
+
+        let accepted = interaction.data.custom_id == accept_button_id;
+        interaction
+            .create_response(ctx.http(), CreateInteractionResponse::Acknowledge)
+            .await?;
+        if !accepted {
+            reply_handle
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content(format!("{} declined the challenge.", opponent.mention()))
+                        .components(vec![]),
+                )
+                .await?;
+        }
+
+        return Ok(accepted);
+    }
+}
+
+async fn play_round<G: TurnBasedGame>(
+    ctx: Context<'_>,
+    reply_handle: &ReplyHandle<'_>,
+    players: &Players,
+    difficulty: BotDifficulty,
+    game: &mut G,
+    ids: &ComponentIds,
+) -> anyhow::Result<RoundResult> {
+    loop {
+        touch_game_session(ctx.channel_id().get());
+        let outcome = game.outcome();
+        if outcome != GameOutcome::Ongoing {
+            return Ok(RoundResult::Finished(outcome));
+        }
+
+        let player_index = game.current_player();
+        let Some(current_player) = players[player_index].as_ref() else {
+            let Some(game_move) = choose_bot_move(game, difficulty) else {
+                return Ok(RoundResult::Finished(GameOutcome::Draw));
+            };
+            game.play(game_move);
+            continue;
+        };
+
+        let mut components = game.components(&ids.move_prefix, false);
+        components.push(CreateActionRow::Buttons(vec![
+            CreateButton::new(&ids.forfeit)
+                .label("Forfeit")
+                .style(ButtonStyle::Secondary),
+        ]));
+        reply_handle
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content("")
+                    .embed(build_board_embed(
+                        ctx,
+                        players,
+                        game,
+                        &format!("{}'s turn!", current_player.mention()),
+                    ))
+                    .components(components),
+            )
+            .await?;
+
+        let sent_msg = reply_handle.message().await?;
+        let move_end_time = Instant::now() + Duration::from_secs(MOVE_TIMEOUT);
+        loop {
+            let remaining = move_end_time.saturating_duration_since(Instant::now());
+            let Some(interaction) = sent_msg
+                .await_component_interaction(ctx)
+                .timeout(remaining)
+                .await
+            else {
+                return Ok(RoundResult::Forfeited {
+                    loser: player_index,
+                });
+            };
+
+            let interacting_player = players.iter().position(|player| {
+                player
+                    .as_ref()
+                    .is_some_and(|user| user.id == interaction.user.id)
+            });
+
+            if interaction.data.custom_id == ids.forfeit {
+                match interacting_player {
+                    Some(loser) => {
+                        interaction
+                            .create_response(ctx.http(), CreateInteractionResponse::Acknowledge)
+                            .await?;
+                        return Ok(RoundResult::Forfeited { loser });
+                    }
+                    None => {
+                        respond_ephemeral(ctx, &interaction, "You're not playing this game!")
+                            .await?;
+                        continue;
+                    }
+                }
+            }
+
+            if interacting_player != Some(player_index) {
+                respond_ephemeral(ctx, &interaction, "It's not your turn!").await?;
+                continue;
+            }
+
+            match parse_move(&interaction, &ids.move_prefix)
+                .filter(|game_move| game.valid_moves().contains(game_move))
+            {
+                Some(game_move) => {
+                    interaction
+                        .create_response(ctx.http(), CreateInteractionResponse::Acknowledge)
+                        .await?;
+                    game.play(game_move);
+                    break;
+                }
+                None => {
+                    respond_ephemeral(ctx, &interaction, "You can't move there!").await?;
+                }
+            }
+        }
+    }
+}
+
+async fn await_rematch<G: TurnBasedGame>(
+    ctx: Context<'_>,
+    reply_handle: &ReplyHandle<'_>,
+    players: &Players,
+    game: &G,
+    result: RoundResult,
+    ids: &ComponentIds,
+) -> anyhow::Result<bool> {
+    let player_name = |index: usize| {
+        players[index]
+            .as_ref()
+            .map(|user| user.mention().to_string())
+            .unwrap_or_else(|| bot_name(ctx).to_string())
+    };
+    let result_message = match result {
+        RoundResult::Finished(GameOutcome::Win(winner)) => {
+            format!("{} won the game!", player_name(winner))
+        }
+        RoundResult::Forfeited { loser } => format!(
+            "{} forfeited. {} won the game!",
+            player_name(loser),
+            player_name(1 - loser)
+        ),
+        _ => "It's a draw!".to_string(),
+    };
+
+    let embed = build_board_embed(ctx, players, game, &result_message);
+    let mut components = game.components(&ids.move_prefix, true);
+    components.push(CreateActionRow::Buttons(vec![
+        CreateButton::new(&ids.rematch)
+            .label("Rematch")
+            .style(ButtonStyle::Success),
+    ]));
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content("")
+                .embed(embed.clone())
+                .components(components),
+        )
+        .await?;
+
+    let sent_msg = reply_handle.message().await?;
+    let rematch_end_time = Instant::now() + Duration::from_secs(REMATCH_TIMEOUT);
+    loop {
+        let remaining = rematch_end_time.saturating_duration_since(Instant::now());
+        let Some(interaction) = sent_msg
+            .await_component_interaction(ctx)
+            .timeout(remaining)
+            .await
+        else {
+            reply_handle
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content("")
+                        .embed(embed)
+                        .components(game.components(&ids.move_prefix, true)),
+                )
+                .await?;
+            return Ok(false);
+        };
+
+        let is_player = players
+            .iter()
+            .flatten()
+            .any(|user| user.id == interaction.user.id);
+        if interaction.data.custom_id != ids.rematch || !is_player {
+            respond_ephemeral(ctx, &interaction, "You're not playing this game!").await?;
+            continue;
+        }
+
+        interaction
+            .create_response(ctx.http(), CreateInteractionResponse::Acknowledge)
+            .await?;
+        return Ok(true);
+    }
+}
+
+async fn record_stats<G: TurnBasedGame>(
+    ctx: Context<'_>,
+    players: &Players,
+    winner: Option<usize>,
+) {
+    let winner_id = winner
+        .and_then(|winner| players[winner].as_ref())
+        .map(|user| user.id.get());
+    let participants = players
+        .iter()
+        .flatten()
+        .map(|user| user.id.get())
+        .collect::<Vec<_>>();
+
+    if let Err(e) = update_game_stats(
+        &ctx.data().user_records,
+        ctx.guild_id().map(|id| id.get()),
+        &participants,
+        |user_id, stats| {
+            let board_game_stats = G::stats(stats);
+            board_game_stats.games_played += 1;
+            match winner {
+                None => board_game_stats.draws += 1,
+                Some(_) if winner_id == Some(user_id) => board_game_stats.wins += 1,
+                Some(_) => board_game_stats.losses += 1,
+            }
+        },
+    )
+    .await
+    {
+        tracing::error!("Failed to record {} stats: {}", G::NAME, e);
+    }
+}
+
+fn choose_bot_move<G: TurnBasedGame>(game: &G, difficulty: BotDifficulty) -> Option<usize> {
+    let makes_mistake = match difficulty {
+        BotDifficulty::Easy => true,
+        BotDifficulty::Normal => rand::rng().random_bool(NORMAL_MISTAKE_CHANCE),
+        BotDifficulty::Hard => false,
+    };
+
+    if makes_mistake {
+        game.valid_moves().choose(&mut rand::rng()).copied()
+    } else {
+        game.best_move()
+            .or_else(|| game.valid_moves().choose(&mut rand::rng()).copied())
+    }
+}
+
+fn parse_move(interaction: &ComponentInteraction, move_id_prefix: &str) -> Option<usize> {
+    let suffix = interaction.data.custom_id.strip_prefix(move_id_prefix)?;
+    match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values.first()?.parse().ok(),
+        _ => suffix.parse().ok(),
+    }
+}
+
+async fn respond_ephemeral(
+    ctx: Context<'_>,
+    interaction: &ComponentInteraction,
+    content: &str,
+) -> anyhow::Result<()> {
+    interaction
+        .create_response(
+            ctx.http(),
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+fn build_board_embed<G: TurnBasedGame>(
+    ctx: Context<'_>,
+    players: &Players,
+    game: &G,
+    status: &str,
+) -> CreateEmbed {
+    let player_names = players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| {
+            format!(
+                "{} {}",
+                G::piece(index),
+                player
+                    .as_ref()
+                    .map(|user| user.mention().to_string())
+                    .unwrap_or_else(|| bot_name(ctx).to_string())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" vs ");
+    let description = [player_names, game.render(), status.to_string()]
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let embed = CreateEmbed::new()
+        .title(G::NAME)
+        .description(description)
+        .color(board_game_color(ctx));
+    match G::FOOTER {
+        Some(footer) => embed.footer(CreateEmbedFooter::new(footer)),
+        None => embed,
+    }
+}
+
+fn bot_name(ctx: Context<'_>) -> &'static str {
+    if ctx.data().kou { "Kou" } else { "Taiga" }
+}
+
+fn board_game_color(ctx: Context<'_>) -> Color {
+    if ctx.data().kou {
+        KOU_COLOR
+    } else {
+        TAIGA_COLOR
+    }
+}
@@ -0,0 +1,101 @@
+use crate::commands::game::board_game::{
+    BotDifficulty, GameOutcome, TurnBasedGame, move_button, start_board_game,
+};
+use crate::shared::services::connect_four_service::{COLUMNS, ConnectFourBoard, ROWS};
+use crate::shared::structs::record::game_stats::{BoardGameStats, GameStats};
+use crate::shared::structs::{Context, ContextError};
+use serenity::all::{ButtonStyle, CreateActionRow, User};
+
+const PIECES: [&str; 2] = ["🔴", "🟡"];
+const EMPTY_CELL: &str = "⚫";
+const COLUMN_LABELS: [&str; COLUMNS] = ["1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣"];
+const BUTTONS_PER_ROW: usize = 4;
+
+impl TurnBasedGame for ConnectFourBoard {
+    const NAME: &'static str = "Connect Four";
+    const ID: &'static str = "connect4";
+
+    fn piece(player: usize) -> &'static str {
+        PIECES[player]
+    }
+
+    fn stats(stats: &mut GameStats) -> &mut BoardGameStats {
+        &mut stats.connect_four
+    }
+
+    fn current_player(&self) -> usize {
+        ConnectFourBoard::current_player(self)
+    }
+
+    fn outcome(&self) -> GameOutcome {
+        match self.winner() {
+            Some(winner) => GameOutcome::Win(winner),
+            None if self.is_full() => GameOutcome::Draw,
+            None => GameOutcome::Ongoing,
+        }
+    }
+
+    fn valid_moves(&self) -> Vec<usize> {
+        self.valid_columns()
+    }
+
+    fn play(&mut self, game_move: usize) {
+        self.drop_piece(game_move);
+    }
+
+    fn best_move(&self) -> Option<usize> {
+        ConnectFourBoard::best_move(self)
+    }
+
+    fn render(&self) -> String {
+        let rows = (0..ROWS).map(|row| {
+            (0..COLUMNS)
+                .map(|column| {
+                    self.cell(row, column)
+                        .map(|player| PIECES[player])
+                        .unwrap_or(EMPTY_CELL)
+                })
+                .collect::<String>()
+        });
+
+        std::iter::once(COLUMN_LABELS.concat())
+            .chain(rows)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn components(&self, move_id_prefix: &str, finished: bool) -> Vec<CreateActionRow> {
+        (0..COLUMNS)
+            .collect::<Vec<_>>()
+            .chunks(BUTTONS_PER_ROW)
+            .map(|columns| {
+                CreateActionRow::Buttons(
+                    columns
+                        .iter()
+                        .map(|column| {
+                            move_button(
+                                move_id_prefix,
+                                *column,
+                                &(column + 1).to_string(),
+                                ButtonStyle::Primary,
+                                finished || !self.can_drop(*column),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Play Connect Four with a friend, or with Taiga or Kou.
+#[poise::command(slash_command, guild_only, rename = "connect4")]
+pub async fn connect_four(
+    ctx: Context<'_>,
+    #[description = "The person you want to challenge. Leave empty to play against me."]
+    opponent: Option<User>,
+    #[description = "How well I play when you play against me. Default to normal."]
+    difficulty: Option<BotDifficulty>,
+) -> Result<(), ContextError> {
+    start_board_game(ctx, opponent, difficulty, ConnectFourBoard::default).await
+}
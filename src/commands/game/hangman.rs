@@ -1,39 +1,80 @@
-use crate::shared::structs::game::hangman_question::HANGMAN_QUESTIONS;
+use crate::shared::services::hangman_service::render_gallows;
+use crate::shared::structs::game::game_session::{start_game_session, touch_game_session};
+use crate::shared::structs::game::hangman_question::get_hangman_words;
+use crate::shared::structs::record::game_stats::update_game_stats;
 use crate::shared::structs::{Context, ContextError};
 use crate::shared::utility::{get_author_avatar, get_author_name};
 use poise::CreateReply;
 use rand::prelude::*;
 use serenity::FutureExt;
 use serenity::all::{
-    Color, CommandInteraction, CreateEmbedAuthor, CreateEmbedFooter,
-    CreateInteractionResponseFollowup,
+    ActionRowComponent, ButtonStyle, ChannelId, Color, CommandInteraction, CreateActionRow,
+    CreateAttachment, CreateButton, CreateEmbedAuthor, CreateEmbedFooter, CreateInputText,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    CreateModal, InputTextStyle, Mentionable,
 };
 use serenity::builder::CreateEmbed;
+use serenity::collector::{MessageCollector, ModalInteractionCollector};
 use serenity::futures::prelude::future::BoxFuture;
 use serenity::model::prelude::User;
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 const HANGMAN_COLOR: Color = Color::new(0xffd43b);
 const DEFAULT_MAX_ATTEMPTS: i32 = 10;
+const WORD_GUESS_PENALTY: i32 = 2;
+const HINT_THRESHOLD: i32 = 5;
+const GUESS_TIMEOUT: u64 = 60;
+const JOINING_TIME: u64 = 20;
 const HANGMAN_THUMBNAIL: &str =
     "https://cdn.discordapp.com/attachments/700003813981028433/736202279983513671/unnamed.png";
 const HANGMAN_FOOTER: &str = "Hangman original Python version made by: @Kirito#9286";
-const PROMPT_MESSAGE: &str = "input a letter:";
+const GALLOWS_FILE_NAME: &str = "gallows.png";
+const PROMPT_MESSAGE: &str = "input a letter, or press the button to guess the whole word:";
 const INPUT_ERROR_MESSAGE: &str = "the answer has to be an English letter!";
 const WIN_MESSAGE: &str = "you got the correct answer!";
 const LOSE_MESSAGE: &str = "you lose!";
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, poise::ChoiceParameter)]
+pub enum HangmanMode {
+    #[name = "Solo"]
+    Solo,
+    #[name = "Party (anyone in this channel can guess)"]
+    Party,
+    #[name = "Turns (joined players take turns)"]
+    Turns,
+}
+
+#[derive(Debug, Clone, Default)]
+struct HangmanPlayerRecord {
+    letters_guessed: u32,
+    correct_letters: u32,
+}
+
 #[derive(Debug, Clone)]
 struct HangmanData {
     context: serenity::prelude::Context,
     command: CommandInteraction,
+    channel_id: ChannelId,
     answer: String,
+    category: String,
+    hint: Option<String>,
     author_name: String,
     author_avatar_url: String,
     guesses: Vec<char>,
     attempts_remained: i32,
-    user: User,
-    failures: i32,
+    mode: HangmanMode,
+    players: Vec<User>,
+    turn: usize,
+    skipped_turns: usize,
+    solver: Option<User>,
+    player_records: HashMap<u64, HangmanPlayerRecord>,
+}
+
+#[derive(Clone, Debug)]
+enum HangmanGuess {
+    Letter(char),
+    Word(String),
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -43,9 +84,71 @@ enum HangmanResult {
     Aborted,
 }
 
+impl HangmanData {
+    fn current_player(&self) -> Option<&User> {
+        match self.mode {
+            HangmanMode::Party => None,
+            _ => self.players.get(self.turn % self.players.len().max(1)),
+        }
+    }
+
+    fn target_name(&self) -> String {
+        match self.mode {
+            HangmanMode::Solo => self.author_name.clone(),
+            HangmanMode::Party => "Everyone".to_string(),
+            HangmanMode::Turns => self
+                .current_player()
+                .map(|user| user.mention().to_string())
+                .unwrap_or_else(|| self.author_name.clone()),
+        }
+    }
+
+    fn is_revealed(&self, c: char) -> bool {
+        !c.is_ascii_alphabetic() || self.guesses.contains(&c.to_ascii_uppercase())
+    }
+
+    fn hidden_letters(&self) -> usize {
+        self.answer
+            .chars()
+            .filter(|c| !self.is_revealed(*c))
+            .count()
+    }
+
+    fn masked_word(&self) -> String {
+        self.answer
+            .split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .map(|c| {
+                        if !self.is_revealed(c) {
+                            "\\_".to_string()
+                        } else if c.is_ascii_punctuation() {
+                            format!("\\{c}")
+                        } else {
+                            c.to_ascii_uppercase().to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("   ")
+    }
+
+    fn wrong_guesses(&self) -> i32 {
+        DEFAULT_MAX_ATTEMPTS - self.attempts_remained
+    }
+}
+
 /// Play a hangman game with Taiga or Kou.
 #[poise::command(slash_command)]
-pub async fn hangman(ctx: Context<'_>) -> Result<(), ContextError> {
+pub async fn hangman(
+    ctx: Context<'_>,
+    #[description = "Who can guess. Default to solo."] mode: Option<HangmanMode>,
+    #[description = "Only pick words from this category."]
+    #[autocomplete = "autocomplete_category"]
+    category: Option<String>,
+) -> Result<(), ContextError> {
     let member = ctx.author_member().await.map(|member| match member {
         Cow::Borrowed(m) => m.clone(),
         Cow::Owned(m) => m,
@@ -53,6 +156,28 @@ pub async fn hangman(ctx: Context<'_>) -> Result<(), ContextError> {
     let author = ctx.author();
     let author_name = get_author_name(author, &member);
     let author_avatar_url = get_author_avatar(author);
+    let mode = mode.unwrap_or(HangmanMode::Solo);
+    let guild_id = ctx.guild_id().map(|id| id.get());
+
+    let candidates = get_hangman_words(&*ctx.data().hangman_words.read().await, guild_id)
+        .into_iter()
+        .filter(|word| {
+            category
+                .as_ref()
+                .is_none_or(|category| word.category.eq_ignore_ascii_case(category))
+        })
+        .collect::<Vec<_>>();
+    let Some(hangman_word) = candidates.choose(&mut rand::rng()).cloned() else {
+        ctx.send(CreateReply::default().content("There are no hangman words in this category!"))
+            .await?;
+        return Ok(());
+    };
+
+    let Some(session) = start_game_session(ctx.channel_id().get(), guild_id, "hangman") else {
+        ctx.send(CreateReply::default().content("A game is already running in this channel!"))
+            .await?;
+        return Ok(());
+    };
 
     let reply_handle = ctx
         .send(CreateReply::default().content(format!(
@@ -61,115 +186,155 @@ pub async fn hangman(ctx: Context<'_>) -> Result<(), ContextError> {
         )))
         .await?;
 
-    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-
-    let answer = {
-        let mut rng = rand::rng();
-        HANGMAN_QUESTIONS
-            .choose(&mut rng)
-            .map(|s| s.as_str())
-            .unwrap_or_default()
+    let players = match mode {
+        HangmanMode::Solo => vec![author.clone()],
+        HangmanMode::Party => vec![],
+        HangmanMode::Turns => gather_players(ctx, &reply_handle).await?,
     };
 
-    reply_handle
-        .edit(
-            ctx,
-            CreateReply::default().content(format!(
-                "There are {} letters in this word.",
-                answer.chars().count()
-            )),
-        )
-        .await?;
-
     tokio::time::sleep(std::time::Duration::from_secs(2)).await;
 
-    let word: String = answer.chars().map(|_| "\\_").collect::<Vec<_>>().join(" ");
+    let answer = hangman_word.word.as_str();
+    let word_count = answer.split_whitespace().count();
+    let letter_count = answer.chars().filter(|c| c.is_ascii_alphabetic()).count();
 
     reply_handle
         .edit(
             ctx,
-            CreateReply::default().embed(
-                CreateEmbed::new()
-                    .author(CreateEmbedAuthor::new(&author_name).icon_url(&author_avatar_url))
-                    .description(format!("You have {DEFAULT_MAX_ATTEMPTS} attempts left."))
-                    .color(HANGMAN_COLOR)
-                    .title(&word)
-                    .thumbnail(HANGMAN_THUMBNAIL)
-                    .footer(CreateEmbedFooter::new(HANGMAN_FOOTER)),
-            ),
+            CreateReply::default()
+                .content(if word_count > 1 {
+                    format!(
+                        "There are {letter_count} letters in this {word_count}-word phrase from the category **{}**.",
+                        &hangman_word.category
+                    )
+                } else {
+                    format!(
+                        "There are {letter_count} letters in this word from the category **{}**.",
+                        &hangman_word.category
+                    )
+                })
+                .components(vec![]),
         )
         .await?;
 
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
     if let Context::Application(app_context) = ctx {
-        let user = author.clone();
         let context = ctx.serenity_context().clone();
         let command = app_context.interaction.clone();
         let hangman_data = HangmanData {
             context,
             command,
+            channel_id: ctx.channel_id(),
             answer: answer.to_string(),
+            category: hangman_word.category.clone(),
+            hint: hangman_word.hint.clone(),
             author_name,
             author_avatar_url,
             guesses: vec![],
             attempts_remained: DEFAULT_MAX_ATTEMPTS,
-            user,
-            failures: 0,
+            mode,
+            players,
+            turn: 0,
+            skipped_turns: 0,
+            solver: None,
+            player_records: HashMap::new(),
+        };
+
+        let reply = CreateReply::default()
+            .content("")
+            .embed(build_board_embed(&hangman_data));
+        let reply = match render_gallows(0, DEFAULT_MAX_ATTEMPTS) {
+            Ok(image) => reply.attachment(CreateAttachment::bytes(image, GALLOWS_FILE_NAME)),
+            Err(e) => {
+                tracing::error!("Failed to render hangman gallows: {}", e);
+                reply
+            }
         };
-        let hangman_data_clone = hangman_data.clone();
+        reply_handle.edit(ctx, reply).await?;
+
+        let user_records = ctx.data().user_records.clone();
 
         tokio::spawn(async move {
-            match hangman_loop(hangman_data).await {
-                Ok(game_result) => match game_result {
-                    HangmanResult::Win => {
-                        if let Err(e) = hangman_data_clone
-                            .command
-                            .create_followup(
-                                hangman_data_clone.context.http,
-                                CreateInteractionResponseFollowup::new().content(format!(
-                                    "{}, {}\nThe answer is **{}**!",
-                                    &hangman_data_clone.author_name,
-                                    WIN_MESSAGE,
-                                    &hangman_data_clone.answer
-                                )),
-                            )
-                            .await
-                        {
-                            tracing::error!("{}", e);
-                        }
-                    }
-                    HangmanResult::Lose => {
-                        if let Err(e) = hangman_data_clone
-                            .command
-                            .create_followup(
-                                hangman_data_clone.context.http,
-                                CreateInteractionResponseFollowup::new().content(format!(
-                                    "{}, {}\nThe answer is **{}**!",
-                                    &hangman_data_clone.author_name,
-                                    LOSE_MESSAGE,
-                                    &hangman_data_clone.answer
-                                )),
-                            )
-                            .await
+            let result = tokio::select! {
+                result = hangman_loop(hangman_data) => result,
+                _ = session.cancelled() => return,
+            };
+
+            match result {
+                Ok((game_result, hangman_data)) => {
+                    if !matches!(game_result, HangmanResult::Aborted) {
+                        let mut participants = hangman_data
+                            .players
+                            .iter()
+                            .map(|user| user.id.get())
+                            .chain(hangman_data.player_records.keys().copied())
+                            .collect::<Vec<_>>();
+                        participants.sort_unstable();
+                        participants.dedup();
+
+                        if let Err(e) = update_game_stats(
+                            &user_records,
+                            guild_id,
+                            &participants,
+                            |user_id, stats| {
+                                let record = hangman_data
+                                    .player_records
+                                    .get(&user_id)
+                                    .cloned()
+                                    .unwrap_or_default();
+                                let hangman_stats = &mut stats.hangman;
+                                hangman_stats.games_played += 1;
+                                if matches!(game_result, HangmanResult::Win) {
+                                    hangman_stats.words_solved += 1;
+                                }
+                                hangman_stats.letters_guessed += record.letters_guessed;
+                                hangman_stats.correct_letters += record.correct_letters;
+                            },
+                        )
+                        .await
                         {
-                            tracing::error!("{}", e);
+                            tracing::error!("Failed to record hangman stats: {}", e);
                         }
                     }
-                    HangmanResult::Aborted => {
-                        if let Err(e) = hangman_data_clone
-                            .command
-                            .create_followup(
-                                hangman_data_clone.context.http,
-                                CreateInteractionResponseFollowup::new().content(format!(
-                                    "No input from {} is provided. Game aborted.",
-                                    &hangman_data_clone.author_name
-                                )),
-                            )
-                            .await
-                        {
-                            tracing::error!("{}", e);
-                        }
+
+                    let content = match game_result {
+                        HangmanResult::Win => format!(
+                            "{}, {}\nThe answer is **{}**!",
+                            hangman_data
+                                .solver
+                                .as_ref()
+                                .map(|user| user.mention().to_string())
+                                .unwrap_or_else(|| hangman_data.author_name.clone()),
+                            WIN_MESSAGE,
+                            &hangman_data.answer
+                        ),
+                        HangmanResult::Lose => format!(
+                            "{}, {}\nThe answer is **{}**!",
+                            match hangman_data.mode {
+                                HangmanMode::Solo => hangman_data.author_name.clone(),
+                                _ => "Everyone".to_string(),
+                            },
+                            LOSE_MESSAGE,
+                            &hangman_data.answer
+                        ),
+                        HangmanResult::Aborted => format!(
+                            "No input from {} is provided. Game aborted.",
+                            hangman_data.target_name()
+                        ),
+                    };
+
+                    if let Err(e) = hangman_data
+                        .command
+                        .create_followup(
+                            hangman_data.context.http,
+                            CreateInteractionResponseFollowup::new().content(content),
+                        )
+                        .await
+                    {
+                        tracing::error!("{}", e);
                     }
-                },
+                }
                 Err(e) => {
                     tracing::error!("An error occurred during a hangman game: {}", e);
                 }
@@ -180,131 +345,354 @@ pub async fn hangman(ctx: Context<'_>) -> Result<(), ContextError> {
     Ok(())
 }
 
+async fn autocomplete_category<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let guild_id = ctx.guild_id().map(|id| id.get());
+    let mut categories = get_hangman_words(&*ctx.data().hangman_words.read().await, guild_id)
+        .into_iter()
+        .map(|word| word.category)
+        .collect::<Vec<_>>();
+    categories.sort_unstable();
+    categories.dedup();
+    categories
+        .into_iter()
+        .filter(move |category| category.to_lowercase().starts_with(&partial.to_lowercase()))
+}
+
+async fn gather_players(
+    ctx: Context<'_>,
+    reply_handle: &poise::ReplyHandle<'_>,
+) -> anyhow::Result<Vec<User>> {
+    let join_button_id = format!("hangman_join_{}", ctx.id());
+    let leave_button_id = format!("hangman_leave_{}", ctx.id());
+    let mut players = vec![ctx.author().clone()];
+    let build_content = |players: &[User]| {
+        format!(
+            "Press the button below to join the hangman game! We'll take turns guessing.\nCurrent players: {}",
+            players
+                .iter()
+                .map(|user| user.mention().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(build_content(&players))
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(&join_button_id)
+                        .label("Join")
+                        .style(ButtonStyle::Success),
+                    CreateButton::new(&leave_button_id)
+                        .label("Leave")
+                        .style(ButtonStyle::Secondary),
+                ])]),
+        )
+        .await?;
+
+    let sent_msg = reply_handle.message().await?;
+    let joining_end_time = std::time::Instant::now() + std::time::Duration::from_secs(JOINING_TIME);
+
+    loop {
+        let remaining = joining_end_time.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+
+        let Some(interaction) = sent_msg
+            .await_component_interaction(ctx)
+            .timeout(remaining)
+            .await
+        else {
+            break;
+        };
+
+        if interaction.data.custom_id == join_button_id {
+            if !players.iter().any(|user| user.id == interaction.user.id) {
+                players.push(interaction.user.clone());
+            }
+        } else if interaction.data.custom_id == leave_button_id
+            && interaction.user.id != ctx.author().id
+        {
+            players.retain(|user| user.id != interaction.user.id);
+        }
+
+        interaction
+            .create_response(
+                ctx.http(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new().content(build_content(&players)),
+                ),
+            )
+            .await?;
+    }
+
+    Ok(players)
+}
+
+fn build_board_embed(hangman_data: &HangmanData) -> CreateEmbed {
+    let previous_guesses: String = hangman_data
+        .guesses
+        .iter()
+        .map(|c| format!("'{c}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut description = format!(
+        "You have {} attempts left.",
+        hangman_data.attempts_remained.max(0)
+    );
+    if !previous_guesses.is_empty() {
+        description += &format!("\nYour previous guesses: {previous_guesses}.");
+    }
+    if let Some(hint) = hangman_data
+        .hint
+        .as_ref()
+        .filter(|_| hangman_data.wrong_guesses() >= HINT_THRESHOLD)
+    {
+        description += &format!("\nHint: {hint}");
+    }
+    if hangman_data.mode == HangmanMode::Turns {
+        description += &format!("\nNext up: {}", hangman_data.target_name());
+    }
+
+    CreateEmbed::new()
+        .author(
+            CreateEmbedAuthor::new(&hangman_data.author_name)
+                .icon_url(&hangman_data.author_avatar_url),
+        )
+        .description(description)
+        .color(HANGMAN_COLOR)
+        .title(hangman_data.masked_word())
+        .thumbnail(HANGMAN_THUMBNAIL)
+        .image(format!("attachment://{GALLOWS_FILE_NAME}"))
+        .field("Category", &hangman_data.category, true)
+        .footer(CreateEmbedFooter::new(HANGMAN_FOOTER))
+}
+
 fn hangman_loop(
     mut hangman_data: HangmanData,
-) -> BoxFuture<'static, anyhow::Result<HangmanResult>> {
+) -> BoxFuture<'static, anyhow::Result<(HangmanResult, HangmanData)>> {
     async move {
-        hangman_data.failures = 0;
-
+        touch_game_session(hangman_data.channel_id.get());
+        let word_button_id = format!("hangman_word_{}", hangman_data.command.id);
         let sent_msg = hangman_data
             .command
             .create_followup(
                 hangman_data.context.http.clone(),
                 CreateInteractionResponseFollowup::new()
-                    .content(format!("{}, {}", &hangman_data.author_name, PROMPT_MESSAGE)),
+                    .content(format!("{}, {}", hangman_data.target_name(), PROMPT_MESSAGE))
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new(&word_button_id)
+                            .label("Guess the whole word")
+                            .style(ButtonStyle::Primary),
+                    ])]),
             )
             .await?;
 
-        let user_guess: char;
-        loop {
-            if let Some(user_reply) = hangman_data
-                .user
-                .await_reply(&hangman_data.context)
-                .timeout(std::time::Duration::from_secs(60))
-                .await
-            {
-                if let Some(char) = user_reply.content.chars().next() {
-                    if char.is_ascii_alphabetic() {
-                        user_guess = char.to_ascii_uppercase();
-                        user_reply.delete(hangman_data.context.http.clone()).await?;
-                        break;
-                    }
+        let modal_id = format!("hangman_word_modal_{}", sent_msg.id);
+        let allowed_player_ids = match hangman_data.mode {
+            HangmanMode::Party => vec![],
+            _ => hangman_data
+                .current_player()
+                .map(|user| vec![user.id.get()])
+                .unwrap_or_default(),
+        };
+        let is_allowed = {
+            let allowed_player_ids = allowed_player_ids.clone();
+            move |user_id: u64| allowed_player_ids.is_empty() || allowed_player_ids.contains(&user_id)
+        };
 
-                    user_reply.delete(hangman_data.context.http.clone()).await?;
-                    hangman_data
-                        .command
-                        .edit_followup(
-                            hangman_data.context.http.clone(),
-                            sent_msg.id,
-                            CreateInteractionResponseFollowup::new().content(format!(
-                                "{}, {}",
-                                &hangman_data.author_name, INPUT_ERROR_MESSAGE
-                            )),
-                        )
-                        .await?;
-                    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+        let delay = tokio::time::sleep(std::time::Duration::from_secs(GUESS_TIMEOUT));
+        tokio::pin!(delay);
+
+        let (guesser, guess) = loop {
+            let message_filter = is_allowed.clone();
+            let message_collector = MessageCollector::new(&hangman_data.context)
+                .channel_id(hangman_data.channel_id)
+                .filter(move |m| !m.author.bot && message_filter(m.author.id.get()));
+            let button_collector = sent_msg.await_component_interaction(&hangman_data.context);
+            let modal_collector = ModalInteractionCollector::new(&hangman_data.context)
+                .custom_ids(vec![modal_id.clone()]);
+
+            tokio::select! {
+                _ = &mut delay => {
                     hangman_data
                         .command
-                        .edit_followup(
-                            hangman_data.context.http.clone(),
-                            sent_msg.id,
-                            CreateInteractionResponseFollowup::new().content(format!(
-                                "{}, {}",
-                                &hangman_data.author_name, PROMPT_MESSAGE
-                            )),
-                        )
+                        .delete_followup(hangman_data.context.http.clone(), sent_msg.id)
                         .await?;
+
+                    if hangman_data.mode == HangmanMode::Turns
+                        && hangman_data.skipped_turns + 1 < hangman_data.players.len()
+                    {
+                        hangman_data.skipped_turns += 1;
+                        hangman_data.turn += 1;
+                        return hangman_loop(hangman_data).await;
+                    }
+
+                    return Ok((HangmanResult::Aborted, hangman_data));
+                }
+                maybe_v = message_collector.next() => {
+                    if let Some(user_reply) = maybe_v {
+                        let content = user_reply.content.trim();
+                        let mut chars = content.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) if c.is_ascii_alphabetic() => {
+                                user_reply.delete(hangman_data.context.http.clone()).await?;
+                                break (user_reply.author.clone(), HangmanGuess::Letter(c.to_ascii_uppercase()));
+                            }
+                            _ if hangman_data.mode != HangmanMode::Party => {
+                                user_reply.delete(hangman_data.context.http.clone()).await?;
+                                hangman_data
+                                    .command
+                                    .edit_followup(
+                                        hangman_data.context.http.clone(),
+                                        sent_msg.id,
+                                        CreateInteractionResponseFollowup::new().content(format!(
+                                            "{}, {}",
+                                            hangman_data.target_name(), INPUT_ERROR_MESSAGE
+                                        )),
+                                    )
+                                    .await?;
+                                tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+                                hangman_data
+                                    .command
+                                    .edit_followup(
+                                        hangman_data.context.http.clone(),
+                                        sent_msg.id,
+                                        CreateInteractionResponseFollowup::new().content(format!(
+                                            "{}, {}",
+                                            hangman_data.target_name(), PROMPT_MESSAGE
+                                        )),
+                                    )
+                                    .await?;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                maybe_v = button_collector.next() => {
+                    if let Some(interaction) = maybe_v {
+                        let response = if is_allowed(interaction.user.id.get()) {
+                            CreateInteractionResponse::Modal(
+                                CreateModal::new(&modal_id, "Guess the Whole Word").components(vec![
+                                    CreateActionRow::InputText(
+                                        CreateInputText::new(InputTextStyle::Short, "Word", "word")
+                                            .placeholder(format!("A wrong guess costs {WORD_GUESS_PENALTY} attempts!"))
+                                            .max_length(100),
+                                    ),
+                                ]),
+                            )
+                        } else {
+                            CreateInteractionResponse::Message(
+                                CreateInteractionResponseMessage::new()
+                                    .content("It's not your turn!")
+                                    .ephemeral(true),
+                            )
+                        };
+                        interaction.create_response(hangman_data.context.http.clone(), response).await?;
+                    }
+                }
+                maybe_v = modal_collector.next() => {
+                    if let Some(interaction) = maybe_v {
+                        let word = interaction
+                            .data
+                            .components
+                            .iter()
+                            .flat_map(|row| row.components.iter())
+                            .find_map(|component| match component {
+                                ActionRowComponent::InputText(input) => input.value.clone(),
+                                _ => None,
+                            })
+                            .unwrap_or_default();
+                        interaction
+                            .create_response(hangman_data.context.http.clone(), CreateInteractionResponse::Acknowledge)
+                            .await?;
+                        break (interaction.user.clone(), HangmanGuess::Word(word.trim().to_ascii_uppercase()));
+                    }
                 }
-            } else {
-                hangman_data
-                    .command
-                    .delete_followup(hangman_data.context.http.clone(), sent_msg.id)
-                    .await?;
-                return Ok(HangmanResult::Aborted);
             }
-        }
+        };
 
-        hangman_data.guesses.push(user_guess);
-        hangman_data.guesses.dedup();
-        hangman_data.guesses.sort_unstable();
+        hangman_data.skipped_turns = 0;
+        let answer = hangman_data.answer.to_ascii_uppercase();
+        let normalized_answer = normalize_phrase(&answer);
+        let record = hangman_data
+            .player_records
+            .entry(guesser.id.get())
+            .or_default();
 
-        let word: String = hangman_data
-            .answer
-            .clone()
-            .chars()
-            .map(|c| {
-                if hangman_data.guesses.contains(&c.to_ascii_uppercase()) {
-                    c.to_ascii_uppercase().to_string()
+        match guess {
+            HangmanGuess::Letter(letter) => {
+                if !hangman_data.guesses.contains(&letter) {
+                    record.letters_guessed += 1;
+                    if answer.contains(letter) {
+                        record.correct_letters += 1;
+                    } else {
+                        hangman_data.attempts_remained -= 1;
+                    }
+                    hangman_data.guesses.push(letter);
+                    hangman_data.guesses.sort_unstable();
+                }
+            }
+            HangmanGuess::Word(word) => {
+                if normalize_phrase(&word) == normalized_answer {
+                    for letter in answer.chars().filter(|c| c.is_ascii_alphabetic()) {
+                        if !hangman_data.guesses.contains(&letter) {
+                            hangman_data.guesses.push(letter);
+                        }
+                    }
+                    hangman_data.guesses.sort_unstable();
                 } else {
-                    hangman_data.failures += 1;
-                    "\\_".to_string()
+                    hangman_data.attempts_remained -= WORD_GUESS_PENALTY;
                 }
-            })
-            .collect::<Vec<_>>()
-            .join(" ");
+            }
+        }
 
-        if !hangman_data.answer.contains(user_guess) {
-            hangman_data.attempts_remained -= 1;
+        let is_solved = hangman_data.hidden_letters() == 0;
+        if is_solved {
+            hangman_data.solver = Some(guesser);
+        } else {
+            hangman_data.turn += 1;
         }
 
-        let previous_guesses: String = hangman_data
-            .guesses
-            .clone()
-            .into_iter()
-            .map(|c| format!("'{c}'"))
-            .collect::<Vec<_>>()
-            .join(", ");
+        let followup = CreateInteractionResponseFollowup::new()
+            .content("")
+            .embed(build_board_embed(&hangman_data))
+            .components(vec![]);
+        let followup = match render_gallows(hangman_data.wrong_guesses(), DEFAULT_MAX_ATTEMPTS) {
+            Ok(image) => followup.files(vec![CreateAttachment::bytes(image, GALLOWS_FILE_NAME)]),
+            Err(e) => {
+                tracing::error!("Failed to render hangman gallows: {}", e);
+                followup
+            }
+        };
 
         hangman_data
             .command
-            .edit_followup(
-                hangman_data.context.http.clone(),
-                sent_msg.id,
-                CreateInteractionResponseFollowup::new().content("").embed(
-                    CreateEmbed::new()
-                        .author(
-                            CreateEmbedAuthor::new(&hangman_data.author_name)
-                                .icon_url(&hangman_data.author_avatar_url),
-                        )
-                        .description(format!(
-                            "You have {} attempts left.\nYour previous guesses: {}.",
-                            hangman_data.attempts_remained, previous_guesses
-                        ))
-                        .color(HANGMAN_COLOR)
-                        .title(word)
-                        .thumbnail(HANGMAN_THUMBNAIL)
-                        .footer(CreateEmbedFooter::new(HANGMAN_FOOTER)),
-                ),
-            )
+            .edit_followup(hangman_data.context.http.clone(), sent_msg.id, followup)
             .await?;
 
-        if hangman_data.failures == 0 {
-            return Ok(HangmanResult::Win);
-        } else if hangman_data.attempts_remained == 0 {
-            return Ok(HangmanResult::Lose);
+        if is_solved {
+            return Ok((HangmanResult::Win, hangman_data));
+        } else if hangman_data.attempts_remained <= 0 {
+            return Ok((HangmanResult::Lose, hangman_data));
         }
 
-        hangman_loop(hangman_data.clone()).await
+        hangman_loop(hangman_data).await
     }
     .boxed()
 }
+
+/// Keeps only letters and digits so that spaces and punctuation don't matter when guessing the whole phrase.
+fn normalize_phrase(phrase: &str) -> String {
+    phrase
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
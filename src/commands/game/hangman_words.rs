@@ -0,0 +1,211 @@
+use poise::CreateReply;
+use serenity::all::CreateAttachment;
+
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::game::hangman_question::{
+    DEFAULT_HANGMAN_CATEGORY, HangmanWord, write_hangman_words,
+};
+use crate::shared::structs::{Context, ContextError};
+
+/// Manage the custom hangman words of this server.
+#[poise::command(
+    slash_command,
+    rename = "hangman-words",
+    subcommands("add", "edit", "remove", "list"),
+    subcommand_required,
+    guild_only,
+    category = "Game",
+    custom_data = "RESTRICTED"
+)]
+pub async fn hangman_words(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Add a new word or phrase to this server's hangman words.
+#[poise::command(slash_command, guild_only)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The word or phrase. Spaces and punctuation are revealed automatically."]
+    word: String,
+    #[description = "The category of the word. Default to Camp Buddy."] category: Option<String>,
+    #[description = "A hint shown after several wrong guesses."] hint: Option<String>,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().to_string();
+    let mut new_word = HangmanWord {
+        id: 0,
+        word: word.trim().to_uppercase(),
+        category: category.unwrap_or_else(|| DEFAULT_HANGMAN_CATEGORY.to_string()),
+        hint,
+    };
+
+    if let Err(e) = new_word.validate() {
+        ctx.send(CreateReply::default().content(e)).await?;
+        return Ok(());
+    }
+
+    let id = {
+        let mut hangman_words_write_lock = ctx.data().hangman_words.write().await;
+        let guild_words = hangman_words_write_lock.entry(guild_id).or_default();
+        new_word.id = guild_words
+            .iter()
+            .map(|word| word.id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let id = new_word.id;
+        guild_words.push(new_word);
+        write_hangman_words(&hangman_words_write_lock)?;
+        id
+    };
+
+    ctx.send(CreateReply::default().content(format!("Successfully added the word with ID {id}!")))
+        .await?;
+
+    Ok(())
+}
+
+/// Edit an existing hangman word of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "The ID of the word to edit."] id: u32,
+    #[description = "The new word or phrase."] word: Option<String>,
+    #[description = "The new category of the word."] category: Option<String>,
+    #[description = "The new hint. Leave empty to keep the current one."] hint: Option<String>,
+    #[description = "Whether to remove the hint. Default to false."] clear_hint: Option<bool>,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().to_string();
+    let mut hangman_words_write_lock = ctx.data().hangman_words.write().await;
+    let Some(existing_word) = hangman_words_write_lock
+        .get_mut(&guild_id)
+        .and_then(|words| words.iter_mut().find(|word| word.id == id))
+    else {
+        ctx.send(CreateReply::default().content(format!("There is no word with ID {id}!")))
+            .await?;
+        return Ok(());
+    };
+
+    let mut edited_word = existing_word.clone();
+    if let Some(word) = word {
+        edited_word.word = word.trim().to_uppercase();
+    }
+    if let Some(category) = category {
+        edited_word.category = category;
+    }
+    if clear_hint.unwrap_or(false) {
+        edited_word.hint = None;
+    } else if let Some(hint) = hint {
+        edited_word.hint = Some(hint);
+    }
+
+    if let Err(e) = edited_word.validate() {
+        ctx.send(CreateReply::default().content(e)).await?;
+        return Ok(());
+    }
+
+    *existing_word = edited_word;
+    write_hangman_words(&hangman_words_write_lock)?;
+    drop(hangman_words_write_lock);
+
+    ctx.send(CreateReply::default().content(format!("Successfully edited the word with ID {id}!")))
+        .await?;
+
+    Ok(())
+}
+
+/// Remove a hangman word from this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The ID of the word to remove."] id: u32,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().to_string();
+    let removed = {
+        let mut hangman_words_write_lock = ctx.data().hangman_words.write().await;
+        let removed = hangman_words_write_lock
+            .get_mut(&guild_id)
+            .map(|words| {
+                let original_length = words.len();
+                words.retain(|word| word.id != id);
+                words.len() != original_length
+            })
+            .unwrap_or(false);
+        if removed {
+            write_hangman_words(&hangman_words_write_lock)?;
+        }
+        removed
+    };
+
+    let content = if removed {
+        format!("Successfully removed the word with ID {id}!")
+    } else {
+        format!("There is no word with ID {id}!")
+    };
+    ctx.send(CreateReply::default().content(content)).await?;
+
+    Ok(())
+}
+
+/// List the custom hangman words of this server as a file.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Only list words of this category."] category: Option<String>,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().to_string();
+    let words = ctx
+        .data()
+        .hangman_words
+        .read()
+        .await
+        .get(&guild_id)
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|word| {
+            category
+                .as_ref()
+                .is_none_or(|category| word.category.eq_ignore_ascii_case(category))
+        })
+        .collect::<Vec<_>>();
+
+    if words.is_empty() {
+        ctx.send(CreateReply::default().content(
+            "This server has no custom hangman words yet, so the default Camp Buddy words are used.",
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let content = words
+        .iter()
+        .map(|word| {
+            format!(
+                "{}. {} [{}]{}",
+                word.id,
+                &word.word,
+                &word.category,
+                word.hint
+                    .as_ref()
+                    .map(|hint| format!(" - Hint: {hint}"))
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "This server has {} custom hangman words!",
+                words.len()
+            ))
+            .attachment(CreateAttachment::bytes(
+                content.as_bytes(),
+                "hangman_words.txt",
+            )),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use poise::CreateReply;
+use serenity::all::CreateEmbed;
+
+use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::structs::record::game_stats::{BoardGameStats, GameStats};
+use crate::shared::structs::{Context, ContextError};
+
+const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(Debug, Copy, Clone, poise::ChoiceParameter)]
+pub enum LeaderboardGame {
+    #[name = "quiz"]
+    Quiz,
+    #[name = "hangman"]
+    Hangman,
+    #[name = "tictactoe"]
+    TicTacToe,
+    #[name = "connect4"]
+    ConnectFour,
+    #[name = "reversi"]
+    Reversi,
+}
+
+/// Show the all-time leaderboard of a game in this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn leaderboard(
+    ctx: Context<'_>,
+    #[description = "The game of which you want to see the leaderboard."] game: LeaderboardGame,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or_default();
+
+    let mut rankings = {
+        let user_records_read_lock = ctx.data().user_records.read().await;
+        user_records_read_lock
+            .iter()
+            .filter_map(|(user_id, record)| {
+                record
+                    .games
+                    .of_guild(guild_id)
+                    .and_then(|stats| ranking_entry(game, stats))
+                    .map(|entry| (user_id.clone(), entry))
+            })
+            .collect::<Vec<_>>()
+    };
+    rankings.sort_by(|(_, (key_a, _)), (_, (key_b, _))| key_b.cmp(key_a));
+
+    let description = if rankings.is_empty() {
+        "Nobody has played this game here yet!".to_string()
+    } else {
+        rankings
+            .into_iter()
+            .take(LEADERBOARD_SIZE)
+            .enumerate()
+            .map(|(rank, (user_id, (_, summary)))| {
+                format!("{}) <@{}> - {}", rank + 1, user_id, summary)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let color = if ctx.data().kou {
+        KOU_COLOR
+    } else {
+        TAIGA_COLOR
+    };
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title(format!(
+                    "Leaderboard for `{}`",
+                    poise::ChoiceParameter::name(&game)
+                ))
+                .color(color)
+                .description(description),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn ranking_entry(game: LeaderboardGame, stats: &GameStats) -> Option<((u32, u32), String)> {
+    match game {
+        LeaderboardGame::Quiz if stats.quiz.games_played > 0 => Some((
+            (stats.quiz.wins, stats.quiz.correct_answers),
+            format!(
+                "{} wins, {} correct answers ({:.1}% accuracy)",
+                stats.quiz.wins,
+                stats.quiz.correct_answers,
+                stats.quiz.accuracy()
+            ),
+        )),
+        LeaderboardGame::Hangman if stats.hangman.games_played > 0 => Some((
+            (stats.hangman.words_solved, stats.hangman.correct_letters),
+            format!(
+                "{} words solved out of {} games ({:.1}% accuracy)",
+                stats.hangman.words_solved,
+                stats.hangman.games_played,
+                stats.hangman.accuracy()
+            ),
+        )),
+        LeaderboardGame::TicTacToe => board_game_entry(&stats.tictactoe),
+        LeaderboardGame::ConnectFour => board_game_entry(&stats.connect_four),
+        LeaderboardGame::Reversi => board_game_entry(&stats.reversi),
+        _ => None,
+    }
+}
+
+fn board_game_entry(stats: &BoardGameStats) -> Option<((u32, u32), String)> {
+    (stats.games_played > 0).then(|| {
+        (
+            (stats.wins, stats.draws),
+            format!(
+                "{} wins, {} losses, {} draws ({:.1}% win rate)",
+                stats.wins,
+                stats.losses,
+                stats.draws,
+                stats.win_rate()
+            ),
+        )
+    })
+}
@@ -1,14 +1,38 @@
+use crate::commands::game::abort::abort;
+use crate::commands::game::connect_four::connect_four;
 use crate::commands::game::hangman::hangman;
+use crate::commands::game::leaderboard::leaderboard;
 use crate::commands::game::quiz::quiz;
+use crate::commands::game::reversi::reversi;
+use crate::commands::game::tictactoe::tictactoe;
+use crate::commands::game::who_said_it::who_said_it;
 use crate::shared::structs::{Context, ContextError};
 
+pub mod abort;
+pub mod board_game;
+pub mod connect_four;
 pub mod hangman;
+pub mod hangman_words;
+pub mod leaderboard;
 pub mod quiz;
+pub mod quiz_bank;
+pub mod reversi;
+pub mod tictactoe;
+pub mod who_said_it;
 
 /// Play mini games with Kou/Taiga.
 #[poise::command(
     slash_command,
-    subcommands("quiz", "hangman"),
+    subcommands(
+        "quiz",
+        "hangman",
+        "tictactoe",
+        "connect_four",
+        "reversi",
+        "who_said_it",
+        "leaderboard",
+        "abort"
+    ),
     subcommand_required,
     category = "Game"
 )]
@@ -1,19 +1,23 @@
 #![allow(clippy::too_many_arguments)]
 use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::structs::game::game_session::{start_game_session, touch_game_session};
+use crate::shared::structs::game::quiz_question::{QuizDifficulty, QuizFilter, QuizQuestionKind};
+use crate::shared::structs::record::game_stats::update_game_stats;
 use crate::shared::structs::{Context, ContextData, ContextError};
 use chrono::{Duration, Utc};
-use once_cell::sync::OnceCell;
 use poise::{ApplicationContext, CreateReply};
 use rand::prelude::*;
 use serenity::all::{
-    Color, ComponentInteractionDataKind, CreateActionRow, CreateInteractionResponse,
-    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateSelectMenuKind,
-    CreateSelectMenuOption, Mentionable, Message, ReactionType, User, UserId,
+    ActionRowComponent, ButtonStyle, Color, CreateActionRow, CreateButton, CreateInputText,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    CreateModal, InputTextStyle, Mentionable, Message, User,
 };
-use serenity::builder::{CreateEmbed, CreateSelectMenu};
-use serenity::collector::MessageCollector;
-use std::collections::{HashMap, HashSet};
-use tokio::sync::RwLock;
+use serenity::builder::CreateEmbed;
+use serenity::collector::ModalInteractionCollector;
+use serenity::futures::StreamExt;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::time::Instant;
 
 const TAIGA_RESPONSES: [&str; 5] = [
     "Nice one!",
@@ -31,57 +35,127 @@ const KOU_RESPONSES: [&str; 5] = [
     "Great! <:KouSmug:736061465848578091>",
 ];
 
-const DEFAULT_ROUNDS: i32 = 7;
+const DEFAULT_ROUNDS: usize = 7;
+const MAX_ROUNDS: usize = 20;
 const STALE_TIMEOUT: u64 = 30;
+const JOINING_TIME: i64 = 20;
+const MAX_BUTTON_LABEL_LENGTH: usize = 80;
+const MAX_ANSWER_LENGTH: u16 = 100;
+
+#[derive(Debug, Copy, Clone, Default)]
+struct PlayerScore {
+    points: u8,
+    wrong_answers: u8,
+    fastest_answer: Option<std::time::Duration>,
+}
 
-static ONGOING_QUIZZES: OnceCell<RwLock<HashSet<u64>>> = OnceCell::new();
+impl PlayerScore {
+    fn add_correct_answer(&mut self, elapsed: std::time::Duration) {
+        self.points += 1;
+        self.fastest_answer = Some(
+            self.fastest_answer
+                .map_or(elapsed, |fastest| fastest.min(elapsed)),
+        );
+    }
+}
 
 /// Play a fun quiz with your friends. Optionally specify rounds (default 7).
 #[poise::command(slash_command)]
 pub async fn quiz(
     ctx: Context<'_>,
-    #[description = "Rounds you want to play."] rounds: Option<i32>,
+    #[description = "Rounds you want to play."]
+    #[min = 1]
+    #[max = 20]
+    rounds: Option<usize>,
+    #[description = "Only ask questions from this category."]
+    #[autocomplete = "autocomplete_category"]
+    category: Option<String>,
+    #[description = "Only ask questions of this difficulty."] difficulty: Option<QuizDifficulty>,
+    #[description = "Whether to skip questions containing spoilers. Default to false."]
+    spoiler_free: Option<bool>,
 ) -> Result<(), ContextError> {
     let is_kou = ctx.data().kou;
 
-    {
-        let ongoing_quizzes = ONGOING_QUIZZES.get_or_init(|| RwLock::new(HashSet::new()));
-
-        let ongoing_quizzes_read_lock = ongoing_quizzes.read().await;
-        if ongoing_quizzes_read_lock.contains(&ctx.channel_id().get()) {
-            ctx.send(CreateReply::default().content("A game is already running in this channel!"))
-                .await?;
-            return Ok(());
-        }
-    }
-
     if ctx.guild_id().is_none() {
         ctx.send(CreateReply::default().content("The quiz game can only be started in a guild!"))
             .await?;
         return Ok(());
     }
 
+    let filter = QuizFilter {
+        category,
+        difficulty,
+        spoiler_free: spoiler_free.unwrap_or(false),
+    };
+
+    let has_questions = ctx
+        .data()
+        .quiz_questions
+        .read()
+        .await
+        .iter()
+        .any(|question| filter.matches(question));
+    if !has_questions {
+        ctx.send(CreateReply::default().content("There are no questions matching your filters!"))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(session) = start_game_session(
+        ctx.channel_id().get(),
+        ctx.guild_id().map(|id| id.get()),
+        "quiz",
+    ) else {
+        ctx.send(CreateReply::default().content("A game is already running in this channel!"))
+            .await?;
+        return Ok(());
+    };
+
     let color = if is_kou { KOU_COLOR } else { TAIGA_COLOR };
-    new_game(ctx, rounds, color, is_kou).await?;
+    tokio::select! {
+        result = new_game(ctx, rounds, color, is_kou, &filter) => result?,
+        _ = session.cancelled() => {}
+    }
 
     Ok(())
 }
 
+async fn autocomplete_category<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let mut categories = ctx
+        .data()
+        .quiz_questions
+        .read()
+        .await
+        .iter()
+        .map(|question| question.category.clone())
+        .collect::<Vec<_>>();
+    categories.sort_unstable();
+    categories.dedup();
+    categories
+        .into_iter()
+        .filter(move |category| category.to_lowercase().starts_with(&partial.to_lowercase()))
+}
+
 async fn new_game(
     ctx: Context<'_>,
-    rounds: Option<i32>,
+    rounds: Option<usize>,
     color: Color,
     is_kou: bool,
+    filter: &QuizFilter,
 ) -> anyhow::Result<()> {
-    let max_rounds = rounds.unwrap_or(DEFAULT_ROUNDS);
+    let max_rounds = rounds.unwrap_or(DEFAULT_ROUNDS).clamp(1, MAX_ROUNDS);
     ctx.send(
         CreateReply::default().content(format!("Starting a game with {max_rounds} rounds...")),
     )
     .await?;
 
     if let Context::Application(app_context) = ctx {
-        if let Ok(players) = join_game(ctx, color, is_kou).await {
-            if let Ok(score_board) = progress_game(ctx, is_kou, &players, max_rounds).await {
+        if let Ok(players) = join_game(ctx, color, is_kou, filter).await {
+            if let Ok(score_board) = progress_game(ctx, is_kou, &players, max_rounds, filter).await
+            {
                 finalize(
                     ctx,
                     app_context,
@@ -102,83 +176,71 @@ async fn new_game(
     Ok(())
 }
 
-async fn join_game(ctx: Context<'_>, color: Color, is_kou: bool) -> anyhow::Result<Vec<User>> {
-    {
-        let ongoing_quizzes = ONGOING_QUIZZES
-            .get()
-            .expect("Failed to get ongoing quizzes.");
-        let mut ongoing_quizzes_write_lock = ongoing_quizzes.write().await;
-        ongoing_quizzes_write_lock.insert(ctx.channel_id().get());
-    }
-
-    let joining_end_time = Utc::now() + Duration::seconds(10);
-    let description = format!(
-        "React below to join the game!\nThis game may contain spoilers{}.\nCurrent players:{}\n{} seconds left!",
-        if is_kou { "" } else { " or NSFW themes" },
-        "",
-        (joining_end_time - Utc::now()).num_seconds()
-    );
-
+async fn join_game(
+    ctx: Context<'_>,
+    color: Color,
+    is_kou: bool,
+    filter: &QuizFilter,
+) -> anyhow::Result<Vec<User>> {
+    let joining_end_time = Utc::now() + Duration::seconds(JOINING_TIME);
+    let join_button_id = format!("quiz_join_{}", ctx.id());
+    let leave_button_id = format!("quiz_leave_{}", ctx.id());
+    let join_buttons = vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(&join_button_id)
+            .label("Join")
+            .style(ButtonStyle::Success),
+        CreateButton::new(&leave_button_id)
+            .label("Leave")
+            .style(ButtonStyle::Secondary),
+    ])];
+
+    let description = build_join_description(is_kou, filter, &[], joining_end_time.timestamp());
     let embed = build_embed("Minigame Starting!", &description, color, None);
     if let Context::Application(app_context) = ctx {
         let sent_msg = app_context
             .interaction
             .create_followup(
                 ctx.http(),
-                CreateInteractionResponseFollowup::new().add_embed(embed),
+                CreateInteractionResponseFollowup::new()
+                    .add_embed(embed)
+                    .components(join_buttons),
             )
             .await?;
-        sent_msg
-            .react(ctx.http(), ReactionType::Unicode("🇴".to_string()))
-            .await?;
 
         let mut users: Vec<User> = vec![];
         loop {
-            let user_mentions = users
-                .iter()
-                .map(|u| u.mention().to_string())
-                .collect::<Vec<_>>();
+            let remaining = (joining_end_time - Utc::now()).to_std().unwrap_or_default();
+            if remaining.is_zero() {
+                break;
+            }
 
-            let description = format!(
-                "React below to join the game!\nThis game may contain spoilers{}.\nCurrent players:{}\n{} seconds left!",
-                if is_kou { "" } else { " or NSFW themes" },
-                user_mentions.join(", "),
-                (joining_end_time - Utc::now()).num_seconds()
-            );
+            let Some(interaction) = sent_msg
+                .await_component_interaction(ctx)
+                .timeout(remaining)
+                .await
+            else {
+                break;
+            };
 
-            let embed = build_embed("Minigame Starting!", &description, color, None);
+            if interaction.data.custom_id == join_button_id {
+                if !users.iter().any(|u| u.id == interaction.user.id) {
+                    users.push(interaction.user.clone());
+                }
+            } else if interaction.data.custom_id == leave_button_id {
+                users.retain(|u| u.id != interaction.user.id);
+            }
 
-            let sent_msg = app_context
-                .interaction
-                .edit_followup(
+            let description =
+                build_join_description(is_kou, filter, &users, joining_end_time.timestamp());
+            let embed = build_embed("Minigame Starting!", &description, color, None);
+            interaction
+                .create_response(
                     ctx.http(),
-                    sent_msg.id,
-                    CreateInteractionResponseFollowup::new().embed(embed),
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new().embed(embed),
+                    ),
                 )
                 .await?;
-
-            let reactions_collector = sent_msg
-                .await_reactions(ctx)
-                .timeout(std::time::Duration::from_secs(2));
-
-            if let Some(reaction) = reactions_collector.next().await {
-                if reaction.emoji.as_data() == "🇴" {
-                    users = reaction
-                        .users(
-                            ctx.http(),
-                            reaction.emoji.clone(),
-                            None::<u8>,
-                            None::<UserId>,
-                        )
-                        .await
-                        .unwrap_or_default();
-                    users = users.into_iter().filter(|u| !u.bot).collect::<Vec<_>>();
-                }
-            }
-
-            if Utc::now() > joining_end_time {
-                break;
-            }
         }
 
         if users.is_empty() {
@@ -193,6 +255,30 @@ async fn join_game(ctx: Context<'_>, color: Color, is_kou: bool) -> anyhow::Resu
     }
 }
 
+fn build_join_description(
+    is_kou: bool,
+    filter: &QuizFilter,
+    users: &[User],
+    start_timestamp: i64,
+) -> String {
+    let warning = match (filter.spoiler_free, is_kou) {
+        (true, true) => "Spoiler-free mode is on.",
+        (true, false) => "Spoiler-free mode is on, but this game may contain NSFW themes.",
+        (false, true) => "This game may contain spoilers.",
+        (false, false) => "This game may contain spoilers or NSFW themes.",
+    };
+
+    let user_mentions = users
+        .iter()
+        .map(|u| u.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "Press the button below to join the game!\n{warning}\n\n{filter}\n\nCurrent players: {user_mentions}\nThe game starts <t:{start_timestamp}:R>!"
+    )
+}
+
 fn build_embed(
     title: &str,
     description: &str,
@@ -233,7 +319,9 @@ async fn start_game(
         .edit_followup(
             ctx.http(),
             sent_msg.id,
-            CreateInteractionResponseFollowup::new().embed(embed),
+            CreateInteractionResponseFollowup::new()
+                .embed(embed)
+                .components(vec![]),
         )
         .await?;
     Ok(())
@@ -262,7 +350,9 @@ async fn cancel_game(
         .edit_followup(
             ctx.http(),
             sent_msg.id,
-            CreateInteractionResponseFollowup::new().embed(embed),
+            CreateInteractionResponseFollowup::new()
+                .embed(embed)
+                .components(vec![]),
         )
         .await?;
     Ok(())
@@ -272,49 +362,109 @@ async fn progress_game(
     ctx: Context<'_>,
     is_kou: bool,
     players: &[User],
-    max_rounds: i32,
-) -> anyhow::Result<HashMap<u64, u8>> {
+    max_rounds: usize,
+    filter: &QuizFilter,
+) -> anyhow::Result<HashMap<u64, PlayerScore>> {
     let player_ids = players.iter().map(|u| u.id.get()).collect::<Vec<_>>();
     let mut score_board = player_ids
         .iter()
-        .map(|id| (*id, 0_u8))
+        .map(|id| (*id, PlayerScore::default()))
         .collect::<HashMap<_, _>>();
     if let Context::Application(app_context) = ctx {
         let quiz_questions = {
+            let quiz_questions_read_lock = ctx.data().quiz_questions.read().await;
             let mut rng = rand::rng();
-            ctx.data()
-                .quiz_questions
-                .choose_multiple(&mut rng, max_rounds as usize)
-                .zip(1..=max_rounds)
+            quiz_questions_read_lock
+                .iter()
+                .filter(|question| filter.matches(question))
+                .choose_multiple(&mut rng, max_rounds)
+                .into_iter()
+                .cloned()
                 .collect::<Vec<_>>()
         };
 
-        let mut result: anyhow::Result<()> = Ok(());
-        for (question, _) in quiz_questions.into_iter() {
-            if question.question_type.as_str() == "FILL" {
-                result = build_fill_question(
-                    ctx,
-                    app_context,
-                    is_kou,
-                    &mut score_board,
-                    &question.question,
-                    &question.answers,
-                    &player_ids,
-                )
-                .await;
-            } else if question.question_type.as_str() == "MULTIPLE" {
-                result = build_multiple_choice_question(
-                    ctx,
-                    app_context,
-                    is_kou,
-                    &mut score_board,
-                    &question.question,
-                    &question.answers.first().cloned().unwrap_or_default(),
-                    &question.wrong,
-                    &player_ids,
-                )
-                .await;
-            }
+        for question in quiz_questions.into_iter() {
+            touch_game_session(ctx.channel_id().get());
+            let result = match question.kind {
+                QuizQuestionKind::Fill { ref answers } => {
+                    build_fill_question(
+                        ctx,
+                        app_context,
+                        is_kou,
+                        &mut score_board,
+                        &question.question,
+                        None,
+                        answers,
+                        &player_ids,
+                    )
+                    .await
+                }
+                QuizQuestionKind::Image {
+                    ref image_url,
+                    ref answers,
+                } => {
+                    build_fill_question(
+                        ctx,
+                        app_context,
+                        is_kou,
+                        &mut score_board,
+                        &question.question,
+                        Some(image_url),
+                        answers,
+                        &player_ids,
+                    )
+                    .await
+                }
+                QuizQuestionKind::Ordering { ref items } => {
+                    let (question_text, answers) =
+                        build_ordering_question(&question.question, items);
+                    build_fill_question(
+                        ctx,
+                        app_context,
+                        is_kou,
+                        &mut score_board,
+                        &question_text,
+                        None,
+                        &answers,
+                        &player_ids,
+                    )
+                    .await
+                }
+                QuizQuestionKind::MultipleChoice {
+                    ref answers,
+                    ref wrong,
+                } => {
+                    build_multiple_choice_question(
+                        ctx,
+                        app_context,
+                        is_kou,
+                        &mut score_board,
+                        &question.question,
+                        &answers.first().cloned().unwrap_or_default(),
+                        wrong,
+                        &player_ids,
+                    )
+                    .await
+                }
+                QuizQuestionKind::TrueFalse { answer } => {
+                    let (answer, wrong) = if answer {
+                        ("True", "False")
+                    } else {
+                        ("False", "True")
+                    };
+                    build_multiple_choice_question(
+                        ctx,
+                        app_context,
+                        is_kou,
+                        &mut score_board,
+                        &question.question,
+                        answer,
+                        &[wrong.to_string()],
+                        &player_ids,
+                    )
+                    .await
+                }
+            };
 
             if result.is_err() {
                 return Err(anyhow::anyhow!("Game is cancelled."));
@@ -329,33 +479,52 @@ async fn build_fill_question(
     ctx: Context<'_>,
     app_context: ApplicationContext<'_, ContextData, ContextError>,
     is_kou: bool,
-    score_board: &mut HashMap<u64, u8>,
+    score_board: &mut HashMap<u64, PlayerScore>,
     question: &str,
+    image_url: Option<&str>,
     answers: &[String],
     player_ids: &[u64],
 ) -> anyhow::Result<()> {
-    app_context
+    let answer_button_id = format!("quiz_fill_answer_{}", ctx.id());
+    let followup = CreateInteractionResponseFollowup::new()
+        .content(question)
+        .components(vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(&answer_button_id)
+                .label("Answer")
+                .style(ButtonStyle::Primary),
+        ])]);
+    let followup = if let Some(url) = image_url {
+        followup.embed(CreateEmbed::new().image(url))
+    } else {
+        followup
+    };
+
+    let sent_msg = app_context
         .interaction
-        .create_followup(
-            ctx.http(),
-            CreateInteractionResponseFollowup::new().content(question),
-        )
+        .create_followup(ctx.http(), followup)
         .await?;
 
+    let modal_id = format!("quiz_fill_modal_{}", sent_msg.id);
+    let asked_at = Instant::now();
     let delay = tokio::time::sleep(std::time::Duration::from_secs(STALE_TIMEOUT));
     tokio::pin!(delay);
 
-    let player_ids = player_ids.to_vec();
+    // Both collectors are kept for the whole answer window, so that nothing submitted in between is missed.
+    let button_stream = sent_msg.await_component_interaction(ctx).stream();
+    tokio::pin!(button_stream);
+    let modal_stream = ModalInteractionCollector::new(ctx)
+        .channel_id(ctx.channel_id())
+        .custom_ids(vec![modal_id.clone()])
+        .stream();
+    tokio::pin!(modal_stream);
 
     loop {
-        let cloned_player_ids = player_ids.clone();
-        let collector = MessageCollector::new(ctx)
-            .channel_id(ctx.channel_id())
-            .guild_id(ctx.guild_id().unwrap_or_default())
-            .filter(move |m| cloned_player_ids.contains(&m.author.id.get()));
-
         tokio::select! {
             _ = &mut delay => {
+                app_context
+                    .interaction
+                    .edit_followup(ctx.http(), sent_msg.id, CreateInteractionResponseFollowup::new().components(vec![]))
+                    .await?;
                 app_context
                     .interaction
                     .create_followup(ctx.http(), CreateInteractionResponseFollowup::new()
@@ -363,33 +532,107 @@ async fn build_fill_question(
                     .await?;
                 return Err(anyhow::anyhow!("Game is cancelled."));
             }
-            maybe_v = collector.next() => {
-                if let Some(ref msg) = maybe_v {
-                    if answers.iter()
-                    .map(|s| s.to_lowercase())
-                    .any(|s| s == msg.content.to_lowercase()) {
-                        let random_response = get_random_response(is_kou);
-
-                        app_context
-                            .interaction
-                            .create_followup(ctx.http(), CreateInteractionResponseFollowup::new()
-                            .content(format!("{} {}", msg.author.mention(), random_response)))
-                            .await?;
-                        let score_entry = score_board.entry(msg.author.id.get()).or_default();
-                        *score_entry += 1;
-                        return Ok(());
-                    }
+            Some(interaction) = button_stream.next() => {
+                let response = if player_ids.contains(&interaction.user.id.get()) {
+                    CreateInteractionResponse::Modal(
+                        CreateModal::new(&modal_id, "Your Answer").components(vec![
+                            CreateActionRow::InputText(
+                                CreateInputText::new(InputTextStyle::Short, "Answer", "answer")
+                                    .max_length(MAX_ANSWER_LENGTH),
+                            ),
+                        ]),
+                    )
+                } else {
+                    build_ephemeral_response("You are not in this game!")
+                };
+                interaction.create_response(ctx.http(), response).await?;
+            }
+            Some(interaction) = modal_stream.next() => {
+                let submitted = interaction
+                    .data
+                    .components
+                    .iter()
+                    .flat_map(|row| row.components.iter())
+                    .find_map(|component| match component {
+                        ActionRowComponent::InputText(input) => input.value.clone(),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+
+                if answers
+                    .iter()
+                    .any(|answer| answer.to_lowercase() == submitted.trim().to_lowercase())
+                {
+                    let random_response = get_random_response(is_kou);
+                    interaction
+                        .create_response(ctx.http(), CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                        .content(format!("{} {}", interaction.user.mention(), random_response))))
+                        .await?;
+                    app_context
+                        .interaction
+                        .edit_followup(ctx.http(), sent_msg.id, CreateInteractionResponseFollowup::new().components(vec![]))
+                        .await?;
+                    score_board
+                        .entry(interaction.user.id.get())
+                        .or_default()
+                        .add_correct_answer(asked_at.elapsed());
+                    return Ok(());
                 }
+
+                score_board.entry(interaction.user.id.get()).or_default().wrong_answers += 1;
+                interaction
+                    .create_response(ctx.http(), build_ephemeral_response("That's not the correct answer!"))
+                    .await?;
             }
         }
     }
 }
 
+/// Shuffles the items of an ordering question and returns the question text with the accepted answers,
+/// which are the item numbers in the correct order, e.g. `3 1 2`.
+fn build_ordering_question(question: &str, items: &[String]) -> (String, Vec<String>) {
+    let mut shuffled_items = items.iter().enumerate().collect::<Vec<_>>();
+    {
+        let mut rng = rand::rng();
+        shuffled_items.shuffle(&mut rng);
+    }
+
+    let item_list = shuffled_items
+        .iter()
+        .enumerate()
+        .map(|(no, (_, item))| format!("{}. {}", no + 1, item))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let correct_order = (0..items.len())
+        .filter_map(|original_index| {
+            shuffled_items
+                .iter()
+                .position(|(index, _)| *index == original_index)
+                .map(|no| (no + 1).to_string())
+        })
+        .collect::<Vec<_>>();
+
+    let answers = vec![
+        correct_order.join(" "),
+        correct_order.join(","),
+        correct_order.join(", "),
+        correct_order.join(""),
+    ];
+
+    (
+        format!(
+            "{question}\n{item_list}\nAnswer with the numbers in the correct order, e.g. `1 2 3`."
+        ),
+        answers,
+    )
+}
+
 async fn build_multiple_choice_question(
     ctx: Context<'_>,
     app_context: ApplicationContext<'_, ContextData, ContextError>,
     is_kou: bool,
-    score_board: &mut HashMap<u64, u8>,
+    score_board: &mut HashMap<u64, PlayerScore>,
     question: &str,
     answer: &str,
     wrong_answers: &[String],
@@ -402,10 +645,21 @@ async fn build_multiple_choice_question(
         shuffled_answers.shuffle(&mut rng);
     }
 
-    let answer_options = shuffled_answers
-        .into_iter()
+    let answer_buttons = shuffled_answers
+        .iter()
         .enumerate()
-        .map(|(no, choice)| CreateSelectMenuOption::new(format!("{}. {}", no + 1, choice), choice))
+        .map(|(no, choice)| {
+            let label = format!("{}. {}", no + 1, choice)
+                .chars()
+                .take(MAX_BUTTON_LABEL_LENGTH)
+                .collect::<String>();
+            CreateButton::new(format!("quiz_choice_{no}"))
+                .label(label)
+                .style(ButtonStyle::Secondary)
+        })
+        .collect::<Vec<_>>()
+        .chunks(5)
+        .map(|buttons| CreateActionRow::Buttons(buttons.to_vec()))
         .collect::<Vec<_>>();
 
     let sent_msg = app_context
@@ -414,70 +668,107 @@ async fn build_multiple_choice_question(
             ctx.http(),
             CreateInteractionResponseFollowup::new()
                 .content(question)
-                .components(vec![CreateActionRow::SelectMenu(
-                    CreateSelectMenu::new(
-                        "multiple_choice",
-                        CreateSelectMenuKind::String {
-                            options: answer_options,
-                        },
-                    )
-                    .min_values(1)
-                    .max_values(1)
-                    .placeholder("Pick an answer!"),
-                )]),
+                .components(answer_buttons),
         )
         .await?;
 
-    let delay = tokio::time::sleep(std::time::Duration::from_secs(STALE_TIMEOUT));
-    tokio::pin!(delay);
+    let asked_at = Instant::now();
+    let deadline = asked_at + std::time::Duration::from_secs(STALE_TIMEOUT);
+    let mut locked_in: HashMap<u64, (bool, std::time::Duration)> = HashMap::new();
 
-    let player_ids = player_ids.to_vec();
+    while locked_in.len() < player_ids.len() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
 
-    loop {
-        let cloned_player_ids = player_ids.clone();
-        let collector = sent_msg
+        let Some(interaction) = sent_msg
             .await_component_interaction(ctx)
-            .channel_id(ctx.channel_id())
-            .guild_id(ctx.guild_id().unwrap_or_default())
-            .filter(move |interaction| cloned_player_ids.contains(&interaction.user.id.get()));
+            .timeout(remaining)
+            .await
+        else {
+            break;
+        };
 
-        tokio::select! {
-            _ = &mut delay => {
-                app_context.interaction.create_followup(ctx.http(), CreateInteractionResponseFollowup::new().content("Cancelling stale game...")).await?;
-                return Err(anyhow::anyhow!("Game is cancelled."));
-            }
-            maybe_v = collector.next() => {
-                if let Some(ref interaction) = maybe_v {
-                    if let ComponentInteractionDataKind::StringSelect {
-                        values
-                    } = interaction.data.kind.clone() {
-                        let value = values[0].as_str();
-                        if value == answer {
-                            let random_response = get_random_response(is_kou);
-
-                            interaction
-                                .create_response(ctx.http(), CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                .content(format!("{} {}", interaction.user.mention(), random_response))))
-                                .await?;
-
-                            let score_entry = score_board.entry(interaction.user.id.get()).or_default();
-                            *score_entry += 1;
-                            app_context
-                                .interaction
-                                .delete_followup(ctx.http(), sent_msg.id).await?;
-                            return Ok(());
-                        } else {
-                            app_context
-                                .interaction
-                                .create_response(ctx.http(), CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                .content(format!("{}, that's not the correct answer!", interaction.user.mention()))))
-                                .await?;
-                        }
-                    }
-                }
-            }
+        let user_id = interaction.user.id.get();
+        let response = if !player_ids.contains(&user_id) {
+            build_ephemeral_response("You are not in this game!")
+        } else if let Entry::Vacant(entry) = locked_in.entry(user_id) {
+            let choice = interaction
+                .data
+                .custom_id
+                .strip_prefix("quiz_choice_")
+                .and_then(|no| no.parse::<usize>().ok())
+                .and_then(|no| shuffled_answers.get(no))
+                .cloned()
+                .unwrap_or_default();
+            entry.insert((choice == answer, asked_at.elapsed()));
+            build_ephemeral_response(&format!("You locked in **{choice}**!"))
+        } else {
+            build_ephemeral_response("You have already locked in your answer!")
+        };
+        interaction.create_response(ctx.http(), response).await?;
+    }
+
+    app_context
+        .interaction
+        .edit_followup(
+            ctx.http(),
+            sent_msg.id,
+            CreateInteractionResponseFollowup::new().components(vec![]),
+        )
+        .await?;
+
+    if locked_in.is_empty() {
+        app_context
+            .interaction
+            .create_followup(
+                ctx.http(),
+                CreateInteractionResponseFollowup::new().content("Cancelling stale game..."),
+            )
+            .await?;
+        return Err(anyhow::anyhow!("Game is cancelled."));
+    }
+
+    let mut winners = vec![];
+    for (user_id, (correct, elapsed)) in locked_in.into_iter() {
+        let score_entry = score_board.entry(user_id).or_default();
+        if correct {
+            score_entry.add_correct_answer(elapsed);
+            winners.push(format!("<@{user_id}>"));
+        } else {
+            score_entry.wrong_answers += 1;
         }
     }
+
+    let content = if winners.is_empty() {
+        format!("The correct answer is **{answer}**! Nobody got it right...")
+    } else {
+        format!(
+            "The correct answer is **{}**! {} {}",
+            answer,
+            winners.join(", "),
+            get_random_response(is_kou)
+        )
+    };
+
+    app_context
+        .interaction
+        .create_followup(
+            ctx.http(),
+            CreateInteractionResponseFollowup::new().content(content),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn build_ephemeral_response(content: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
 }
 
 async fn finalize(
@@ -485,19 +776,13 @@ async fn finalize(
     app_context: ApplicationContext<'_, ContextData, ContextError>,
     color: Color,
     is_kou: bool,
-    score_board: Option<HashMap<u64, u8>>,
+    score_board: Option<HashMap<u64, PlayerScore>>,
     players: Option<&[User]>,
 ) -> anyhow::Result<()> {
-    {
-        let ongoing_quizzes = ONGOING_QUIZZES
-            .get()
-            .expect("Failed to get ongoing quizzes.");
-        let mut ongoing_quizzes_write_lock = ongoing_quizzes.write().await;
-        ongoing_quizzes_write_lock.remove(&ctx.channel_id().get());
-    }
-
     if let Some(board) = score_board {
         let players = players.expect("Failed to get participating players.");
+        record_quiz_stats(ctx, &board).await?;
+
         let mut score_board = board
             .into_iter()
             .map(|(user_id, score)| {
@@ -508,7 +793,7 @@ async fn finalize(
                         .expect("Failed to map user ID to an user.")
                         .mention()
                         .to_string(),
-                    score,
+                    score.points,
                 )
             })
             .collect::<Vec<_>>();
@@ -543,6 +828,43 @@ async fn finalize(
     Ok(())
 }
 
+async fn record_quiz_stats(
+    ctx: Context<'_>,
+    score_board: &HashMap<u64, PlayerScore>,
+) -> anyhow::Result<()> {
+    let top_points = score_board
+        .values()
+        .map(|score| score.points)
+        .max()
+        .unwrap_or_default();
+    let player_ids = score_board.keys().copied().collect::<Vec<_>>();
+
+    update_game_stats(
+        &ctx.data().user_records,
+        ctx.guild_id().map(|id| id.get()),
+        &player_ids,
+        |user_id, stats| {
+            let score = score_board.get(&user_id).copied().unwrap_or_default();
+            let quiz_stats = &mut stats.quiz;
+            quiz_stats.games_played += 1;
+            if top_points > 0 && score.points == top_points {
+                quiz_stats.wins += 1;
+            }
+            quiz_stats.correct_answers += score.points as u32;
+            quiz_stats.wrong_answers += score.wrong_answers as u32;
+            if let Some(fastest) = score.fastest_answer {
+                let fastest = fastest.as_millis() as u64;
+                quiz_stats.fastest_answer_ms = Some(
+                    quiz_stats
+                        .fastest_answer_ms
+                        .map_or(fastest, |previous| previous.min(fastest)),
+                );
+            }
+        },
+    )
+    .await
+}
+
 fn get_random_response(is_kou: bool) -> &'static str {
     let mut rng = rand::rng();
     if is_kou {
@@ -0,0 +1,291 @@
+#![allow(clippy::too_many_arguments)]
+use poise::CreateReply;
+use serenity::all::{Attachment, CreateAttachment};
+
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::game::quiz_question::{
+    DEFAULT_CATEGORY, QuizDifficulty, QuizQuestion, QuizQuestionKind, QuizQuestionType,
+    assign_question_ids, write_quiz_questions,
+};
+use crate::shared::structs::{Context, ContextError};
+
+const ANSWER_SEPARATOR: char = '|';
+
+/// Manage the questions of the quiz game.
+#[poise::command(
+    slash_command,
+    rename = "quiz-bank",
+    subcommands("add", "edit", "remove", "import", "export"),
+    subcommand_required,
+    category = "Game",
+    custom_data = "RESTRICTED"
+)]
+pub async fn quiz_bank(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Add a new question to the quiz.
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The type of the question."] question_type: QuizQuestionType,
+    #[description = "The question."] question: String,
+    #[description = "Correct answers separated by |. For ordering questions, the items in the correct order."]
+    answers: String,
+    #[description = "Wrong answers separated by |. Only used by multiple choice questions."]
+    wrong: Option<String>,
+    #[description = "The URL of the image. Only used by image questions."] image_url: Option<
+        String,
+    >,
+    #[description = "The difficulty of the question. Default to Normal."] difficulty: Option<
+        QuizDifficulty,
+    >,
+    #[description = "The category of the question. Default to General."] category: Option<String>,
+    #[description = "Whether the question contains spoilers. Default to false."] spoiler: Option<
+        bool,
+    >,
+) -> Result<(), ContextError> {
+    let kind = match QuizQuestionKind::from_parts(
+        question_type,
+        split_answers(&answers),
+        wrong.as_deref().map(split_answers).unwrap_or_default(),
+        image_url,
+    ) {
+        Ok(kind) => kind,
+        Err(e) => {
+            ctx.send(CreateReply::default().content(e)).await?;
+            return Ok(());
+        }
+    };
+
+    let mut new_question = QuizQuestion {
+        id: 0,
+        question,
+        kind,
+        difficulty: difficulty.unwrap_or_default(),
+        category: category.unwrap_or_else(|| DEFAULT_CATEGORY.to_string()),
+        spoiler: spoiler.unwrap_or(false),
+    };
+
+    if let Err(e) = new_question.validate() {
+        ctx.send(CreateReply::default().content(e)).await?;
+        return Ok(());
+    }
+
+    let id = {
+        let mut quiz_questions_write_lock = ctx.data().quiz_questions.write().await;
+        new_question.id = quiz_questions_write_lock
+            .iter()
+            .map(|question| question.id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let id = new_question.id;
+        quiz_questions_write_lock.push(new_question);
+        write_quiz_questions(ctx.data().kou, &quiz_questions_write_lock)?;
+        id
+    };
+
+    ctx.send(
+        CreateReply::default().content(format!("Successfully added the question with ID {id}!")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Edit an existing question of the quiz.
+#[poise::command(slash_command)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "The ID of the question to edit."] id: u32,
+    #[description = "The new question."] question: Option<String>,
+    #[description = "New correct answers separated by |."] answers: Option<String>,
+    #[description = "New wrong answers separated by |."] wrong: Option<String>,
+    #[description = "The new URL of the image."] image_url: Option<String>,
+    #[description = "The new difficulty of the question."] difficulty: Option<QuizDifficulty>,
+    #[description = "The new category of the question."] category: Option<String>,
+    #[description = "Whether the question contains spoilers."] spoiler: Option<bool>,
+) -> Result<(), ContextError> {
+    let mut quiz_questions_write_lock = ctx.data().quiz_questions.write().await;
+    let Some(index) = quiz_questions_write_lock
+        .iter()
+        .position(|question| question.id == id)
+    else {
+        ctx.send(CreateReply::default().content(format!("There is no question with ID {id}!")))
+            .await?;
+        return Ok(());
+    };
+
+    let mut edited_question = quiz_questions_write_lock[index].clone();
+    let (question_type, old_answers, old_wrong, old_image_url) = edited_question.kind.to_parts();
+    let kind = match QuizQuestionKind::from_parts(
+        question_type,
+        answers.as_deref().map(split_answers).unwrap_or(old_answers),
+        wrong.as_deref().map(split_answers).unwrap_or(old_wrong),
+        image_url.or(old_image_url),
+    ) {
+        Ok(kind) => kind,
+        Err(e) => {
+            ctx.send(CreateReply::default().content(e)).await?;
+            return Ok(());
+        }
+    };
+
+    edited_question.kind = kind;
+    if let Some(question) = question {
+        edited_question.question = question;
+    }
+    if let Some(difficulty) = difficulty {
+        edited_question.difficulty = difficulty;
+    }
+    if let Some(category) = category {
+        edited_question.category = category;
+    }
+    if let Some(spoiler) = spoiler {
+        edited_question.spoiler = spoiler;
+    }
+
+    if let Err(e) = edited_question.validate() {
+        ctx.send(CreateReply::default().content(e)).await?;
+        return Ok(());
+    }
+
+    quiz_questions_write_lock[index] = edited_question;
+    write_quiz_questions(ctx.data().kou, &quiz_questions_write_lock)?;
+    drop(quiz_questions_write_lock);
+
+    ctx.send(
+        CreateReply::default().content(format!("Successfully edited the question with ID {id}!")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Remove a question from the quiz.
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The ID of the question to remove."] id: u32,
+) -> Result<(), ContextError> {
+    let removed = {
+        let mut quiz_questions_write_lock = ctx.data().quiz_questions.write().await;
+        let original_length = quiz_questions_write_lock.len();
+        quiz_questions_write_lock.retain(|question| question.id != id);
+        let removed = quiz_questions_write_lock.len() != original_length;
+        if removed {
+            write_quiz_questions(ctx.data().kou, &quiz_questions_write_lock)?;
+        }
+        removed
+    };
+
+    let content = if removed {
+        format!("Successfully removed the question with ID {id}!")
+    } else {
+        format!("There is no question with ID {id}!")
+    };
+    ctx.send(CreateReply::default().content(content)).await?;
+
+    Ok(())
+}
+
+/// Import quiz questions from a JSON file.
+#[poise::command(slash_command)]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "The JSON file containing an array of questions."] file: Attachment,
+    #[description = "Whether to replace all existing questions. Default to false."] replace: Option<
+        bool,
+    >,
+) -> Result<(), ContextError> {
+    ctx.defer().await?;
+
+    let raw_bytes = file.download().await?;
+    let mut imported_questions = match serde_json::from_slice::<Vec<QuizQuestion>>(&raw_bytes) {
+        Ok(questions) => questions,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().content(format!("Failed to parse the question file: {e}")),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let errors = imported_questions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, question)| {
+            question
+                .validate()
+                .err()
+                .map(|e| format!("Question {}: {}", index + 1, e))
+        })
+        .collect::<Vec<_>>();
+
+    if !errors.is_empty() {
+        ctx.send(CreateReply::default().content(format!(
+            "Nothing was imported because some questions are invalid:\n{}",
+            errors.into_iter().take(10).collect::<Vec<_>>().join("\n")
+        )))
+        .await?;
+        return Ok(());
+    }
+
+    let imported_count = imported_questions.len();
+    let total = {
+        let mut quiz_questions_write_lock = ctx.data().quiz_questions.write().await;
+        if replace.unwrap_or(false) {
+            assign_question_ids(&mut imported_questions);
+            *quiz_questions_write_lock = imported_questions;
+        } else {
+            for question in imported_questions.iter_mut() {
+                question.id = 0;
+            }
+            quiz_questions_write_lock.extend(imported_questions);
+            assign_question_ids(&mut quiz_questions_write_lock);
+        }
+        write_quiz_questions(ctx.data().kou, &quiz_questions_write_lock)?;
+        quiz_questions_write_lock.len()
+    };
+
+    ctx.send(CreateReply::default().content(format!(
+        "Successfully imported {imported_count} questions! There are {total} questions in total now."
+    )))
+    .await?;
+
+    Ok(())
+}
+
+/// Export all quiz questions as a JSON file.
+#[poise::command(slash_command)]
+pub async fn export(ctx: Context<'_>) -> Result<(), ContextError> {
+    let (serialized_json, total) = {
+        let quiz_questions_read_lock = ctx.data().quiz_questions.read().await;
+        (
+            serde_json::to_string_pretty(&*quiz_questions_read_lock)?,
+            quiz_questions_read_lock.len(),
+        )
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("Here are all {total} questions of the quiz!"))
+            .attachment(CreateAttachment::bytes(
+                serialized_json.as_bytes(),
+                "quiz_questions.json",
+            )),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn split_answers(answers: &str) -> Vec<String> {
+    answers
+        .split(ANSWER_SEPARATOR)
+        .map(|answer| answer.trim().to_string())
+        .filter(|answer| !answer.is_empty())
+        .collect()
+}
@@ -0,0 +1,116 @@
+use crate::commands::game::board_game::{
+    BotDifficulty, GameOutcome, TurnBasedGame, move_select_menu, start_board_game,
+};
+use crate::shared::services::reversi_service::{BOARD_SIZE, ReversiBoard};
+use crate::shared::structs::record::game_stats::{BoardGameStats, GameStats};
+use crate::shared::structs::{Context, ContextError};
+use serenity::all::{CreateActionRow, User};
+use std::cmp::Ordering;
+
+const PIECES: [&str; 2] = ["⚫", "⚪"];
+const EMPTY_CELL: &str = "🟩";
+const CORNER_LABEL: &str = "⬛";
+const COLUMN_LABELS: [&str; BOARD_SIZE] = ["1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣"];
+const ROW_LABELS: [&str; BOARD_SIZE] = ["🇦", "🇧", "🇨", "🇩", "🇪", "🇫", "🇬", "🇭"];
+const ROW_LETTERS: [char; BOARD_SIZE] = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H'];
+
+impl TurnBasedGame for ReversiBoard {
+    const NAME: &'static str = "Reversi";
+    const ID: &'static str = "reversi";
+
+    fn piece(player: usize) -> &'static str {
+        PIECES[player]
+    }
+
+    fn stats(stats: &mut GameStats) -> &mut BoardGameStats {
+        &mut stats.reversi
+    }
+
+    fn current_player(&self) -> usize {
+        ReversiBoard::current_player(self)
+    }
+
+    fn outcome(&self) -> GameOutcome {
+        if !self.is_game_over() {
+            return GameOutcome::Ongoing;
+        }
+
+        match self.disc_count(0).cmp(&self.disc_count(1)) {
+            Ordering::Greater => GameOutcome::Win(0),
+            Ordering::Less => GameOutcome::Win(1),
+            Ordering::Equal => GameOutcome::Draw,
+        }
+    }
+
+    fn valid_moves(&self) -> Vec<usize> {
+        ReversiBoard::valid_moves(self)
+    }
+
+    fn play(&mut self, game_move: usize) {
+        ReversiBoard::play(self, game_move);
+    }
+
+    fn best_move(&self) -> Option<usize> {
+        ReversiBoard::best_move(self)
+    }
+
+    fn render(&self) -> String {
+        let rows = ROW_LABELS.iter().enumerate().map(|(row, label)| {
+            let cells = (0..BOARD_SIZE)
+                .map(|column| {
+                    self.cell(row * BOARD_SIZE + column)
+                        .map(|player| PIECES[player])
+                        .unwrap_or(EMPTY_CELL)
+                })
+                .collect::<String>();
+            format!("{label}{cells}")
+        });
+
+        std::iter::once(format!("{CORNER_LABEL}{}", COLUMN_LABELS.concat()))
+            .chain(rows)
+            .chain(std::iter::once(format!(
+                "{} {} - {} {}",
+                PIECES[0],
+                self.disc_count(0),
+                self.disc_count(1),
+                PIECES[1]
+            )))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn components(&self, move_id_prefix: &str, finished: bool) -> Vec<CreateActionRow> {
+        if finished {
+            return vec![];
+        }
+
+        let moves = ReversiBoard::valid_moves(self)
+            .into_iter()
+            .map(|game_move| {
+                let label = format!(
+                    "{}{}",
+                    ROW_LETTERS[game_move / BOARD_SIZE],
+                    game_move % BOARD_SIZE + 1
+                );
+                (game_move, label)
+            })
+            .collect::<Vec<_>>();
+        vec![move_select_menu(
+            move_id_prefix,
+            moves,
+            "Choose where to place your disc",
+        )]
+    }
+}
+
+/// Play Reversi with a friend, or with Taiga or Kou.
+#[poise::command(slash_command, guild_only)]
+pub async fn reversi(
+    ctx: Context<'_>,
+    #[description = "The person you want to challenge. Leave empty to play against me."]
+    opponent: Option<User>,
+    #[description = "How well I play when you play against me. Default to normal."]
+    difficulty: Option<BotDifficulty>,
+) -> Result<(), ContextError> {
+    start_board_game(ctx, opponent, difficulty, ReversiBoard::default).await
+}
@@ -0,0 +1,114 @@
+use crate::commands::game::board_game::{
+    BotDifficulty, GameOutcome, TurnBasedGame, move_button, start_board_game,
+};
+use crate::shared::services::tictactoe_service::{
+    BOARD_SIZE, Board, BoardState, Mark, best_move, check_board,
+};
+use crate::shared::structs::record::game_stats::{BoardGameStats, GameStats};
+use crate::shared::structs::{Context, ContextError};
+use serenity::all::{ButtonStyle, CreateActionRow, User};
+
+const EMPTY_SLOT: &str = "□";
+
+/// The first player plays circles and the second plays crosses.
+#[derive(Debug, Clone, Default)]
+struct TicTacToe {
+    board: Board,
+    turn: usize,
+}
+
+impl TurnBasedGame for TicTacToe {
+    const NAME: &'static str = "Tic-tac-toe";
+    const ID: &'static str = "tictactoe";
+    const FOOTER: Option<&'static str> =
+        Some("Tic-tac-toe original Python version made by: @Kirito#9286");
+
+    fn piece(player: usize) -> &'static str {
+        mark_of(player).symbol()
+    }
+
+    fn stats(stats: &mut GameStats) -> &mut BoardGameStats {
+        &mut stats.tictactoe
+    }
+
+    fn current_player(&self) -> usize {
+        self.turn % 2
+    }
+
+    fn outcome(&self) -> GameOutcome {
+        match check_board(&self.board) {
+            BoardState::Ongoing => GameOutcome::Ongoing,
+            BoardState::Win(Mark::Circle) => GameOutcome::Win(0),
+            BoardState::Win(Mark::Cross) => GameOutcome::Win(1),
+            BoardState::Draw => GameOutcome::Draw,
+        }
+    }
+
+    fn valid_moves(&self) -> Vec<usize> {
+        (0..self.board.len())
+            .filter(|index| self.board[*index].is_none())
+            .collect()
+    }
+
+    fn play(&mut self, game_move: usize) {
+        self.board[game_move] = Some(mark_of(self.current_player()));
+        self.turn += 1;
+    }
+
+    fn best_move(&self) -> Option<usize> {
+        best_move(&self.board, mark_of(self.current_player()))
+    }
+
+    fn render(&self) -> String {
+        String::new()
+    }
+
+    fn components(&self, move_id_prefix: &str, finished: bool) -> Vec<CreateActionRow> {
+        self.board
+            .chunks(BOARD_SIZE)
+            .enumerate()
+            .map(|(row, slots)| {
+                CreateActionRow::Buttons(
+                    slots
+                        .iter()
+                        .enumerate()
+                        .map(|(column, slot)| {
+                            let (label, style) = match slot {
+                                Some(Mark::Circle) => (Mark::Circle.symbol(), ButtonStyle::Primary),
+                                Some(Mark::Cross) => (Mark::Cross.symbol(), ButtonStyle::Danger),
+                                None => (EMPTY_SLOT, ButtonStyle::Secondary),
+                            };
+                            move_button(
+                                move_id_prefix,
+                                row * BOARD_SIZE + column,
+                                label,
+                                style,
+                                finished || slot.is_some(),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Play tic-tac-toe with a friend, or with Taiga or Kou.
+#[poise::command(slash_command, guild_only)]
+pub async fn tictactoe(
+    ctx: Context<'_>,
+    #[description = "The person you want to challenge. Leave empty to play against me."]
+    opponent: Option<User>,
+    #[description = "How well I play when you play against me. Default to normal."]
+    difficulty: Option<BotDifficulty>,
+) -> Result<(), ContextError> {
+    start_board_game(ctx, opponent, difficulty, TicTacToe::default).await
+}
+
+fn mark_of(player: usize) -> Mark {
+    if player == 0 {
+        Mark::Circle
+    } else {
+        Mark::Cross
+    }
+}
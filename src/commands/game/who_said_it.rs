@@ -0,0 +1,426 @@
+use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::services::message_service::get_channel_messages;
+use crate::shared::structs::game::game_session::{start_game_session, touch_game_session};
+use crate::shared::structs::record::message::MessageRecordSimple;
+use crate::shared::structs::record::user_record::write_user_records;
+use crate::shared::structs::{Context, ContextError};
+use once_cell::sync::Lazy;
+use poise::CreateReply;
+use rand::prelude::*;
+use regex::Regex;
+use serenity::all::{
+    ButtonStyle, Color, CreateActionRow, CreateButton, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::builder::CreateEmbed;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+const DEFAULT_ROUNDS: usize = 5;
+const MAX_ROUNDS: usize = 10;
+const MIN_QUOTE_LENGTH: usize = 30;
+const MAX_CHOICES: usize = 4;
+const ANSWER_TIME: u64 = 20;
+const MAX_BUTTON_LABEL_LENGTH: usize = 80;
+const REDACTED_NAME: &str = "█████";
+
+static MENTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<@[!&]?\d+>").expect("Failed to build mention regex."));
+
+#[derive(Debug, Clone)]
+struct Quote {
+    user_id: u64,
+    text: String,
+}
+
+/// Guess who said it from anonymized past messages of this server.
+#[poise::command(
+    slash_command,
+    rename = "whosaidit",
+    subcommands("play", "opt_in", "opt_out"),
+    subcommand_required,
+    guild_only
+)]
+pub async fn who_said_it(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Allow your messages in this server to be used in "Who said it?".
+#[poise::command(slash_command, guild_only, rename = "opt-in")]
+pub async fn opt_in(ctx: Context<'_>) -> Result<(), ContextError> {
+    set_opt_in(ctx, true).await?;
+    ctx.send(
+        CreateReply::default()
+            .content("Your messages in this server can now show up in \"Who said it?\"!")
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Stop your messages in this server from being used in "Who said it?".
+#[poise::command(slash_command, guild_only, rename = "opt-out")]
+pub async fn opt_out(ctx: Context<'_>) -> Result<(), ContextError> {
+    set_opt_in(ctx, false).await?;
+    ctx.send(
+        CreateReply::default()
+            .content("Your messages in this server won't show up in \"Who said it?\" anymore.")
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Play "Who said it?" with past messages of this channel.
+#[poise::command(slash_command, guild_only)]
+pub async fn play(
+    ctx: Context<'_>,
+    #[description = "Rounds you want to play. Default to 5."]
+    #[min = 1]
+    #[max = 10]
+    rounds: Option<usize>,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or_default();
+    let channel_id = ctx.channel_id().get();
+
+    let is_nsfw = ctx
+        .guild_channel()
+        .await
+        .is_some_and(|channel| channel.nsfw);
+    let is_ignored = ctx
+        .data()
+        .channel_control
+        .read()
+        .await
+        .is_ignored(guild_id, channel_id);
+    if is_nsfw || is_ignored {
+        ctx.send(
+            CreateReply::default()
+                .content("Quotes can't be taken from this channel!")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let opted_in_user_ids = ctx
+        .data()
+        .user_records
+        .read()
+        .await
+        .iter()
+        .filter(|(_, record)| record.quote_game_guild_ids.contains(&guild_id))
+        .filter_map(|(user_id, _)| user_id.parse::<u64>().ok())
+        .collect::<HashSet<_>>();
+    if opted_in_user_ids.len() < 2 {
+        ctx.send(CreateReply::default().content(
+            "At least two people in this server have to join with `/game whosaidit opt-in` first!",
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let Some(session) = start_game_session(channel_id, Some(guild_id), "Who said it?") else {
+        ctx.send(CreateReply::default().content("A game is already running in this channel!"))
+            .await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+    let messages = get_channel_messages(ctx.serenity_context(), channel_id, ctx.data()).await?;
+    let (quotes, author_names) = build_quotes(messages, &opted_in_user_ids);
+    if author_names.len() < 2 {
+        ctx.send(CreateReply::default().content(
+            "There aren't enough quotes from people who joined the game in this channel yet!",
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let rounds = rounds.unwrap_or(DEFAULT_ROUNDS).clamp(1, MAX_ROUNDS);
+    tokio::select! {
+        result = run_game(ctx, quotes, &author_names, rounds) => result?,
+        _ = session.cancelled() => {}
+    }
+
+    Ok(())
+}
+
+async fn set_opt_in(ctx: Context<'_>, opt_in: bool) -> anyhow::Result<()> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or_default();
+    let mut user_records_write_lock = ctx.data().user_records.write().await;
+    let user_record = user_records_write_lock
+        .entry(ctx.author().id.to_string())
+        .or_default();
+    if opt_in {
+        user_record.quote_game_guild_ids.insert(guild_id);
+    } else {
+        user_record.quote_game_guild_ids.remove(&guild_id);
+    }
+    write_user_records(&user_records_write_lock)
+}
+
+/// Keeps only long enough text messages from opted-in users and hides the names in them.
+/// Returns the quotes and the latest known name of each of their authors.
+fn build_quotes(
+    messages: Vec<MessageRecordSimple>,
+    opted_in_user_ids: &HashSet<u64>,
+) -> (Vec<Quote>, HashMap<u64, String>) {
+    let mut author_names = HashMap::new();
+    let mut seen_texts = HashSet::new();
+    let mut quotes = vec![];
+
+    for message in messages.into_iter() {
+        let Ok(user_id) = message.user_id.parse::<u64>() else {
+            continue;
+        };
+        if message.message_type != "text" || !opted_in_user_ids.contains(&user_id) {
+            continue;
+        }
+
+        let text = anonymize(&message.message, &message.user_name);
+        if text.chars().count() < MIN_QUOTE_LENGTH || !seen_texts.insert(text.clone()) {
+            continue;
+        }
+
+        author_names.insert(user_id, message.user_name);
+        quotes.push(Quote { user_id, text });
+    }
+
+    (quotes, author_names)
+}
+
+fn anonymize(message: &str, user_name: &str) -> String {
+    let text = MENTION_REGEX.replace_all(message.trim(), "@someone");
+    if user_name.trim().is_empty() {
+        return text.into_owned();
+    }
+
+    match Regex::new(&format!("(?i){}", regex::escape(user_name.trim()))) {
+        Ok(name_regex) => name_regex.replace_all(&text, REDACTED_NAME).into_owned(),
+        Err(_) => text.into_owned(),
+    }
+}
+
+async fn run_game(
+    ctx: Context<'_>,
+    quotes: Vec<Quote>,
+    author_names: &HashMap<u64, String>,
+    rounds: usize,
+) -> anyhow::Result<()> {
+    let color = if ctx.data().kou {
+        KOU_COLOR
+    } else {
+        TAIGA_COLOR
+    };
+    let selected_quotes = {
+        let mut rng = rand::rng();
+        quotes
+            .choose_multiple(&mut rng, rounds)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let mut score_board: HashMap<u64, u32> = HashMap::new();
+    for (round, quote) in selected_quotes.iter().enumerate() {
+        touch_game_session(ctx.channel_id().get());
+        let answered = play_round(
+            ctx,
+            color,
+            round,
+            selected_quotes.len(),
+            quote,
+            author_names,
+            &mut score_board,
+        )
+        .await?;
+
+        if !answered {
+            ctx.send(CreateReply::default().content("Nobody is answering... Game over!"))
+                .await?;
+            break;
+        }
+    }
+
+    let mut rankings = score_board.into_iter().collect::<Vec<_>>();
+    rankings.sort_by(|(_, score_a), (_, score_b)| score_b.cmp(score_a));
+    let description = if rankings.is_empty() {
+        "Nobody got any points...".to_string()
+    } else {
+        rankings
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (user_id, score))| {
+                format!("{}) <@{}> - {} points", rank + 1, user_id, score)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title("Who said it? - Results")
+                .description(description)
+                .color(color),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Asks who said the quote. Returns `false` if nobody answered.
+async fn play_round(
+    ctx: Context<'_>,
+    color: Color,
+    round: usize,
+    total_rounds: usize,
+    quote: &Quote,
+    author_names: &HashMap<u64, String>,
+    score_board: &mut HashMap<u64, u32>,
+) -> anyhow::Result<bool> {
+    let choices = {
+        let mut rng = rand::rng();
+        let mut choices = author_names
+            .keys()
+            .copied()
+            .filter(|user_id| *user_id != quote.user_id)
+            .collect::<Vec<_>>()
+            .choose_multiple(&mut rng, MAX_CHOICES - 1)
+            .copied()
+            .collect::<Vec<_>>();
+        choices.push(quote.user_id);
+        choices.shuffle(&mut rng);
+        choices
+    };
+
+    let button_id_prefix = format!("whosaidit_{}_{}_", ctx.id(), round);
+    let build_buttons = |finished: bool| {
+        vec![CreateActionRow::Buttons(
+            choices
+                .iter()
+                .enumerate()
+                .map(|(index, user_id)| {
+                    let label = author_names
+                        .get(user_id)
+                        .map(|name| {
+                            name.chars()
+                                .take(MAX_BUTTON_LABEL_LENGTH)
+                                .collect::<String>()
+                        })
+                        .unwrap_or_default();
+                    let style = if finished && *user_id == quote.user_id {
+                        ButtonStyle::Success
+                    } else {
+                        ButtonStyle::Secondary
+                    };
+                    CreateButton::new(format!("{button_id_prefix}{index}"))
+                        .label(label)
+                        .style(style)
+                        .disabled(finished)
+                })
+                .collect(),
+        )]
+    };
+    let build_quote_embed = |footer: String| {
+        CreateEmbed::new()
+            .title(format!("Who said it? ({}/{})", round + 1, total_rounds))
+            .description(format!(">>> {}", &quote.text))
+            .color(color)
+            .footer(CreateEmbedFooter::new(footer))
+    };
+
+    let reply_handle = ctx
+        .send(
+            CreateReply::default()
+                .embed(build_quote_embed(format!(
+                    "You have {ANSWER_TIME} seconds to answer!"
+                )))
+                .components(build_buttons(false)),
+        )
+        .await?;
+    let sent_msg = reply_handle.message().await?;
+
+    let mut answers: HashMap<u64, usize> = HashMap::new();
+    let answer_end_time = Instant::now() + Duration::from_secs(ANSWER_TIME);
+    loop {
+        let remaining = answer_end_time.saturating_duration_since(Instant::now());
+        let Some(interaction) = sent_msg
+            .await_component_interaction(ctx)
+            .timeout(remaining)
+            .await
+        else {
+            break;
+        };
+
+        let choice = interaction
+            .data
+            .custom_id
+            .strip_prefix(&button_id_prefix)
+            .and_then(|index| index.parse::<usize>().ok())
+            .filter(|index| *index < choices.len());
+        let content = match choice {
+            Some(_) if answers.contains_key(&interaction.user.id.get()) => {
+                "You have already locked in your answer!".to_string()
+            }
+            Some(index) => {
+                answers.insert(interaction.user.id.get(), index);
+                format!(
+                    "You locked in **{}**!",
+                    author_names
+                        .get(&choices[index])
+                        .map(|name| name.as_str())
+                        .unwrap_or_default()
+                )
+            }
+            None => "That's not a valid answer!".to_string(),
+        };
+        interaction
+            .create_response(
+                ctx.http(),
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+    }
+
+    let winners = answers
+        .iter()
+        .filter(|(_, index)| choices[**index] == quote.user_id)
+        .map(|(user_id, _)| *user_id)
+        .collect::<Vec<_>>();
+    for user_id in winners.iter() {
+        *score_board.entry(*user_id).or_default() += 1;
+    }
+
+    let author_name = author_names
+        .get(&quote.user_id)
+        .map(|name| name.as_str())
+        .unwrap_or_default();
+    let footer = if winners.is_empty() {
+        format!("It was {author_name}! Nobody got it right...")
+    } else {
+        format!(
+            "It was {author_name}! {} got it right!",
+            if winners.len() == 1 {
+                "1 person".to_string()
+            } else {
+                format!("{} people", winners.len())
+            }
+        )
+    };
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .embed(build_quote_embed(footer))
+                .components(build_buttons(true)),
+        )
+        .await?;
+
+    Ok(!answers.is_empty())
+}
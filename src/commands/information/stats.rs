@@ -7,6 +7,7 @@ use serenity::all::{Color, CreateEmbedAuthor};
 use serenity::builder::CreateEmbed;
 
 use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::structs::record::game_stats::{BoardGameStats, HangmanStats, QuizStats};
 use crate::shared::structs::record::user_record::UserRecord;
 use crate::shared::structs::{Context, ContextError};
 use crate::shared::utility::{get_author_avatar, get_author_name};
@@ -17,6 +18,16 @@ pub enum StatChoice {
     Route,
     #[name = "valentine"]
     Valentine,
+    #[name = "quiz"]
+    Quiz,
+    #[name = "hangman"]
+    Hangman,
+    #[name = "tictactoe"]
+    TicTacToe,
+    #[name = "connect4"]
+    ConnectFour,
+    #[name = "reversi"]
+    Reversi,
 }
 
 /// This command will show your records with several commands.
@@ -54,6 +65,41 @@ pub async fn stats(
             StatChoice::Valentine => {
                 build_valentine_records(author_name, author_avatar_url, color, user_record)
             }
+            StatChoice::Quiz => build_game_records(
+                author_name,
+                author_avatar_url,
+                color,
+                "quiz",
+                quiz_fields(&user_record.games.quiz),
+            ),
+            StatChoice::Hangman => build_game_records(
+                author_name,
+                author_avatar_url,
+                color,
+                "hangman",
+                hangman_fields(&user_record.games.hangman),
+            ),
+            StatChoice::TicTacToe => build_game_records(
+                author_name,
+                author_avatar_url,
+                color,
+                "tictactoe",
+                board_game_fields(&user_record.games.tictactoe),
+            ),
+            StatChoice::ConnectFour => build_game_records(
+                author_name,
+                author_avatar_url,
+                color,
+                "connect4",
+                board_game_fields(&user_record.games.connect_four),
+            ),
+            StatChoice::Reversi => build_game_records(
+                author_name,
+                author_avatar_url,
+                color,
+                "reversi",
+                board_game_fields(&user_record.games.reversi),
+            ),
         }
     } else {
         build_all(author_name, author_avatar_url, color, user_record)
@@ -107,6 +153,104 @@ fn build_valentine_records(
     add_valentine_character_fields(embed, character_name_and_counts, amount)
 }
 
+fn build_game_records(
+    author_name: String,
+    author_avatar_url: String,
+    color: Color,
+    game_name: &str,
+    fields: Vec<(String, String, bool)>,
+) -> CreateEmbed {
+    CreateEmbed::new()
+        .author(CreateEmbedAuthor::new(&author_name).icon_url(author_avatar_url))
+        .color(color)
+        .description(format!("Here's {author_name}'s records with `{game_name}`"))
+        .fields(fields)
+}
+
+fn quiz_fields(quiz_stats: &QuizStats) -> Vec<(String, String, bool)> {
+    vec![
+        (
+            "**Games Played**".to_string(),
+            quiz_stats.games_played.to_string(),
+            true,
+        ),
+        ("**Wins**".to_string(), quiz_stats.wins.to_string(), true),
+        (
+            "**Accuracy**".to_string(),
+            format!("{:.1}%", quiz_stats.accuracy()),
+            true,
+        ),
+        (
+            "**Correct Answers**".to_string(),
+            quiz_stats.correct_answers.to_string(),
+            true,
+        ),
+        (
+            "**Fastest Answer**".to_string(),
+            quiz_stats
+                .fastest_answer_ms
+                .map(|ms| format!("{:.2}s", ms as f64 / 1000.0))
+                .unwrap_or_else(|| "N/A".to_string()),
+            true,
+        ),
+    ]
+}
+
+fn hangman_fields(hangman_stats: &HangmanStats) -> Vec<(String, String, bool)> {
+    vec![
+        (
+            "**Games Played**".to_string(),
+            hangman_stats.games_played.to_string(),
+            true,
+        ),
+        (
+            "**Words Solved**".to_string(),
+            hangman_stats.words_solved.to_string(),
+            true,
+        ),
+        (
+            "**Letters Guessed**".to_string(),
+            hangman_stats.letters_guessed.to_string(),
+            true,
+        ),
+        (
+            "**Accuracy**".to_string(),
+            format!("{:.1}%", hangman_stats.accuracy()),
+            true,
+        ),
+    ]
+}
+
+fn board_game_fields(board_game_stats: &BoardGameStats) -> Vec<(String, String, bool)> {
+    vec![
+        (
+            "**Games Played**".to_string(),
+            board_game_stats.games_played.to_string(),
+            true,
+        ),
+        (
+            "**Wins**".to_string(),
+            board_game_stats.wins.to_string(),
+            true,
+        ),
+        (
+            "**Losses**".to_string(),
+            board_game_stats.losses.to_string(),
+            true,
+        ),
+        (
+            "**Draws**".to_string(),
+            board_game_stats.draws.to_string(),
+            true,
+        ),
+        (
+            "**Win Rate**".to_string(),
+            format!("{:.1}%", board_game_stats.win_rate()),
+            true,
+        ),
+    ]
+}
+
 fn build_all(
     author_name: String,
     author_avatar_url: String,
@@ -132,7 +276,7 @@ fn build_all(
         .author(CreateEmbedAuthor::new(&author_name).icon_url(author_avatar_url))
         .color(color)
         .description(format!(
-            "Here's {author_name}'s records with `route, valentine`"
+            "Here's {author_name}'s records with `route, valentine, quiz, hangman, tictactoe, connect4, reversi`"
         ));
 
     let embed = embed.field("**Route**", "Records for `route`", false);
@@ -143,7 +287,56 @@ fn build_all(
         format!("Records for `valentine` (top {amount})"),
         false,
     );
-    add_valentine_character_fields(embed, valentine_name_and_counts, amount)
+    let embed = add_valentine_character_fields(embed, valentine_name_and_counts, amount);
+
+    let quiz_stats = &user_record.games.quiz;
+    let hangman_stats = &user_record.games.hangman;
+    embed
+        .field(
+            "**Quiz**",
+            format!(
+                "{} games, {} wins, {:.1}% accuracy",
+                quiz_stats.games_played,
+                quiz_stats.wins,
+                quiz_stats.accuracy()
+            ),
+            false,
+        )
+        .field(
+            "**Hangman**",
+            format!(
+                "{} games, {} words solved, {:.1}% accuracy",
+                hangman_stats.games_played,
+                hangman_stats.words_solved,
+                hangman_stats.accuracy()
+            ),
+            false,
+        )
+        .field(
+            "**Tic-tac-toe**",
+            board_game_summary(&user_record.games.tictactoe),
+            false,
+        )
+        .field(
+            "**Connect Four**",
+            board_game_summary(&user_record.games.connect_four),
+            false,
+        )
+        .field(
+            "**Reversi**",
+            board_game_summary(&user_record.games.reversi),
+            false,
+        )
+}
+
+fn board_game_summary(board_game_stats: &BoardGameStats) -> String {
+    format!(
+        "{} games, {} wins, {} losses, {} draws",
+        board_game_stats.games_played,
+        board_game_stats.wins,
+        board_game_stats.losses,
+        board_game_stats.draws
+    )
 }
 
 fn add_route_character_fields(
@@ -2,5 +2,6 @@ pub mod admin;
 pub mod fun;
 pub mod game;
 pub mod information;
+pub mod moderation;
 pub mod smite;
 pub mod utility;
@@ -0,0 +1,229 @@
+#![allow(clippy::too_many_arguments)]
+
+use poise::CreateReply;
+
+use crate::shared::services::anti_spam_service::is_raided;
+use crate::shared::services::moderation_service::{log_case, new_case};
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::moderation::moderation_case::CaseAction;
+use crate::shared::structs::moderation::moderation_settings::{AntiSpamSettings, SpamPunishment};
+use crate::shared::structs::{Context, ContextError};
+use crate::shared::utility::{MAX_DURATION_DAYS, parse_duration};
+
+/// Detect spam and raids in this server.
+#[poise::command(
+    slash_command,
+    rename = "anti-spam",
+    subcommands("show", "set"),
+    subcommand_required,
+    guild_only,
+    category = "Admin",
+    custom_data = "RESTRICTED"
+)]
+pub async fn anti_spam(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Show the anti-spam settings of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn show(ctx: Context<'_>) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let settings = ctx
+        .data()
+        .moderation
+        .settings
+        .read()
+        .await
+        .get(guild_id)
+        .anti_spam;
+
+    let mut content = describe_settings(&settings);
+    if is_raided(guild_id) {
+        content += "\n**A raid is in progress. New members aren't greeted.**";
+    }
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Change the anti-spam settings of this server. Thresholds of 0 disable their check.
+#[poise::command(slash_command, guild_only)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Whether to detect spam and raids."] enabled: Option<bool>,
+    #[description = "Identical messages of a member that count as spam."]
+    #[max = 50]
+    duplicate_messages: Option<usize>,
+    #[description = "Seconds in which identical messages are counted."]
+    #[min = 1]
+    #[max = 600]
+    duplicate_window: Option<u64>,
+    #[description = "Mentions in a single message that count as spam."]
+    #[max = 100]
+    max_mentions: Option<usize>,
+    #[description = "Whether invite links count as spam."] block_invite_links: Option<bool>,
+    #[description = "Joins that count as a raid."]
+    #[max = 500]
+    raid_joins: Option<usize>,
+    #[description = "Seconds in which joins are counted."]
+    #[min = 1]
+    #[max = 3600]
+    raid_window: Option<u64>,
+    #[description = "Minutes without a join flood before a raid is over."]
+    #[min = 1]
+    #[max = 1440]
+    raid_cooldown: Option<u64>,
+    #[description = "Whether to delete spam messages."] delete_messages: Option<bool>,
+    #[description = "How to punish spammers."] punishment: Option<SpamPunishment>,
+    #[description = "How long the punishment lasts, e.g. 10m, 1h or 1d."]
+    punishment_duration: Option<String>,
+    #[description = "Whether to record spam and raids as cases in the mod-log channel."]
+    alert_mod_log: Option<bool>,
+) -> Result<(), ContextError> {
+    if let Some(duration) = punishment_duration
+        .as_ref()
+        .filter(|duration| parse_duration(duration).is_none())
+    {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "`{duration}` is not a valid duration! Try something like `10m`, `1h` or `1d`. It can be at most {MAX_DURATION_DAYS} days."
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let settings = {
+        let mut settings_write_lock = ctx.data().moderation.settings.write().await;
+        let settings = &mut settings_write_lock.get_mut(guild_id).anti_spam;
+        if let Some(enabled) = enabled {
+            settings.enabled = enabled;
+        }
+        if let Some(duplicate_messages) = duplicate_messages {
+            settings.duplicate_messages = duplicate_messages;
+        }
+        if let Some(duplicate_window) = duplicate_window {
+            settings.duplicate_window_seconds = duplicate_window;
+        }
+        if let Some(max_mentions) = max_mentions {
+            settings.max_mentions = max_mentions;
+        }
+        if let Some(block_invite_links) = block_invite_links {
+            settings.block_invite_links = block_invite_links;
+        }
+        if let Some(raid_joins) = raid_joins {
+            settings.raid_joins = raid_joins;
+        }
+        if let Some(raid_window) = raid_window {
+            settings.raid_window_seconds = raid_window;
+        }
+        if let Some(raid_cooldown) = raid_cooldown {
+            settings.raid_cooldown_minutes = raid_cooldown;
+        }
+        if let Some(delete_messages) = delete_messages {
+            settings.delete_messages = delete_messages;
+        }
+        if let Some(punishment) = punishment {
+            settings.punishment = punishment;
+        }
+        if let Some(punishment_duration) = punishment_duration {
+            settings.punishment_duration = punishment_duration;
+        }
+        if let Some(alert_mod_log) = alert_mod_log {
+            settings.alert_mod_log = alert_mod_log;
+        }
+        let settings = settings.clone();
+        settings_write_lock.write_moderation_settings()?;
+        settings
+    };
+
+    let description = describe_settings(&settings);
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::UpdateAntiSpam).with_details(description.clone()),
+    )
+    .await;
+    ctx.send(
+        CreateReply::default().content(format!("Updated the anti-spam settings.\n{description}")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn describe_settings(settings: &AntiSpamSettings) -> String {
+    let threshold = |value: usize, text: String| {
+        if value == 0 { "off".to_string() } else { text }
+    };
+    let punishment = match settings.punishment {
+        SpamPunishment::NoPunishment => "none".to_string(),
+        SpamPunishment::Smite => format!("smite for {}", &settings.punishment_duration),
+        SpamPunishment::Timeout => format!("timeout for {}", &settings.punishment_duration),
+    };
+
+    [
+        format!(
+            "**Anti-spam:** {}",
+            if settings.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        ),
+        format!(
+            "**Duplicate messages:** {}",
+            threshold(
+                settings.duplicate_messages,
+                format!(
+                    "{} within {} seconds",
+                    settings.duplicate_messages, settings.duplicate_window_seconds
+                )
+            )
+        ),
+        format!(
+            "**Mass mentions:** {}",
+            threshold(
+                settings.max_mentions,
+                format!("{} mentions", settings.max_mentions)
+            )
+        ),
+        format!(
+            "**Invite links:** {}",
+            if settings.block_invite_links {
+                "blocked"
+            } else {
+                "allowed"
+            }
+        ),
+        format!(
+            "**Raids:** {}",
+            threshold(
+                settings.raid_joins,
+                format!(
+                    "{} joins within {} seconds, over after {} minutes",
+                    settings.raid_joins,
+                    settings.raid_window_seconds,
+                    settings.raid_cooldown_minutes
+                )
+            )
+        ),
+        format!(
+            "**Delete spam:** {}",
+            if settings.delete_messages {
+                "yes"
+            } else {
+                "no"
+            }
+        ),
+        format!("**Punishment:** {punishment}"),
+        format!(
+            "**Alert mod-log:** {}",
+            if settings.alert_mod_log { "yes" } else { "no" }
+        ),
+    ]
+    .join("\n")
+}
@@ -0,0 +1,159 @@
+use poise::CreateReply;
+use serenity::all::User;
+
+use crate::shared::services::moderation_service::{build_case_embed, update_case_log_message};
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::moderation::moderation_case::CaseTarget;
+use crate::shared::structs::{Context, ContextError};
+
+const CASES_PER_PAGE: usize = 10;
+const MAX_REASON_PREVIEW_LENGTH: usize = 50;
+
+/// View and manage the moderation cases of this server.
+#[poise::command(
+    slash_command,
+    subcommands("view", "list", "edit_reason"),
+    subcommand_required,
+    guild_only,
+    category = "Admin",
+    custom_data = "RESTRICTED"
+)]
+pub async fn case(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// View a moderation case.
+#[poise::command(slash_command, guild_only)]
+pub async fn view(
+    ctx: Context<'_>,
+    #[description = "The ID of the case."] id: u64,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let case = ctx
+        .data()
+        .moderation
+        .cases
+        .read()
+        .await
+        .get(guild_id, id)
+        .cloned();
+
+    let reply = match case {
+        Some(case) => CreateReply::default().embed(build_case_embed(&case, ctx.data().kou)),
+        None => CreateReply::default().content(format!("There is no case with ID {id}!")),
+    };
+    ctx.send(reply.ephemeral(true)).await?;
+
+    Ok(())
+}
+
+/// List the most recent moderation cases of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Only list cases against this member."] member: Option<User>,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let target = member.map(|member| CaseTarget::User(member.id.get()));
+    let cases = ctx
+        .data()
+        .moderation
+        .cases
+        .read()
+        .await
+        .list(guild_id)
+        .iter()
+        .rev()
+        .filter(|case| target.is_none() || case.target == target)
+        .take(CASES_PER_PAGE)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if cases.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no cases yet!")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let content = cases
+        .iter()
+        .map(|case| {
+            let reason = case.reason.as_deref().unwrap_or("No reason given.");
+            let reason = if reason.chars().count() > MAX_REASON_PREVIEW_LENGTH {
+                reason
+                    .chars()
+                    .take(MAX_REASON_PREVIEW_LENGTH)
+                    .collect::<String>()
+                    + "..."
+            } else {
+                reason.to_string()
+            };
+            format!(
+                "**#{}** {} | {} | by <@{}> <t:{}:R>\n> {}",
+                case.id,
+                case.action,
+                case.target
+                    .as_ref()
+                    .map(|target| target.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                case.actor_id,
+                case.created_at.timestamp(),
+                reason
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Change the reason of a moderation case.
+#[poise::command(slash_command, guild_only, rename = "edit-reason")]
+pub async fn edit_reason(
+    ctx: Context<'_>,
+    #[description = "The ID of the case."] id: u64,
+    #[description = "The new reason."] reason: String,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let case = {
+        let mut cases_write_lock = ctx.data().moderation.cases.write().await;
+        let case = cases_write_lock.get_mut(guild_id, id).map(|case| {
+            case.reason = Some(reason);
+            case.clone()
+        });
+        if case.is_some() {
+            cases_write_lock.write_moderation_cases()?;
+        }
+        case
+    };
+
+    let Some(case) = case else {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("There is no case with ID {id}!"))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if let Err(e) = update_case_log_message(ctx.http(), &case, ctx.data().kou).await {
+        tracing::error!("Failed to update mod-log message of case {}: {}", id, e);
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("Updated the reason of case #{id}."))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
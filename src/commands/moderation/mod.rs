@@ -0,0 +1,4 @@
+pub mod anti_spam;
+pub mod case;
+pub mod permissions;
+pub mod warn;
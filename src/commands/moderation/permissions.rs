@@ -0,0 +1,259 @@
+use poise::CreateReply;
+use serenity::all::{CreateAllowedMentions, Role, User};
+
+use crate::shared::services::moderation_service::{log_case, new_case};
+use crate::shared::services::permission_service::is_bot_admin;
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::config::command_permission::{CATEGORY_SCOPE_PREFIX, PermissionTarget};
+use crate::shared::structs::moderation::moderation_case::CaseAction;
+use crate::shared::structs::{Context, ContextData, ContextError};
+
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+const PERMISSIONS_COMMAND_NAME: &str = "permissions";
+
+/// Grant or deny commands to roles and members of this server.
+#[poise::command(
+    slash_command,
+    subcommands("grant", "deny", "reset", "list"),
+    subcommand_required,
+    guild_only,
+    category = "Admin",
+    custom_data = "RESTRICTED"
+)]
+pub async fn permissions(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Allow a role or member to use a command or a category of commands.
+#[poise::command(slash_command, guild_only)]
+pub async fn grant(
+    ctx: Context<'_>,
+    #[description = "A command such as `qotd` or `smite user`, or a category such as `category:admin`."]
+    #[autocomplete = "autocomplete_scope"]
+    command: String,
+    #[description = "The role to grant the command to."] role: Option<Role>,
+    #[description = "The member to grant the command to."] member: Option<User>,
+) -> Result<(), ContextError> {
+    set_permission(ctx, command, role, member, true).await
+}
+
+/// Forbid a role or member from using a command or a category of commands.
+#[poise::command(slash_command, guild_only)]
+pub async fn deny(
+    ctx: Context<'_>,
+    #[description = "A command such as `qotd` or `smite user`, or a category such as `category:admin`."]
+    #[autocomplete = "autocomplete_scope"]
+    command: String,
+    #[description = "The role to deny the command to."] role: Option<Role>,
+    #[description = "The member to deny the command to."] member: Option<User>,
+) -> Result<(), ContextError> {
+    set_permission(ctx, command, role, member, false).await
+}
+
+/// Remove a role's or member's rule for a command or a category of commands.
+#[poise::command(slash_command, guild_only)]
+pub async fn reset(
+    ctx: Context<'_>,
+    #[description = "A command such as `qotd` or `smite user`, or a category such as `category:admin`."]
+    #[autocomplete = "autocomplete_scope"]
+    command: String,
+    #[description = "The role whose rule to remove."] role: Option<Role>,
+    #[description = "The member whose rule to remove."] member: Option<User>,
+) -> Result<(), ContextError> {
+    if !ensure_bot_admin(ctx).await? {
+        return Ok(());
+    }
+
+    let Some(target) = resolve_target(ctx, role, member).await? else {
+        return Ok(());
+    };
+
+    let scope = command.trim().to_lowercase();
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let removed = {
+        let mut command_permissions_write_lock = ctx.data().command_permissions.write().await;
+        let removed = command_permissions_write_lock.reset(guild_id, target, &scope);
+        if removed {
+            command_permissions_write_lock.write_command_permissions()?;
+        }
+        removed
+    };
+
+    let content = if removed {
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::UpdateCommandPermissions)
+                .with_details(format!("Reset `{scope}` for {target}.")),
+        )
+        .await;
+        format!("Reset `{scope}` for {target}.")
+    } else {
+        format!("{target} has no rule for `{scope}`!")
+    };
+    ctx.send(
+        CreateReply::default()
+            .content(content)
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// List the command permissions of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: Context<'_>) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let content = {
+        let command_permissions_read_lock = ctx.data().command_permissions.read().await;
+        let permissions = command_permissions_read_lock.list(guild_id);
+        if permissions.is_empty() {
+            "This server has no command permissions. Admin commands are only usable by admins."
+                .to_string()
+        } else {
+            permissions
+                .iter()
+                .map(|permission| {
+                    format!(
+                        "{} `{}` {} {}",
+                        if permission.allowed { "✅" } else { "⛔" },
+                        &permission.scope,
+                        if permission.allowed { "to" } else { "for" },
+                        permission.target
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    };
+    ctx.send(
+        CreateReply::default()
+            .content(content)
+            .allowed_mentions(CreateAllowedMentions::new())
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn set_permission(
+    ctx: Context<'_>,
+    command: String,
+    role: Option<Role>,
+    member: Option<User>,
+    allowed: bool,
+) -> Result<(), ContextError> {
+    if !ensure_bot_admin(ctx).await? {
+        return Ok(());
+    }
+
+    let Some(target) = resolve_target(ctx, role, member).await? else {
+        return Ok(());
+    };
+
+    let scope = command.trim().to_lowercase();
+    if !available_scopes(&ctx.framework().options().commands).contains(&scope) {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("There is no command or category called `{scope}`!"))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    {
+        let mut command_permissions_write_lock = ctx.data().command_permissions.write().await;
+        command_permissions_write_lock.set(guild_id, target, &scope, allowed);
+        command_permissions_write_lock.write_command_permissions()?;
+    }
+
+    let summary = if allowed {
+        format!("Granted `{scope}` to {target}.")
+    } else {
+        format!("Denied `{scope}` for {target}.")
+    };
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::UpdateCommandPermissions).with_details(summary.clone()),
+    )
+    .await;
+    ctx.send(
+        CreateReply::default()
+            .content(summary)
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Only bot admins can change the command permissions, even if the command has been granted to others,
+/// so that nobody can grant themselves more than they were given.
+async fn ensure_bot_admin(ctx: Context<'_>) -> Result<bool, ContextError> {
+    if is_bot_admin(ctx).await {
+        return Ok(true);
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content("Only admins can change the command permissions!")
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// Exactly one of the role and the member has to be given.
+async fn resolve_target(
+    ctx: Context<'_>,
+    role: Option<Role>,
+    member: Option<User>,
+) -> Result<Option<PermissionTarget>, ContextError> {
+    match (role, member) {
+        (Some(role), None) => Ok(Some(PermissionTarget::Role(role.id.get()))),
+        (None, Some(member)) => Ok(Some(PermissionTarget::User(member.id.get()))),
+        _ => {
+            ctx.send(
+                CreateReply::default()
+                    .content("Please specify either a role or a member!")
+                    .ephemeral(true),
+            )
+            .await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Every command, subcommand and category a permission rule can target.
+/// The permissions command itself is left out, as only bot admins can use it.
+fn available_scopes(commands: &[poise::Command<ContextData, ContextError>]) -> Vec<String> {
+    let mut scopes = vec![];
+    for command in commands
+        .iter()
+        .filter(|command| command.qualified_name != PERMISSIONS_COMMAND_NAME)
+    {
+        scopes.push(command.qualified_name.to_lowercase());
+        if let Some(category) = command.category.as_ref() {
+            scopes.push(format!(
+                "{CATEGORY_SCOPE_PREFIX}{}",
+                category.to_lowercase()
+            ));
+        }
+        scopes.extend(available_scopes(&command.subcommands));
+    }
+    scopes.sort();
+    scopes.dedup();
+    scopes
+}
+
+async fn autocomplete_scope(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+    available_scopes(&ctx.framework().options().commands)
+        .into_iter()
+        .filter(|scope| scope.contains(&partial))
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .collect()
+}
@@ -0,0 +1,295 @@
+use poise::CreateReply;
+use serenity::all::{CreateAllowedMentions, User};
+
+use crate::shared::services::moderation_service::{escalate_warnings, log_case, new_case};
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::moderation::moderation_case::{CaseAction, CaseTarget};
+use crate::shared::structs::moderation::moderation_settings::{EscalationAction, EscalationRule};
+use crate::shared::structs::{Context, ContextError};
+use crate::shared::utility::{MAX_DURATION_DAYS, parse_duration};
+
+/// Warn bad behaving members and manage automatic escalation.
+#[poise::command(
+    slash_command,
+    subcommands("user", "remove", "rules", "add_rule", "remove_rule"),
+    subcommand_required,
+    guild_only,
+    category = "Admin",
+    custom_data = "RESTRICTED"
+)]
+pub async fn warn(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Warn a member. Reaching a threshold of warnings may punish the member automatically.
+#[poise::command(slash_command, guild_only)]
+pub async fn user(
+    ctx: Context<'_>,
+    #[description = "The member to warn."] member: User,
+    #[description = "Why the member is warned."] reason: String,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let user_id = member.id.get();
+    let (warning, total) = {
+        let mut warnings_write_lock = ctx.data().moderation.warnings.write().await;
+        let warning =
+            warnings_write_lock.add(guild_id, user_id, ctx.author().id.get(), reason.clone());
+        warnings_write_lock.write_warnings()?;
+        (
+            warning,
+            warnings_write_lock.of_user(guild_id, user_id).len(),
+        )
+    };
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::Warn)
+            .with_target(CaseTarget::User(user_id))
+            .with_reason(Some(reason))
+            .with_details(format!("Warning #{}.", warning.id)),
+    )
+    .await;
+
+    let escalation = match escalate_warnings(
+        ctx.http(),
+        ctx.data(),
+        guild_id,
+        user_id,
+        ctx.framework().bot_id.get(),
+    )
+    .await
+    {
+        Ok(Some(rule)) => format!(
+            "\nThey have received {} warnings within {} days and have been {} for {}.",
+            rule.warnings,
+            rule.within_days,
+            match rule.action {
+                EscalationAction::Smite => "smitten",
+                EscalationAction::Timeout => "timed out",
+            },
+            &rule.duration
+        ),
+        Ok(None) => String::new(),
+        Err(e) => {
+            tracing::error!("Failed to escalate warnings: {}", e);
+            "\nI couldn't apply the escalation rule for their warnings!".to_string()
+        }
+    };
+
+    ctx.send(CreateReply::default().content(format!(
+        "<@{user_id}> has been warned. (Warning #{}, {total} in total){escalation}",
+        warning.id
+    )))
+    .await?;
+
+    Ok(())
+}
+
+/// Remove a warning.
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The ID of the warning."] id: u64,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let removed_warning = {
+        let mut warnings_write_lock = ctx.data().moderation.warnings.write().await;
+        let removed_warning = warnings_write_lock.remove(guild_id, id);
+        if removed_warning.is_some() {
+            warnings_write_lock.write_warnings()?;
+        }
+        removed_warning
+    };
+
+    let content = match removed_warning {
+        Some(warning) => {
+            log_case(
+                ctx,
+                new_case(ctx, CaseAction::RemoveWarning)
+                    .with_target(CaseTarget::User(warning.user_id))
+                    .with_details(format!("Warning #{}: {}", warning.id, &warning.reason)),
+            )
+            .await;
+            format!("Removed warning #{id} of <@{}>.", warning.user_id)
+        }
+        None => format!("There is no warning with ID {id}!"),
+    };
+    ctx.send(
+        CreateReply::default()
+            .content(content)
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// List the escalation rules of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn rules(ctx: Context<'_>) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let escalation_rules = ctx
+        .data()
+        .moderation
+        .settings
+        .read()
+        .await
+        .get(guild_id)
+        .escalation_rules;
+
+    let content = if escalation_rules.is_empty() {
+        "This server has no escalation rules. Add one with `/warn add-rule`.".to_string()
+    } else {
+        escalation_rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| format!("{}. {rule}", index + 1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Punish members automatically when they reach a number of warnings.
+#[poise::command(slash_command, guild_only, rename = "add-rule")]
+pub async fn add_rule(
+    ctx: Context<'_>,
+    #[description = "The number of warnings that triggers the rule."]
+    #[min = 1]
+    #[max = 50]
+    warnings: usize,
+    #[description = "Only count warnings given within this many days."]
+    #[min = 1]
+    #[max = 365]
+    within_days: i64,
+    #[description = "The punishment."] action: EscalationAction,
+    #[description = "How long the punishment lasts, e.g. 30m, 12h or 1d."] duration: String,
+) -> Result<(), ContextError> {
+    if parse_duration(&duration).is_none() {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "`{duration}` is not a valid duration! Try something like `30m`, `2h` or `1d12h`. It can be at most {MAX_DURATION_DAYS} days."
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let rule = EscalationRule {
+        warnings,
+        within_days,
+        action,
+        duration,
+    };
+    let description = rule.to_string();
+    {
+        let mut settings_write_lock = ctx.data().moderation.settings.write().await;
+        settings_write_lock
+            .get_mut(guild_id)
+            .escalation_rules
+            .push(rule);
+        settings_write_lock.write_moderation_settings()?;
+    }
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::UpdateEscalationRules)
+            .with_details(format!("Added rule: {description}")),
+    )
+    .await;
+
+    ctx.send(CreateReply::default().content(format!("Added escalation rule: {description}")))
+        .await?;
+
+    Ok(())
+}
+
+/// Remove an escalation rule.
+#[poise::command(slash_command, guild_only, rename = "remove-rule")]
+pub async fn remove_rule(
+    ctx: Context<'_>,
+    #[description = "The number of the rule as shown in `/warn rules`."]
+    #[min = 1]
+    number: usize,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let removed_rule = {
+        let mut settings_write_lock = ctx.data().moderation.settings.write().await;
+        let escalation_rules = &mut settings_write_lock.get_mut(guild_id).escalation_rules;
+        if number <= escalation_rules.len() {
+            let removed_rule = escalation_rules.remove(number - 1);
+            settings_write_lock.write_moderation_settings()?;
+            Some(removed_rule)
+        } else {
+            None
+        }
+    };
+
+    let content = match removed_rule {
+        Some(rule) => {
+            log_case(
+                ctx,
+                new_case(ctx, CaseAction::UpdateEscalationRules)
+                    .with_details(format!("Removed rule: {rule}")),
+            )
+            .await;
+            format!("Removed escalation rule: {rule}")
+        }
+        None => format!("There is no escalation rule number {number}!"),
+    };
+    ctx.send(CreateReply::default().content(content)).await?;
+
+    Ok(())
+}
+
+/// List the warnings of a member.
+#[poise::command(
+    slash_command,
+    guild_only,
+    category = "Admin",
+    custom_data = "RESTRICTED"
+)]
+pub async fn warnings(
+    ctx: Context<'_>,
+    #[description = "The member whose warnings to list."] member: User,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let warnings = ctx
+        .data()
+        .moderation
+        .warnings
+        .read()
+        .await
+        .of_user(guild_id, member.id.get());
+
+    let content = if warnings.is_empty() {
+        format!("{} has no warnings.", &member.name)
+    } else {
+        let warning_list = warnings
+            .iter()
+            .map(|warning| {
+                format!(
+                    "**#{}** <t:{}:d> by <@{}>: {}",
+                    warning.id,
+                    warning.created_at.timestamp(),
+                    warning.moderator_id,
+                    &warning.reason
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{} has {} warnings:\n{warning_list}",
+            &member.name,
+            warnings.len()
+        )
+    };
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -1,85 +1,251 @@
-use crate::shared::constants::{KOU_SERVER_SMOTE_ROLE_ID, TAIGA_SERVER_SMOTE_ROLE_ID};
-use crate::shared::structs::smite::SmoteUser;
+use crate::shared::services::moderation_service::{log_case, new_case};
+use crate::shared::services::scheduler_service::{ScheduledAction, cancel_job};
+use crate::shared::structs::config::channel_control::RESTRICTED;
+use crate::shared::structs::moderation::moderation_case::{CaseAction, CaseTarget};
+use crate::shared::structs::smite::{remove_smote_user, smite_user};
 use crate::shared::structs::{Context, ContextError};
+use crate::shared::utility::{MAX_DURATION_DAYS, format_duration, parse_duration};
 use chrono::Utc;
 use poise::CreateReply;
 use rand::prelude::*;
-use serenity::all::{RoleId, User};
+use serenity::all::{CreateAllowedMentions, Role, User};
 
-/// Smite bad behaving members.
-#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
-pub async fn smite(
+const DEFAULT_SMITE_DURATION: &str = "1d";
+
+/// Smite bad behaving members, or manage the smitten ones.
+#[poise::command(
+    slash_command,
+    subcommands("user", "list", "role"),
+    subcommand_required,
+    guild_only,
+    custom_data = "RESTRICTED"
+)]
+pub async fn smite(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
+/// Smite a bad behaving member for a while. Smiting again extends the smite.
+#[poise::command(slash_command, guild_only)]
+pub async fn user(
     ctx: Context<'_>,
     #[description = "Bad behaving member to smite."] member: User,
+    #[description = "How long the smite lasts, e.g. 30m, 2h or 1d12h. Default to 1d."]
+    duration: Option<String>,
+    #[description = "Why the member is smitten."] reason: Option<String>,
 ) -> Result<(), ContextError> {
-    let smote_member = if let Some(guild_id) = ctx.guild_id() {
-        ctx.http().get_member(guild_id, member.id).await.ok()
-    } else {
-        None
+    let guild_id = ctx.guild_id().unwrap_or_default();
+    let duration_text = duration.unwrap_or_else(|| DEFAULT_SMITE_DURATION.to_string());
+    let Some(duration) = parse_duration(&duration_text) else {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "`{duration_text}` is not a valid duration! Try something like `30m`, `2h` or `1d12h`. It can be at most {MAX_DURATION_DAYS} days."
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
     };
 
-    let smote_role_ids = vec![TAIGA_SERVER_SMOTE_ROLE_ID, KOU_SERVER_SMOTE_ROLE_ID];
-    if let Some(member) = smote_member {
-        for role_id in smote_role_ids.into_iter() {
-            if member
-                .add_role(ctx.http(), RoleId::new(role_id))
-                .await
-                .is_ok()
-            {
-                let gif_link = {
-                    let mut rng = rand::rng();
-                    ctx.data()
-                        .smite
-                        .smite_gif_links
-                        .choose(&mut rng)
-                        .map(|s| s.as_str())
-                        .unwrap_or_default()
-                };
-                ctx.send(CreateReply::default().content(gif_link)).await?;
-
-                let smote_user_list = ctx.data().smite.smote_user_list.clone();
-                {
-                    let mut smote_users_write_lock = smote_user_list.write().await;
-                    smote_users_write_lock.smote_users.push(SmoteUser {
-                        user_id: member.user.id.get(),
-                        due_time: Utc::now() + chrono::Duration::days(1),
-                        guild_id: ctx.guild_id().map(|id| id.get()).unwrap_or_default(),
-                    });
-                    smote_users_write_lock.write_smote_user_list()?;
-                }
-
-                let context = ctx.serenity_context().clone();
-                tokio::spawn(async move {
-                    let context = context;
-                    tokio::time::sleep(std::time::Duration::from_secs(86400)).await;
-
-                    match member.remove_role(context.http, RoleId::new(role_id)).await {
-                        Ok(_) => {
-                            let mut smote_users_write_lock = smote_user_list.write().await;
-                            let filtered_user_list = smote_users_write_lock
-                                .smote_users
-                                .clone()
-                                .into_iter()
-                                .filter(|u| u.user_id != member.user.id.get())
-                                .collect::<Vec<_>>();
-                            smote_users_write_lock.smote_users = filtered_user_list;
-                            if let Err(e) = smote_users_write_lock.write_smote_user_list() {
-                                tracing::error!(
-                                    "Error when writing smote user list to local disk: {}",
-                                    e
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Error when remove smote role from user: {}", e);
-                        }
-                    }
-                });
-
-                break;
-            }
+    let smite = &ctx.data().smite;
+    let Some(role_id) = smite
+        .smite_settings
+        .read()
+        .await
+        .smote_role_id(guild_id.get())
+    else {
+        ctx.send(
+            CreateReply::default()
+                .content("This server has no smote role yet! Set one with `/smite role` first.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let (smote_user, extended) = match smite_user(
+        ctx.http(),
+        smite,
+        guild_id.get(),
+        member.id.get(),
+        role_id,
+        duration,
+        reason.clone(),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::error!("Error when smiting user: {}", e);
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "I couldn't give <@&{role_id}> to {}!",
+                        &member.name
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
         }
+    };
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::Smite)
+            .with_target(CaseTarget::User(member.id.get()))
+            .with_reason(reason)
+            .with_details(format!(
+                "{} for {duration_text}, due <t:{}:F>.",
+                if extended { "Extended" } else { "Smitten" },
+                smote_user.due_time.timestamp()
+            )),
+    )
+    .await;
+
+    let gif_link = {
+        let mut rng = rand::rng();
+        smite
+            .smite_gif_links
+            .choose(&mut rng)
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+    };
+    let remaining = format_duration(smote_user.due_time - Utc::now());
+    let summary = if extended {
+        format!(
+            "<@{}>'s smite has been extended. Time remaining: {remaining}.",
+            member.id
+        )
+    } else {
+        format!("<@{}> has been smitten for {remaining}.", member.id)
+    };
+    let reason = smote_user
+        .reason
+        .as_ref()
+        .map(|reason| format!("\nReason: {reason}"))
+        .unwrap_or_default();
+    ctx.send(CreateReply::default().content(format!("{summary}{reason}\n{gif_link}")))
+        .await?;
+
+    Ok(())
+}
+
+/// List the smitten members of this server and their remaining time.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: Context<'_>) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let now = Utc::now();
+    let smote_users = ctx
+        .data()
+        .smite
+        .smote_user_list
+        .read()
+        .await
+        .smote_users
+        .iter()
+        .filter(|u| u.guild_id == guild_id)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if smote_users.is_empty() {
+        ctx.send(CreateReply::default().content("Nobody is smitten in this server right now."))
+            .await?;
+        return Ok(());
+    }
+
+    let content = smote_users
+        .iter()
+        .map(|u| {
+            format!(
+                "<@{}> - {} remaining{}",
+                u.user_id,
+                format_duration(u.due_time - now),
+                u.reason
+                    .as_ref()
+                    .map(|reason| format!(" - Reason: {reason}"))
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("Smitten members:\n{content}"))
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Set the role given to smitten members of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn role(
+    ctx: Context<'_>,
+    #[description = "The role given to smitten members."] role: Role,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    {
+        let mut smite_settings_write_lock = ctx.data().smite.smite_settings.write().await;
+        smite_settings_write_lock
+            .smote_role_ids
+            .insert(guild_id.to_string(), role.id.get());
+        smite_settings_write_lock.write_smite_settings()?;
     }
+    log_case(
+        ctx,
+        new_case(ctx, CaseAction::SetSmoteRole).with_target(CaseTarget::Role(role.id.get())),
+    )
+    .await;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Smitten members of this server will now be given <@&{}>.",
+                role.id
+            ))
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lift the smite from a member before it's due.
+#[poise::command(slash_command, guild_only, custom_data = "RESTRICTED")]
+pub async fn unsmite(
+    ctx: Context<'_>,
+    #[description = "The smitten member to forgive."] member: User,
+) -> Result<(), ContextError> {
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let lifted = remove_smote_user(
+        ctx.http(),
+        ctx.data().smite.smote_user_list.clone(),
+        guild_id,
+        member.id.get(),
+        true,
+    )
+    .await?;
+    cancel_job(&ScheduledAction::Unsmite {
+        guild_id,
+        user_id: member.id.get(),
+    });
+
+    if lifted {
+        log_case(
+            ctx,
+            new_case(ctx, CaseAction::Unsmite).with_target(CaseTarget::User(member.id.get())),
+        )
+        .await;
+    }
+
+    let content = if lifted {
+        format!("<@{}> has been forgiven.", member.id)
+    } else {
+        format!("<@{}> isn't smitten!", member.id)
+    };
+    ctx.send(CreateReply::default().content(content)).await?;
 
     Ok(())
 }
@@ -1,11 +1,27 @@
 use crate::commands::utility::translate::{LanguageModel, Novel};
-use crate::shared::services::open_router_service::translate_with_model;
+use crate::shared::services::open_router_service::{judge_translations, translate_text_with_model};
+use crate::shared::services::rate_limit_service::acquire_llm_tokens;
+use crate::shared::services::translation_report_service::build_comparison_report;
+use crate::shared::structs::utility::translation::ModelTranslation;
 use crate::shared::structs::{Context, ContextError};
 use poise::CreateReply;
-use serenity::all::{Attachment, CreateAttachment};
+use serenity::all::{
+    Attachment, ComponentInteractionCollector, ComponentInteractionDataKind, CreateActionRow,
+    CreateAttachment, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+};
+use std::time::Instant;
 use tokio::task::JoinSet;
 
-/// Translate English to traditional Chinese. This is designed for Tetsu's Forged in Starlight.
+const MODEL_SELECTION_TIMEOUT: u64 = 120;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, poise::ChoiceParameter)]
+pub enum ReportFormat {
+    Markdown,
+    #[name = "HTML"]
+    Html,
+}
+
+/// Translate English to traditional Chinese with multiple models and compare the results side by side.
 #[poise::command(slash_command, category = "Utility")]
 pub async fn batch_translate(
     ctx: Context<'_>,
@@ -13,55 +29,201 @@ pub async fn batch_translate(
     #[description = "The document to translate to traditional Chinese."] file: Attachment,
     #[description = "Whether to translate with o1 (High) as well. Default to false."]
     with_o1: Option<bool>,
+    #[description = "Whether to pick the models to compare from a list. Default to false."]
+    pick_models: Option<bool>,
+    #[description = "The format of the comparison report. Default to Markdown."] format: Option<
+        ReportFormat,
+    >,
+    #[description = "Whether to score and rank the translations with a judge model. Default to false."]
+    judge: Option<bool>,
 ) -> Result<(), ContextError> {
     ctx.defer().await?;
 
-    let mut join_set = JoinSet::new();
-    let with_o1 = with_o1.unwrap_or(false);
-
-    let models = if with_o1 {
+    let models = if pick_models.unwrap_or(false) {
+        match select_models(ctx).await? {
+            Some(models) => models,
+            None => return Ok(()),
+        }
+    } else if with_o1.unwrap_or(false) {
         LanguageModel::all()
     } else {
         LanguageModel::all_except_o1()
     };
 
-    for model in models.into_iter() {
-        let instructions = match novel {
-            Novel::ForgedInStarlight => ctx.data().forged_in_starlight_instructions.clone(),
-            Novel::Chronosplit => ctx.data().chronosplit_instructions.clone(),
-        };
+    // Every model translates once and has its translation judged once, so the whole batch is paid upfront.
+    let call_count = models.len() * if judge.unwrap_or(false) { 2 } else { 1 };
+    if let Err(e) = acquire_llm_tokens(ctx.data().kou, call_count as u32) {
+        ctx.send(CreateReply::default().content(e.to_string()))
+            .await?;
+        return Ok(());
+    }
+
+    let raw_bytes = file.download().await?;
+    let text = String::from_utf8(raw_bytes)?;
+
+    let instructions = match novel {
+        Novel::ForgedInStarlight => ctx.data().forged_in_starlight_instructions.clone(),
+        Novel::Chronosplit => ctx.data().chronosplit_instructions.clone(),
+    };
+
+    let mut join_set = JoinSet::new();
+    for (index, model) in models.into_iter().enumerate() {
+        let instructions = instructions.clone();
         let openai_client = ctx.data().openai_client.clone();
         let openai_compatible_clients = ctx.data().openai_compatible_clients.clone();
-        let attachment = file.clone();
+        let text = text.clone();
         join_set.spawn(async move {
-            let result = translate_with_model(
+            let start = Instant::now();
+            let result = translate_text_with_model(
                 novel,
                 instructions,
                 openai_client,
                 openai_compatible_clients,
-                attachment,
+                text,
                 model,
             )
-            .await;
-            match result {
-                Ok(s) => {
-                    format!("{model}:\n{s}")
-                }
-                Err(e) => {
-                    format!("Failed to get response using {model}: {e:?}")
-                }
-            }
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get response using {}: {:?}", model, e);
+                e.to_string()
+            });
+
+            let translation = ModelTranslation {
+                model,
+                latency: start.elapsed(),
+                result,
+            };
+            (index, translation)
         });
     }
 
-    let results = join_set.join_all().await;
+    let mut results = join_set.join_all().await;
+    results.sort_by_key(|(index, _)| *index);
+    let translations = results
+        .into_iter()
+        .map(|(_, translation)| translation)
+        .collect::<Vec<_>>();
+
+    let judgements = if judge.unwrap_or(false) {
+        Some(
+            judge_translations(
+                instructions,
+                ctx.data().openai_compatible_clients.clone(),
+                &text,
+                &translations,
+            )
+            .await,
+        )
+    } else {
+        None
+    };
+
+    let failed_count = translations.iter().filter(|t| t.result.is_err()).count();
+    let mut content = format!(
+        "Translated with {} models ({} failed).",
+        translations.len(),
+        failed_count
+    );
+    if let Some(ref judgements) = judgements {
+        let ranking = judgements
+            .iter()
+            .filter_map(|judgement| judgement.total().map(|total| (judgement.model, total)))
+            .take(3)
+            .enumerate()
+            .map(|(rank, (model, total))| format!("{}) {} with {} points", rank + 1, model, total))
+            .collect::<Vec<_>>();
+        content += &format!("\nTop translations:\n{}", ranking.join("\n"));
+
+        let not_judged = judgements
+            .iter()
+            .filter(|judgement| judgement.result.is_err())
+            .map(|judgement| judgement.model.to_string())
+            .collect::<Vec<_>>();
+        if !not_judged.is_empty() {
+            content += &format!("\nNot judged: {}", not_judged.join(", "));
+        }
+    }
+
+    let format = format.unwrap_or(ReportFormat::Markdown);
+    let report = build_comparison_report(&text, &translations, judgements.as_deref(), format);
+    let file_name = match format {
+        ReportFormat::Markdown => "comparison.md",
+        ReportFormat::Html => "comparison.html",
+    };
 
-    let response = results.join("\n--------------------\n");
     ctx.send(
         CreateReply::default()
-            .attachment(CreateAttachment::bytes(response.as_bytes(), "result.txt")),
+            .content(content)
+            .attachment(CreateAttachment::bytes(report.as_bytes(), file_name)),
     )
     .await?;
 
     Ok(())
 }
+
+async fn select_models(ctx: Context<'_>) -> Result<Option<Vec<LanguageModel>>, ContextError> {
+    let all_models = LanguageModel::all();
+    let options = all_models
+        .iter()
+        .enumerate()
+        .map(|(index, model)| CreateSelectMenuOption::new(model.to_string(), index.to_string()))
+        .collect::<Vec<_>>();
+
+    let custom_id = format!("batch_translate_models_{}", ctx.id());
+    let reply_handle = ctx
+        .send(
+            CreateReply::default()
+                .content(format!(
+                    "Please select the models to compare in {} seconds!",
+                    MODEL_SELECTION_TIMEOUT
+                ))
+                .components(vec![CreateActionRow::SelectMenu(
+                    CreateSelectMenu::new(&custom_id, CreateSelectMenuKind::String { options })
+                        .placeholder("Select models!")
+                        .min_values(1)
+                        .max_values(all_models.len() as u8),
+                )]),
+        )
+        .await?;
+
+    let interaction = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(std::time::Duration::from_secs(MODEL_SELECTION_TIMEOUT))
+        .filter(move |mci| mci.data.custom_id == custom_id)
+        .await;
+
+    let Some(interaction) = interaction else {
+        reply_handle
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content("No models were selected. Cancelling...")
+                    .components(vec![]),
+            )
+            .await?;
+        return Ok(None);
+    };
+
+    interaction.defer(ctx.http()).await?;
+
+    let models = match interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { ref values } => values
+            .iter()
+            .filter_map(|value| value.parse::<usize>().ok())
+            .filter_map(|index| all_models.get(index).copied())
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(format!("Translating with {} models...", models.len()))
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(Some(models))
+}
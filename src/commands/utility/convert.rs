@@ -1,5 +1,6 @@
 use poise::CreateReply;
 
+use crate::shared::structs::config::channel_control::ALWAYS_ALLOWED;
 use crate::shared::structs::utility::convert::ConverterType;
 use crate::shared::structs::utility::convert::exchange_rate_api_response::ExchangeRateAPIResponse;
 use crate::shared::structs::utility::convert::length::Length;
@@ -14,7 +15,8 @@ const EXCHANGE_RATE_API_BASE_URL: &str = "http://api.exchangeratesapi.io/v1/late
     slash_command,
     subcommands("length", "weight", "temperature", "currency"),
     subcommand_required,
-    category = "Utility"
+    category = "Utility",
+    custom_data = "ALWAYS_ALLOWED"
 )]
 pub async fn convert(_: Context<'_>) -> Result<(), ContextError> {
     Ok(())
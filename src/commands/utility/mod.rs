@@ -6,3 +6,5 @@ pub mod image;
 pub mod pick;
 pub mod save_file;
 pub mod translate;
+pub mod translate_message;
+pub mod translation_review;
@@ -2,6 +2,7 @@ use poise::CreateReply;
 use serenity::all::Message;
 
 use crate::shared::structs::authentication::login;
+use crate::shared::structs::config::channel_control::ALWAYS_ALLOWED;
 use crate::shared::structs::utility::save_file::SaveFileRequest;
 use crate::shared::structs::{ContextData, ContextError};
 
@@ -17,7 +18,8 @@ struct FilenameModal {
 #[poise::command(
     context_menu_command = "Save File to Tetsu's Server",
     required_permissions = "ADMINISTRATOR",
-    owners_only
+    owners_only,
+    custom_data = "ALWAYS_ALLOWED"
 )]
 pub async fn save_file(
     ctx: poise::ApplicationContext<'_, ContextData, ContextError>,
@@ -1,9 +1,17 @@
 use poise::CreateReply;
-use serenity::all::Attachment;
+use serde::{Deserialize, Serialize};
+use serenity::all::{Attachment, CreateAllowedMentions, CreateAttachment};
 use std::fmt::{Display, Formatter};
 
-use crate::shared::services::open_router_service::translate_with_model;
-use crate::shared::structs::{Context, ContextError};
+use crate::commands::utility::translation_review::review;
+use crate::shared::services::open_router_service::{translate_general_text, translate_with_model};
+use crate::shared::services::rate_limit_service::throttled_message;
+use crate::shared::services::translation_memory_service::translate_with_memory;
+use crate::shared::structs::record::user_record::write_user_records;
+use crate::shared::structs::utility::translation::GeneralTranslation;
+use crate::shared::structs::{Context, ContextData, ContextError};
+
+const MAX_MESSAGE_LENGTH: usize = 1900;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, poise::ChoiceParameter)]
 pub enum LanguageModel {
@@ -54,14 +62,49 @@ pub enum Novel {
     Chronosplit,
 }
 
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize, poise::ChoiceParameter,
+)]
+pub enum TargetLanguage {
+    #[default]
+    English,
+    #[name = "Traditional Chinese"]
+    TraditionalChinese,
+    #[name = "Simplified Chinese"]
+    SimplifiedChinese,
+    Japanese,
+    Korean,
+    Spanish,
+    French,
+    German,
+    Portuguese,
+    Russian,
+    Indonesian,
+    Vietnamese,
+    Thai,
+}
+
+/// Translate novels, text or messages with language models.
+#[poise::command(
+    slash_command,
+    subcommands("novel", "text", "language", "review"),
+    subcommand_required,
+    category = "Utility"
+)]
+pub async fn translate(_: Context<'_>) -> Result<(), ContextError> {
+    Ok(())
+}
+
 /// Translate English to traditional Chinese. This is designed for Tetsu's Forged in Starlight.
-#[poise::command(slash_command, category = "Utility")]
-pub async fn translate(
+#[poise::command(slash_command)]
+pub async fn novel(
     ctx: Context<'_>,
     #[description = "The novel's title to translate."] novel: Novel,
     #[description = "The document to translate to traditional Chinese."] file: Attachment,
     #[description = "The language model to use to translate. Default to DeepSeek-v3."]
     model: Option<LanguageModel>,
+    #[description = "Whether to reuse previous translations of unchanged paragraphs. Default to true."]
+    use_memory: Option<bool>,
 ) -> Result<(), ContextError> {
     let reply_handle = ctx
         .send(CreateReply::default().content("Translating..."))
@@ -75,23 +118,164 @@ pub async fn translate(
     let openai_client = ctx.data().openai_client.clone();
     let openai_compatible_clients = ctx.data().openai_compatible_clients.clone();
 
-    let result = translate_with_model(
-        novel,
-        instructions,
-        openai_client,
-        openai_compatible_clients,
-        file,
-        model,
-    )
-    .await?;
+    let is_kou = ctx.data().kou;
+    let translation = if use_memory.unwrap_or(true) {
+        let raw_bytes = file.download().await?;
+        let text = String::from_utf8(raw_bytes)?;
+        translate_with_memory(
+            novel,
+            instructions,
+            openai_client,
+            openai_compatible_clients,
+            ctx.data().translation_memory.clone(),
+            text,
+            model,
+            is_kou,
+        )
+        .await
+        .map(|result| {
+            let content = format!(
+                "Translated with {}. {} out of {} paragraphs were reused from the translation memory.",
+                model, result.cache_hits, result.total
+            );
+            (result.text, content)
+        })
+    } else {
+        translate_with_model(
+            novel,
+            instructions,
+            openai_client,
+            openai_compatible_clients,
+            file,
+            model,
+            is_kou,
+        )
+        .await
+        .map(|result| (result.text, format!("Translated with {model}.")))
+    };
+
+    let (result, content) = match translation {
+        Ok(translation) => translation,
+        Err(e) => {
+            let Some(error_message) = throttled_message(&e) else {
+                return Err(e.into());
+            };
+            reply_handle
+                .edit(ctx, CreateReply::default().content(error_message))
+                .await?;
+            return Ok(());
+        }
+    };
 
     reply_handle
-        .edit(ctx, CreateReply::default().content(result))
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(content)
+                .attachment(CreateAttachment::bytes(
+                    result.as_bytes(),
+                    "translation.txt",
+                )),
+        )
         .await?;
 
     Ok(())
 }
 
+/// Translate text to another language. The source language is detected automatically.
+#[poise::command(slash_command)]
+pub async fn text(
+    ctx: Context<'_>,
+    #[description = "The text to translate."] text: String,
+    #[description = "The language to translate to. Default to your preferred language."]
+    target: Option<TargetLanguage>,
+) -> Result<(), ContextError> {
+    ctx.defer().await?;
+
+    let target = match target {
+        Some(language) => language,
+        None => get_preferred_language(ctx.data(), ctx.author().id.get()).await,
+    };
+
+    match translate_general_text(ctx.data(), text, target).await {
+        Ok(translation) => {
+            ctx.send(
+                CreateReply::default()
+                    .content(format_general_translation(&translation, target))
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await?;
+        }
+        Err(e) => {
+            let error_message = throttled_message(&e)
+                .unwrap_or_else(|| format!("An error occurred when translating text: {e:?}"));
+            tracing::error!("{}", &error_message);
+            ctx.send(CreateReply::default().content(error_message))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Set your preferred language for translating text and messages.
+#[poise::command(slash_command)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Your preferred language to translate to."] language: TargetLanguage,
+) -> Result<(), ContextError> {
+    let user_records = ctx.data().user_records.clone();
+    {
+        let mut user_records_lock = user_records.write().await;
+        let user_record_entry = user_records_lock
+            .entry(ctx.author().id.get().to_string())
+            .or_default();
+        user_record_entry.translation_language = Some(language);
+    }
+
+    {
+        let user_records_lock = user_records.read().await;
+        write_user_records(&user_records_lock)?;
+    }
+
+    ctx.send(CreateReply::default().content(format!(
+        "Alright! I'll translate to **{language}** for you from now on."
+    )))
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_preferred_language(data: &ContextData, user_id: u64) -> TargetLanguage {
+    data.user_records
+        .read()
+        .await
+        .get(&user_id.to_string())
+        .and_then(|record| record.translation_language)
+        .unwrap_or_default()
+}
+
+pub fn format_general_translation(
+    translation: &GeneralTranslation,
+    target: TargetLanguage,
+) -> String {
+    let text = if translation.translation.chars().count() > MAX_MESSAGE_LENGTH {
+        let truncated = translation
+            .translation
+            .chars()
+            .take(MAX_MESSAGE_LENGTH)
+            .collect::<String>();
+        format!("{truncated}...")
+    } else {
+        translation.translation.clone()
+    };
+
+    format!(
+        "**{} → {}**\n{}",
+        &translation.source_language, target, text
+    )
+}
+
 impl LanguageModel {
     pub fn all() -> Vec<Self> {
         vec![
@@ -125,6 +309,12 @@ impl LanguageModel {
     }
 }
 
+impl Display for TargetLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", poise::ChoiceParameter::name(self))
+    }
+}
+
 impl Display for LanguageModel {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", <LanguageModel as Into<String>>::into(*self))
@@ -0,0 +1,47 @@
+use crate::commands::utility::translate::{format_general_translation, get_preferred_language};
+use crate::shared::services::open_router_service::translate_general_text;
+use crate::shared::services::rate_limit_service::throttled_message;
+use crate::shared::structs::{ContextData, ContextError};
+use poise::CreateReply;
+use serenity::all::{CreateAllowedMentions, Message};
+
+#[poise::command(context_menu_command = "Translate message")]
+pub async fn translate_message(
+    ctx: poise::ApplicationContext<'_, ContextData, ContextError>,
+    message: Message,
+) -> Result<(), ContextError> {
+    if message.content.trim().is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There's nothing to translate in this message!")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let target = get_preferred_language(ctx.data, ctx.author().id.get()).await;
+
+    match translate_general_text(ctx.data, message.content.clone(), target).await {
+        Ok(translation) => {
+            ctx.send(
+                CreateReply::default()
+                    .content(format_general_translation(&translation, target))
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await?;
+        }
+        Err(e) => {
+            let error_message = throttled_message(&e).unwrap_or_else(|| {
+                format!("An error occurred when translating the message: {e:?}")
+            });
+            tracing::error!("{}", &error_message);
+            ctx.send(CreateReply::default().content(error_message))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,383 @@
+use std::time::Duration;
+
+use poise::{CreateReply, execute_modal_on_component_interaction};
+use serenity::all::{
+    Attachment, AutoArchiveDuration, ButtonStyle, ChannelType, Color, ComponentInteractionDataKind,
+    CreateActionRow, CreateAttachment, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, CreateSelectMenu,
+    CreateSelectMenuKind, CreateSelectMenuOption, CreateThread, EditMessage, GuildChannel,
+};
+
+use crate::commands::utility::translate::{LanguageModel, Novel};
+use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::services::open_router_service::translate_text_with_model;
+use crate::shared::services::rate_limit_service::{acquire_llm_token, throttled_message};
+use crate::shared::services::translation_memory_service::remember;
+use crate::shared::structs::utility::translation::split_paragraphs;
+use crate::shared::structs::{Context, ContextData, ContextError};
+
+const REVIEW_TIMEOUT: u64 = 600;
+const MAX_PREVIEW_LENGTH: usize = 1900;
+const ACCEPT_BUTTON_ID: &str = "translation_review_accept";
+const EDIT_BUTTON_ID: &str = "translation_review_edit";
+const STOP_BUTTON_ID: &str = "translation_review_stop";
+const REROLL_MENU_ID: &str = "translation_review_reroll";
+
+type ApplicationContext<'a> = poise::ApplicationContext<'a, ContextData, ContextError>;
+
+#[derive(Debug, Clone, poise::Modal)]
+#[name = "Revise Translation"]
+struct RevisionModal {
+    #[name = "Translation"]
+    #[min_length = 1]
+    #[max_length = 4000]
+    #[paragraph]
+    pub translation: String,
+}
+
+enum ReviewOutcome {
+    Accepted(String),
+    Stopped,
+}
+
+/// Review a translation paragraph by paragraph in a thread.
+#[poise::command(slash_command, guild_only)]
+pub async fn review(
+    ctx: Context<'_>,
+    #[description = "The novel's title to translate."] novel: Novel,
+    #[description = "The document to translate to traditional Chinese."] file: Attachment,
+    #[description = "The language model to use to translate. Default to DeepSeek-v3."]
+    model: Option<LanguageModel>,
+) -> Result<(), ContextError> {
+    let Context::Application(app_context) = ctx else {
+        return Ok(());
+    };
+
+    let reply_handle = ctx
+        .send(CreateReply::default().content("Preparing the review thread..."))
+        .await?;
+
+    let raw_bytes = file.download().await?;
+    let text = String::from_utf8(raw_bytes)?;
+    let paragraphs = split_paragraphs(&text);
+
+    if paragraphs.is_empty() {
+        reply_handle
+            .edit(
+                ctx,
+                CreateReply::default().content("There's nothing to translate in this document!"),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let thread = ctx
+        .channel_id()
+        .create_thread(
+            ctx.http(),
+            CreateThread::new(format!("Translation Review: {}", &file.filename))
+                .kind(ChannelType::PublicThread)
+                .auto_archive_duration(AutoArchiveDuration::OneDay),
+        )
+        .await?;
+
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default().content(format!(
+                "Let's review the translation in <#{}>!",
+                thread.id.get()
+            )),
+        )
+        .await?;
+
+    let model = model.unwrap_or(LanguageModel::DeepSeekV3);
+    let total = paragraphs.len();
+    let mut accepted = Vec::with_capacity(total);
+
+    for (index, source) in paragraphs.iter().enumerate() {
+        let outcome = match review_paragraph(
+            app_context,
+            &thread,
+            novel,
+            model,
+            index,
+            total,
+            source,
+        )
+        .await
+        {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                // Keep what was accepted so far when the LLM calls are throttled.
+                let Some(error_message) = throttled_message(&e) else {
+                    return Err(e.into());
+                };
+                thread
+                    .send_message(ctx.http(), CreateMessage::new().content(error_message))
+                    .await?;
+                break;
+            }
+        };
+        match outcome {
+            ReviewOutcome::Accepted(translation) => accepted.push(translation),
+            ReviewOutcome::Stopped => break,
+        }
+    }
+
+    let assembled = accepted.join("\n\n");
+    thread
+        .send_message(
+            ctx.http(),
+            CreateMessage::new()
+                .content(format!(
+                    "Review finished! {} out of {} paragraphs were accepted.",
+                    accepted.len(),
+                    total
+                ))
+                .add_file(CreateAttachment::bytes(
+                    assembled.as_bytes(),
+                    "translation.txt",
+                )),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn review_paragraph(
+    ctx: ApplicationContext<'_>,
+    thread: &GuildChannel,
+    novel: Novel,
+    mut model: LanguageModel,
+    index: usize,
+    total: usize,
+    source: &str,
+) -> anyhow::Result<ReviewOutcome> {
+    let color = if ctx.data.kou { KOU_COLOR } else { TAIGA_COLOR };
+    let cached = ctx
+        .data
+        .translation_memory
+        .read()
+        .await
+        .get(novel, model, source)
+        .map(|entry| entry.translation.clone());
+    let mut translation = match cached {
+        Some(translation) => translation,
+        None => translate_paragraph(ctx.data, novel, model, source).await?,
+    };
+
+    let mut sent_msg = thread
+        .send_message(
+            ctx.http(),
+            CreateMessage::new()
+                .embed(build_review_embed(
+                    index,
+                    total,
+                    source,
+                    &translation,
+                    model,
+                    color,
+                ))
+                .components(build_review_components()),
+        )
+        .await?;
+
+    loop {
+        let interaction = sent_msg
+            .await_component_interaction(ctx.serenity_context())
+            .author_id(ctx.author().id)
+            .timeout(Duration::from_secs(REVIEW_TIMEOUT))
+            .await;
+
+        let Some(interaction) = interaction else {
+            sent_msg
+                .edit(ctx.http(), EditMessage::new().components(vec![]))
+                .await?;
+            thread
+                .say(ctx.http(), "No response in a while. Stopping the review...")
+                .await?;
+            return Ok(ReviewOutcome::Stopped);
+        };
+
+        match interaction.data.kind {
+            ComponentInteractionDataKind::Button => match interaction.data.custom_id.as_str() {
+                ACCEPT_BUTTON_ID => {
+                    interaction
+                        .create_response(
+                            ctx.http(),
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new().components(vec![]),
+                            ),
+                        )
+                        .await?;
+                    remember(
+                        &ctx.data.translation_memory,
+                        novel,
+                        model,
+                        vec![(source.to_string(), translation.clone())],
+                    )
+                    .await?;
+                    return Ok(ReviewOutcome::Accepted(translation));
+                }
+                EDIT_BUTTON_ID => {
+                    let defaults = RevisionModal {
+                        translation: translation.clone(),
+                    };
+                    if let Some(revision) = execute_modal_on_component_interaction(
+                        ctx,
+                        interaction.clone(),
+                        Some(defaults),
+                        Some(Duration::from_secs(REVIEW_TIMEOUT)),
+                    )
+                    .await?
+                    {
+                        translation = revision.translation;
+                        sent_msg
+                            .edit(
+                                ctx.http(),
+                                EditMessage::new()
+                                    .embed(build_review_embed(
+                                        index,
+                                        total,
+                                        source,
+                                        &translation,
+                                        model,
+                                        color,
+                                    ))
+                                    .components(vec![]),
+                            )
+                            .await?;
+                        remember(
+                            &ctx.data.translation_memory,
+                            novel,
+                            model,
+                            vec![(source.to_string(), translation.clone())],
+                        )
+                        .await?;
+                        return Ok(ReviewOutcome::Accepted(translation));
+                    }
+                }
+                STOP_BUTTON_ID => {
+                    interaction
+                        .create_response(
+                            ctx.http(),
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new().components(vec![]),
+                            ),
+                        )
+                        .await?;
+                    return Ok(ReviewOutcome::Stopped);
+                }
+                _ => {}
+            },
+            ComponentInteractionDataKind::StringSelect { ref values } => {
+                interaction.defer(ctx.http()).await?;
+                let selected_model = values
+                    .first()
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .and_then(|index| LanguageModel::all().get(index).copied());
+
+                if let Some(selected_model) = selected_model {
+                    model = selected_model;
+                    translation = translate_paragraph(ctx.data, novel, model, source).await?;
+                    sent_msg
+                        .edit(
+                            ctx.http(),
+                            EditMessage::new().embed(build_review_embed(
+                                index,
+                                total,
+                                source,
+                                &translation,
+                                model,
+                                color,
+                            )),
+                        )
+                        .await?;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn translate_paragraph(
+    data: &ContextData,
+    novel: Novel,
+    model: LanguageModel,
+    source: &str,
+) -> anyhow::Result<String> {
+    let instructions = match novel {
+        Novel::ForgedInStarlight => data.forged_in_starlight_instructions.clone(),
+        Novel::Chronosplit => data.chronosplit_instructions.clone(),
+    };
+
+    acquire_llm_token(data.kou)?;
+    let output = translate_text_with_model(
+        novel,
+        instructions,
+        data.openai_client.clone(),
+        data.openai_compatible_clients.clone(),
+        source.to_string(),
+        model,
+    )
+    .await?;
+
+    Ok(output.text)
+}
+
+fn build_review_embed(
+    index: usize,
+    total: usize,
+    source: &str,
+    translation: &str,
+    model: LanguageModel,
+    color: Color,
+) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(format!("Paragraph {}/{}", index + 1, total))
+        .color(color)
+        .description(format!(
+            "**Source**\n{}\n\n**Translation**\n{}",
+            truncate(source),
+            truncate(translation)
+        ))
+        .footer(CreateEmbedFooter::new(format!("Translated with {model}")))
+}
+
+fn build_review_components() -> Vec<CreateActionRow> {
+    let options = LanguageModel::all()
+        .into_iter()
+        .enumerate()
+        .map(|(index, model)| CreateSelectMenuOption::new(model.to_string(), index.to_string()))
+        .collect::<Vec<_>>();
+
+    vec![
+        CreateActionRow::Buttons(vec![
+            CreateButton::new(ACCEPT_BUTTON_ID)
+                .label("Accept")
+                .style(ButtonStyle::Success),
+            CreateButton::new(EDIT_BUTTON_ID)
+                .label("Edit")
+                .style(ButtonStyle::Primary),
+            CreateButton::new(STOP_BUTTON_ID)
+                .label("Stop Review")
+                .style(ButtonStyle::Danger),
+        ]),
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(REROLL_MENU_ID, CreateSelectMenuKind::String { options })
+                .placeholder("Re-roll with another model!")
+                .min_values(1)
+                .max_values(1),
+        ),
+    ]
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_PREVIEW_LENGTH {
+        let truncated = text.chars().take(MAX_PREVIEW_LENGTH).collect::<String>();
+        format!("{truncated}...")
+    } else {
+        text.to_string()
+    }
+}
@@ -8,7 +8,10 @@ use crate::event_handler::responses::greet::greet;
 use crate::event_handler::responses::handle_bot_responses;
 use crate::event_handler::responses::qotd::handle_qotd;
 use crate::shared::constants::KOU_SERVER_ID;
+use crate::shared::services::anti_spam_service::{handle_member_join, handle_spam};
 use crate::shared::services::message_service::record_message;
+use crate::shared::services::scheduler_service::start_scheduler;
+use crate::shared::structs::game::game_session::cancel_interrupted_game_sessions;
 use crate::shared::structs::smite::schedule_unsmite;
 use crate::shared::structs::{ContextData, ContextError};
 
@@ -23,7 +26,13 @@ pub async fn handle_event(
 ) -> Result<(), ContextError> {
     match event {
         FullEvent::GuildMemberAddition { new_member } => {
-            if new_member.guild_id.get() == KOU_SERVER_ID {
+            let is_raided = handle_member_join(ctx, data, new_member.guild_id.get())
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::error!("Error when checking for a raid: {}", e);
+                    false
+                });
+            if is_raided || new_member.guild_id.get() == KOU_SERVER_ID {
                 return Ok(());
             }
 
@@ -37,6 +46,12 @@ pub async fn handle_event(
             }
         }
         FullEvent::Message { new_message } => {
+            match handle_spam(ctx, new_message, data).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => tracing::error!("Error when handling spam: {}", e),
+            }
+
             if let Err(e) = handle_qotd(ctx, new_message, data).await {
                 tracing::error!("Error when handling qotd: {}", e);
             }
@@ -52,7 +67,11 @@ pub async fn handle_event(
         }
         FullEvent::Ready { data_about_bot } => {
             set_initial_presence(ctx, data).await;
-            schedule_unsmite(ctx, data).await;
+            schedule_unsmite(data).await;
+            start_scheduler(ctx, data);
+            if let Err(e) = cancel_interrupted_game_sessions(&ctx.http).await {
+                tracing::error!("Error when cancelling interrupted games: {}", e);
+            }
             tracing::info!("{} is now online.", data_about_bot.user.name);
         }
         _ => {}
@@ -1,6 +1,7 @@
 use crate::shared::services::open_router_service::{
     categorize_question, opine_conversation, opine_specific,
 };
+use crate::shared::services::rate_limit_service::throttled_message;
 use crate::shared::structs::ContextData;
 use serenity::model::prelude::Message;
 use serenity::prelude::*;
@@ -31,7 +32,8 @@ pub async fn handle_mention_self(
                             new_message.reply(&ctx.http, response).await?;
                         }
                         Err(e) => {
-                            let error_message = format!("Failed to reply to mention: {e:?}");
+                            let error_message = throttled_message(&e)
+                                .unwrap_or_else(|| format!("Failed to reply to mention: {e:?}"));
                             tracing::error!("{}", &error_message);
                             new_message.reply(&ctx.http, error_message).await?;
                         }
@@ -42,7 +44,8 @@ pub async fn handle_mention_self(
                             new_message.reply(&ctx.http, response).await?;
                         }
                         Err(e) => {
-                            let error_message = format!("Failed to reply to mention: {e:?}");
+                            let error_message = throttled_message(&e)
+                                .unwrap_or_else(|| format!("Failed to reply to mention: {e:?}"));
                             tracing::error!("{}", &error_message);
                             new_message.reply(&ctx.http, error_message).await?;
                         }
@@ -50,7 +53,8 @@ pub async fn handle_mention_self(
                 }
             }
             Err(e) => {
-                let error_message = format!("Failed to reply to mention: {e:?}");
+                let error_message = throttled_message(&e)
+                    .unwrap_or_else(|| format!("Failed to reply to mention: {e:?}"));
                 tracing::error!("{}", &error_message);
                 new_message.reply(&ctx.http, error_message).await?;
             }
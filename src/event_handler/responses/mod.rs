@@ -3,7 +3,9 @@ use crate::event_handler::responses::mention::handle_mention_self;
 use crate::event_handler::responses::reaction::handle_reactions;
 use crate::event_handler::responses::response::handle_responses;
 use crate::shared::services::open_router_service::build_reply_to_message_chain;
+use crate::shared::services::rate_limit_service::throttled_message;
 use crate::shared::structs::ContextData;
+use crate::shared::structs::config::channel_control::RESPONSES_CATEGORY;
 use serenity::all::{GuildChannel, PrivateChannel};
 use serenity::model::prelude::Message;
 use serenity::prelude::*;
@@ -29,6 +31,17 @@ pub async fn handle_bot_responses(
         tracing::error!("Failed to reply to self mention: {}", e);
     }
 
+    let guild_id = new_message.guild_id.unwrap_or_default().get();
+    let channel_id = new_message.channel_id.get();
+    let is_response_channel = data.channel_control.read().await.is_category_allowed(
+        guild_id,
+        channel_id,
+        RESPONSES_CATEGORY,
+    );
+    if !is_response_channel {
+        return Ok(());
+    }
+
     if let Some(original_message) = new_message.referenced_message.as_ref() {
         if original_message.author.id.get() == data.config.bot_id {
             let channel = new_message.channel(&ctx.http).await?;
@@ -64,7 +77,8 @@ pub async fn handle_bot_responses(
                     new_message.reply(&ctx.http, response).await?;
                 }
                 Err(e) => {
-                    let error_message = format!("Failed to reply to message chain: {e:?}");
+                    let error_message = throttled_message(&e)
+                        .unwrap_or_else(|| format!("Failed to reply to message chain: {e:?}"));
                     tracing::error!("{}", &error_message);
                     new_message.reply(&ctx.http, error_message).await?;
                 }
@@ -74,16 +88,11 @@ pub async fn handle_bot_responses(
         }
     }
 
-    let channel_control = data.channel_control.clone();
-
-    let is_channel_ignored = {
-        channel_control
-            .read()
-            .await
-            .ignored_channels
-            .iter()
-            .any(|channel_id| *channel_id == new_message.channel_id.get())
-    };
+    let is_channel_ignored = data
+        .channel_control
+        .read()
+        .await
+        .is_ignored(guild_id, channel_id);
 
     if is_channel_ignored {
         return Ok(());
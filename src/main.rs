@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
+use poise::FrameworkError;
 use poise::{BoxFuture, PrefixFrameworkOptions, serenity_prelude as serenity};
-use poise::{CreateReply, FrameworkError};
 use serenity::all::{CreateAllowedMentions, GatewayIntents};
 use tokio::sync::RwLock;
 use tracing::Level;
@@ -11,19 +11,32 @@ use shared::structs::record::*;
 
 use crate::event_handler::handle_event;
 use crate::shared::constants::CONFIG_DIRECTORY;
+use crate::shared::services::error_service::{
+    describe_framework_error, new_error_id, reply_with_error,
+};
 use crate::shared::services::openai_service::initialize_openai_client;
+use crate::shared::services::permission_service::{has_command_permission, permission_denied};
+use crate::shared::services::rate_limit_service::{
+    apply_command_cooldowns, configure_llm_rate_limit,
+};
 use crate::shared::structs::authentication::Authentication;
+use crate::shared::structs::config::channel_control::CommandOptions;
+use crate::shared::structs::config::command_permission::initialize_command_permissions;
 use crate::shared::structs::config::common_settings::initialize_common_settings;
 use crate::shared::structs::config::random_response::initialize_random_response;
+use crate::shared::structs::config::rate_limit::initialize_rate_limits;
 use crate::shared::structs::config::server_info::initialize_server_infos;
 use crate::shared::structs::fun::emote::initialize_emote_list;
 use crate::shared::structs::fun::qotd::initialize_qotd_infos;
 use crate::shared::structs::fun::ship_message::initialize_ship_messages;
+use crate::shared::structs::game::hangman_question::initialize_hangman_words;
 use crate::shared::structs::game::quiz_question::initialize_quiz_questions;
 use crate::shared::structs::information::character::{initialize_routes, initialize_valentines};
 use crate::shared::structs::information::oracle::initialize_oracles;
+use crate::shared::structs::moderation::initialize_moderation;
 use crate::shared::structs::smite::initialize_smite;
 use crate::shared::structs::utility::convert::conversion_table::initialize_conversion_table;
+use crate::shared::structs::utility::translation_memory::initialize_translation_memory;
 use crate::shared::structs::{Context, ContextData, ContextError, OpenAICompatibleClients};
 
 mod commands;
@@ -37,6 +50,8 @@ async fn main() -> anyhow::Result<()> {
         .collect::<Vec<_>>();
 
     let channel_control = channel_control::initialize()?;
+    let rate_limits = initialize_rate_limits()?;
+    configure_llm_rate_limit(rate_limits.llm);
     let user_records = user_record::initialize()?;
 
     let kou = args.contains(&"kou".to_string());
@@ -49,6 +64,8 @@ async fn main() -> anyhow::Result<()> {
         config,
         kou,
         channel_control: Arc::new(RwLock::new(channel_control)),
+        command_permissions: Arc::new(RwLock::new(initialize_command_permissions()?)),
+        rate_limits: Arc::new(RwLock::new(rate_limits)),
         user_records: Arc::new(RwLock::new(user_records)),
         routes: initialize_routes(),
         valentines: initialize_valentines(),
@@ -61,13 +78,16 @@ async fn main() -> anyhow::Result<()> {
         qotd_infos: Arc::new(RwLock::new(initialize_qotd_infos()?)),
         common_settings: initialize_common_settings(kou)?,
         ship_messages: initialize_ship_messages(),
-        quiz_questions: initialize_quiz_questions(kou),
+        quiz_questions: Arc::new(RwLock::new(initialize_quiz_questions(kou)?)),
+        hangman_words: Arc::new(RwLock::new(initialize_hangman_words()?)),
         smite: initialize_smite()?,
+        moderation: initialize_moderation()?,
         openai_client,
         random_response: initialize_random_response()?,
         forged_in_starlight_instructions: load_forged_in_starlight_instructions()?,
         chronosplit_instructions: load_chronosplit_instructions()?,
         openai_compatible_clients: Arc::new(openai_compatible_clients),
+        translation_memory: Arc::new(RwLock::new(initialize_translation_memory()?)),
     };
 
     if context_data.config.token.is_empty() {
@@ -118,13 +138,22 @@ async fn main() -> anyhow::Result<()> {
                 commands::fun::ship::ship(),
                 commands::admin::admin(),
                 commands::game::game(),
+                commands::game::quiz_bank::quiz_bank(),
+                commands::game::hangman_words::hangman_words(),
                 commands::information::guide::guide(),
                 commands::smite::smite(),
+                commands::smite::unsmite(),
+                commands::moderation::case::case(),
+                commands::moderation::warn::warn(),
+                commands::moderation::warn::warnings(),
+                commands::moderation::permissions::permissions(),
+                commands::moderation::anti_spam::anti_spam(),
                 commands::utility::save_file::save_file(),
                 commands::fun::answer_anon::answer_anon(),
                 commands::utility::translate::translate(),
                 commands::fun::what_do_you_think::what_do_you_think(),
                 commands::utility::batch_translate::batch_translate(),
+                commands::utility::translate_message::translate_message(),
             ],
             on_error: |error| Box::pin(handle_error(error)),
             command_check: Some(check_command),
@@ -152,6 +181,10 @@ async fn main() -> anyhow::Result<()> {
         })
         .setup(|ctx, _ready, framework| {
             Box::pin(async move {
+                apply_command_cooldowns(
+                    &framework.options().commands,
+                    &*context_data.rate_limits.read().await,
+                );
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 Ok(context_data)
             })
@@ -172,94 +205,127 @@ async fn main() -> anyhow::Result<()> {
 }
 
 async fn handle_error(framework_error: FrameworkError<'_, ContextData, ContextError>) {
+    let error_id = new_error_id();
+    log_framework_error(&framework_error, &error_id);
+
+    let Some(ctx) = framework_error.ctx() else {
+        return;
+    };
+    if let Some(message) = describe_framework_error(&framework_error, ctx.data().kou) {
+        reply_with_error(ctx, &message, &error_id).await;
+    }
+}
+
+fn log_framework_error(
+    framework_error: &FrameworkError<'_, ContextData, ContextError>,
+    error_id: &str,
+) {
     match framework_error {
         FrameworkError::Setup { error, .. } => {
-            tracing::error!("Failed to start bot: {}", error.to_string());
+            tracing::error!("[{}] Failed to start bot: {}", error_id, error.to_string());
         }
         FrameworkError::EventHandler { error, event, .. } => {
             tracing::error!(
-                "Failed to handle event {}, error: {}",
+                "[{}] Failed to handle event {}, error: {}",
+                error_id,
                 event.snake_case_name(),
                 error.to_string()
             );
         }
         FrameworkError::Command { error, ctx, .. } => {
-            let command_name = ctx.command().name.as_str();
             tracing::error!(
-                "Failed to execute command {}, error: {}",
-                command_name,
+                "[{}] Failed to execute command {}, error: {}",
+                error_id,
+                ctx.command().qualified_name,
                 error.to_string()
             );
         }
-        FrameworkError::ArgumentParse { error, input, .. } => {
+        FrameworkError::CommandPanic { payload, ctx, .. } => {
             tracing::error!(
-                "Failed to parse argument {:?}, error: {}",
-                &input,
+                "[{}] Command {} panicked: {:?}",
+                error_id,
+                ctx.command().qualified_name,
+                payload
+            );
+        }
+        FrameworkError::ArgumentParse {
+            error, input, ctx, ..
+        } => {
+            tracing::error!(
+                "[{}] Failed to parse argument {:?} of command {}, error: {}",
+                error_id,
+                input,
+                ctx.command().qualified_name,
                 error.to_string()
             );
         }
         FrameworkError::MissingBotPermissions {
             missing_permissions,
+            ctx,
             ..
         } => {
             tracing::error!(
-                "Bot is missing required permission: {:?}",
+                "[{}] Bot is missing required permission for command {}: {:?}",
+                error_id,
+                ctx.command().qualified_name,
                 missing_permissions
             )
         }
         FrameworkError::CommandCheckFailed { error, ctx, .. } => {
-            let result = ctx.send(CreateReply::default().content("Either this channel is not enabled for commands, or you're not calling in a guild!")).await;
-
-            if let Ok(reply_handle) = result {
-                tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
-                if let Err(e) = reply_handle.delete(ctx).await {
-                    tracing::error!(
-                        "Error when deleting original response to slash command: {}",
-                        e
-                    );
-                }
-            }
-
-            let command_name = ctx.command().name.as_str();
             tracing::error!(
-                "Command check failed, command: {}, error: {:?}",
-                command_name,
+                "[{}] Command check failed, command: {}, error: {:?}",
+                error_id,
+                ctx.command().qualified_name,
                 error
             )
         }
         error => {
-            if let Err(e) = poise::builtins::on_error(error).await {
-                tracing::error!("An uncategorized error occurred: {}", e.to_string());
-            }
+            tracing::error!("[{}] An uncategorized error occurred: {}", error_id, error);
         }
     }
 }
 
-const SKIP_CHECK_COMMANDS: [&str; 12] = [
-    "save_file",
-    "answer_anon",
-    "enable",
-    "disable",
-    "allow",
-    "disallow",
-    "purge",
-    "convert",
-    "length",
-    "weight",
-    "temperature",
-    "currency",
-];
-
 fn check_command(ctx: Context<'_>) -> BoxFuture<'_, Result<bool, ContextError>> {
     Box::pin(check_command_async(ctx))
 }
 
 async fn check_command_async(ctx: Context<'_>) -> Result<bool, ContextError> {
-    let channel_id = ctx.channel_id();
-    let command_name = ctx.command().name.as_str();
-    let channel_control = ctx.data().channel_control.read().await;
-    Ok(SKIP_CHECK_COMMANDS.contains(&command_name)
-        || channel_control.enabled_channels.contains(&channel_id.get()))
+    if !has_command_permission(ctx).await {
+        return Err(permission_denied());
+    }
+
+    if is_always_allowed(ctx) {
+        return Ok(true);
+    }
+
+    let guild_id = ctx.guild_id().unwrap_or_default().get();
+    let root_command = ctx
+        .parent_commands()
+        .first()
+        .copied()
+        .unwrap_or(ctx.command());
+    let category = root_command
+        .category
+        .as_ref()
+        .map(|category| category.to_lowercase());
+    Ok(ctx.data().channel_control.read().await.is_command_allowed(
+        guild_id,
+        ctx.channel_id().get(),
+        category.as_deref(),
+    ))
+}
+
+/// Commands marked with `custom_data = "ALWAYS_ALLOWED"`, or whose parent command is, skip the channel control.
+fn is_always_allowed(ctx: Context<'_>) -> bool {
+    ctx.parent_commands()
+        .iter()
+        .chain(std::iter::once(&ctx.command()))
+        .any(|command| {
+            command
+                .custom_data
+                .downcast_ref::<CommandOptions>()
+                .is_some_and(|options| options.always_allowed)
+        })
 }
 
 fn load_forged_in_starlight_instructions() -> anyhow::Result<String> {
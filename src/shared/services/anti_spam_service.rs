@@ -0,0 +1,298 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serenity::all::{ChannelId, Context, GuildId, Message, MessageId};
+
+use crate::shared::services::moderation_service::{punish_member, record_case};
+use crate::shared::services::permission_service::is_bot_admin_role;
+use crate::shared::structs::ContextData;
+use crate::shared::structs::moderation::moderation_case::{CaseAction, CaseTarget, ModerationCase};
+use crate::shared::structs::moderation::moderation_settings::AntiSpamSettings;
+
+static INVITE_LINK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:discord\.gg|discord(?:app)?\.com/invite)/[\w-]+")
+        .expect("Failed to build invite link regex.")
+});
+/// Recent messages of every member, keyed by guild ID and user ID.
+static RECENT_MESSAGES: Lazy<Mutex<HashMap<(u64, u64), VecDeque<RecentMessage>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// Recent join times of every guild, keyed by guild ID.
+static RECENT_JOINS: Lazy<Mutex<HashMap<u64, VecDeque<Instant>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// Until when each guild is considered to be raided, keyed by guild ID.
+static RAIDS: Lazy<Mutex<HashMap<u64, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct RecentMessage {
+    channel_id: ChannelId,
+    message_id: MessageId,
+    content: String,
+    sent_at: Instant,
+}
+
+enum SpamKind {
+    DuplicateMessages(usize),
+    MassMentions(usize),
+    InviteLink,
+}
+
+impl Display for SpamKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpamKind::DuplicateMessages(count) => {
+                write!(f, "Anti-spam: sent the same message {count} times.")
+            }
+            SpamKind::MassMentions(count) => {
+                write!(f, "Anti-spam: mentioned {count} users and roles at once.")
+            }
+            SpamKind::InviteLink => write!(f, "Anti-spam: posted an invite link."),
+        }
+    }
+}
+
+/// Checks the message for spam and acts on it according to the guild's anti-spam settings.
+/// Returns whether the message is spam, in which case it shouldn't be processed any further.
+pub async fn handle_spam(
+    ctx: &Context,
+    message: &Message,
+    data: &ContextData,
+) -> anyhow::Result<bool> {
+    let Some(guild_id) = message.guild_id else {
+        return Ok(false);
+    };
+    if message.author.bot {
+        return Ok(false);
+    }
+
+    let settings = data
+        .moderation
+        .settings
+        .read()
+        .await
+        .get(guild_id.get())
+        .anti_spam;
+    if !settings.enabled || is_exempt(ctx, data, guild_id, message) {
+        return Ok(false);
+    }
+
+    let Some((kind, spam_messages)) = detect_spam(message, guild_id.get(), &settings) else {
+        return Ok(false);
+    };
+    tracing::info!(
+        "Detected spam from {} in guild {}: {}",
+        message.author.id,
+        guild_id,
+        &kind
+    );
+
+    if settings.delete_messages {
+        for (channel_id, message_id) in spam_messages.into_iter() {
+            if let Err(e) = channel_id.delete_message(&ctx.http, message_id).await {
+                tracing::error!("Failed to delete spam message {}: {}", message_id, e);
+            }
+        }
+    }
+
+    let user_id = message.author.id.get();
+    let case = ModerationCase::new(guild_id.get(), data.config.bot_id, CaseAction::SpamDetected)
+        .with_target(CaseTarget::User(user_id))
+        .with_reason(Some(kind.to_string()))
+        .with_details(format!("In <#{}>.", message.channel_id));
+    // The spam has been handled once detected, so failing to punish or record it is only logged.
+    let case = match settings.punishment.escalation_action() {
+        Some(action) => match punish_member(
+            &ctx.http,
+            data,
+            guild_id.get(),
+            user_id,
+            action,
+            &settings.punishment_duration,
+            case,
+        )
+        .await
+        {
+            Ok(case) => Some(case),
+            Err(e) => {
+                tracing::error!("Failed to punish spammer {}: {}", user_id, e);
+                None
+            }
+        },
+        None if settings.alert_mod_log => Some(case),
+        None => None,
+    };
+    if let Some(case) = case
+        && let Err(e) = record_case(&ctx.http, data, case).await
+    {
+        tracing::error!("Failed to record moderation case: {}", e);
+    }
+
+    Ok(true)
+}
+
+/// Tracks the join for raid detection. Returns whether the guild is being raided,
+/// in which case new members shouldn't be greeted.
+pub async fn handle_member_join(
+    ctx: &Context,
+    data: &ContextData,
+    guild_id: u64,
+) -> anyhow::Result<bool> {
+    let settings = data
+        .moderation
+        .settings
+        .read()
+        .await
+        .get(guild_id)
+        .anti_spam;
+    if !settings.enabled || settings.raid_joins == 0 {
+        return Ok(false);
+    }
+
+    let now = Instant::now();
+    let join_count = {
+        let mut recent_joins = lock(&RECENT_JOINS);
+        let joins = recent_joins.entry(guild_id).or_default();
+        joins.push_back(now);
+        let window = Duration::from_secs(settings.raid_window_seconds);
+        while joins
+            .front()
+            .is_some_and(|joined_at| now.duration_since(*joined_at) > window)
+        {
+            joins.pop_front();
+        }
+        joins.len()
+    };
+
+    let (is_raided, is_new_raid) = {
+        let mut raids = lock(&RAIDS);
+        let was_raided = raids.get(&guild_id).is_some_and(|until| *until > now);
+        if join_count >= settings.raid_joins {
+            raids.insert(
+                guild_id,
+                now + Duration::from_secs(settings.raid_cooldown_minutes * 60),
+            );
+            (true, !was_raided)
+        } else {
+            (was_raided, false)
+        }
+    };
+
+    if is_new_raid {
+        tracing::warn!(
+            "Detected a raid in guild {}: {} joins within {} seconds.",
+            guild_id,
+            join_count,
+            settings.raid_window_seconds
+        );
+        if settings.alert_mod_log {
+            let case = ModerationCase::new(guild_id, data.config.bot_id, CaseAction::RaidDetected)
+                .with_details(format!(
+                    "{join_count} members joined within {} seconds. Greetings are suppressed until no raid is detected for {} minutes.",
+                    settings.raid_window_seconds, settings.raid_cooldown_minutes
+                ));
+            if let Err(e) = record_case(&ctx.http, data, case).await {
+                tracing::error!("Failed to record raid case: {}", e);
+            }
+        }
+    }
+
+    Ok(is_raided)
+}
+
+pub fn is_raided(guild_id: u64) -> bool {
+    lock(&RAIDS)
+        .get(&guild_id)
+        .is_some_and(|until| *until > Instant::now())
+}
+
+/// Returns the kind of spam and the messages belonging to it.
+fn detect_spam(
+    message: &Message,
+    guild_id: u64,
+    settings: &AntiSpamSettings,
+) -> Option<(SpamKind, Vec<(ChannelId, MessageId)>)> {
+    let this_message = vec![(message.channel_id, message.id)];
+
+    if settings.block_invite_links && INVITE_LINK_REGEX.is_match(&message.content) {
+        return Some((SpamKind::InviteLink, this_message));
+    }
+
+    let mention_count = message.mentions.len()
+        + message.mention_roles.len()
+        + usize::from(message.mention_everyone);
+    if settings.max_mentions > 0 && mention_count >= settings.max_mentions {
+        return Some((SpamKind::MassMentions(mention_count), this_message));
+    }
+
+    let content = message.content.trim().to_lowercase();
+    if settings.duplicate_messages == 0 || content.is_empty() {
+        return None;
+    }
+
+    let now = Instant::now();
+    let window = Duration::from_secs(settings.duplicate_window_seconds);
+    let mut recent_messages = lock(&RECENT_MESSAGES);
+    recent_messages.retain(|_, messages| {
+        messages
+            .back()
+            .is_some_and(|message| now.duration_since(message.sent_at) <= window)
+    });
+    let messages = recent_messages
+        .entry((guild_id, message.author.id.get()))
+        .or_default();
+    while messages
+        .front()
+        .is_some_and(|message| now.duration_since(message.sent_at) > window)
+    {
+        messages.pop_front();
+    }
+    messages.push_back(RecentMessage {
+        channel_id: message.channel_id,
+        message_id: message.id,
+        content: content.clone(),
+        sent_at: now,
+    });
+
+    let duplicate_count = messages
+        .iter()
+        .filter(|message| message.content == content)
+        .count();
+    if duplicate_count < settings.duplicate_messages {
+        return None;
+    }
+
+    let duplicates = messages
+        .iter()
+        .filter(|message| message.content == content)
+        .map(|message| (message.channel_id, message.message_id))
+        .collect();
+    messages.retain(|message| message.content != content);
+    Some((SpamKind::DuplicateMessages(duplicate_count), duplicates))
+}
+
+/// Admins are never treated as spammers.
+fn is_exempt(ctx: &Context, data: &ContextData, guild_id: GuildId, message: &Message) -> bool {
+    let role_ids: Vec<u64> = message
+        .member
+        .as_ref()
+        .map(|member| member.roles.iter().map(|role_id| role_id.get()).collect())
+        .unwrap_or_default();
+    if is_bot_admin_role(data, guild_id.get(), &role_ids) {
+        return true;
+    }
+
+    ctx.cache.guild(guild_id).is_some_and(|guild| {
+        guild
+            .members
+            .get(&message.author.id)
+            .is_some_and(|member| guild.member_permissions(member).administrator())
+    })
+}
+
+fn lock<T>(mutex: &'static Mutex<T>) -> std::sync::MutexGuard<'static, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
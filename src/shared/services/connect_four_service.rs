@@ -0,0 +1,189 @@
+pub const COLUMNS: usize = 7;
+pub const ROWS: usize = 6;
+
+const CONNECT_LENGTH: usize = 4;
+const SEARCH_DEPTH: u32 = 4;
+const WIN_SCORE: i32 = 1_000_000;
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// A Connect Four board. Pieces are stored as the index of the player (0 or 1) who dropped them.
+#[derive(Clone, Debug)]
+pub struct ConnectFourBoard {
+    cells: [[Option<usize>; COLUMNS]; ROWS],
+    move_count: usize,
+}
+
+impl Default for ConnectFourBoard {
+    fn default() -> Self {
+        ConnectFourBoard {
+            cells: [[None; COLUMNS]; ROWS],
+            move_count: 0,
+        }
+    }
+}
+
+impl ConnectFourBoard {
+    pub fn cell(&self, row: usize, column: usize) -> Option<usize> {
+        self.cells[row][column]
+    }
+
+    pub fn current_player(&self) -> usize {
+        self.move_count % 2
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.move_count == ROWS * COLUMNS
+    }
+
+    pub fn can_drop(&self, column: usize) -> bool {
+        column < COLUMNS && self.cells[0][column].is_none()
+    }
+
+    pub fn valid_columns(&self) -> Vec<usize> {
+        (0..COLUMNS)
+            .filter(|column| self.can_drop(*column))
+            .collect()
+    }
+
+    /// Drops the current player's piece into the column. Returns `false` if the column is full.
+    pub fn drop_piece(&mut self, column: usize) -> bool {
+        if !self.can_drop(column) {
+            return false;
+        }
+
+        let row = (0..ROWS)
+            .rev()
+            .find(|row| self.cells[*row][column].is_none())
+            .unwrap_or_default();
+        self.cells[row][column] = Some(self.current_player());
+        self.move_count += 1;
+        true
+    }
+
+    pub fn winner(&self) -> Option<usize> {
+        for row in 0..ROWS {
+            for column in 0..COLUMNS {
+                let Some(player) = self.cells[row][column] else {
+                    continue;
+                };
+
+                let connected = DIRECTIONS.iter().any(|(row_step, column_step)| {
+                    (1..CONNECT_LENGTH as isize).all(|step| {
+                        self.get(
+                            row as isize + row_step * step,
+                            column as isize + column_step * step,
+                        ) == Some(Some(player))
+                    })
+                });
+                if connected {
+                    return Some(player);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Finds the best column for the current player with a depth-limited minimax search.
+    pub fn best_move(&self) -> Option<usize> {
+        let mut best: Option<(usize, i32)> = None;
+        for column in self.ordered_columns() {
+            let mut board = self.clone();
+            board.drop_piece(column);
+            let score = -board.negamax(SEARCH_DEPTH, -WIN_SCORE * 2, WIN_SCORE * 2);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((column, score));
+            }
+        }
+
+        best.map(|(column, _)| column)
+    }
+
+    /// Scores the board from the perspective of the player who is about to move.
+    fn negamax(&self, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+        if self.winner().is_some() {
+            // The previous player just connected four.
+            return -WIN_SCORE - depth as i32;
+        }
+        if self.is_full() {
+            return 0;
+        }
+        if depth == 0 {
+            return self.evaluate(self.current_player());
+        }
+
+        let mut best_score = -WIN_SCORE * 2;
+        for column in self.ordered_columns() {
+            let mut board = self.clone();
+            board.drop_piece(column);
+            let score = -board.negamax(depth - 1, -beta, -alpha);
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    /// Columns ordered from the center outwards, which makes pruning more effective.
+    fn ordered_columns(&self) -> Vec<usize> {
+        let mut columns = self.valid_columns();
+        columns.sort_by_key(|column| (*column as isize - (COLUMNS / 2) as isize).abs());
+        columns
+    }
+
+    fn evaluate(&self, player: usize) -> i32 {
+        let mut score = 0;
+        for row in 0..ROWS {
+            if self.cells[row][COLUMNS / 2] == Some(player) {
+                score += 3;
+            }
+
+            for column in 0..COLUMNS {
+                'directions: for (row_step, column_step) in DIRECTIONS.iter() {
+                    let mut window = [None; CONNECT_LENGTH];
+                    for (step, slot) in window.iter_mut().enumerate() {
+                        let step = step as isize;
+                        match self.get(
+                            row as isize + row_step * step,
+                            column as isize + column_step * step,
+                        ) {
+                            Some(cell) => *slot = cell,
+                            None => continue 'directions,
+                        }
+                    }
+                    score += score_window(&window, player);
+                }
+            }
+        }
+
+        score
+    }
+
+    /// Returns `None` if the position is off the board, or `Some` with the piece at that position.
+    fn get(&self, row: isize, column: isize) -> Option<Option<usize>> {
+        if row < 0 || column < 0 || row >= ROWS as isize || column >= COLUMNS as isize {
+            None
+        } else {
+            Some(self.cells[row as usize][column as usize])
+        }
+    }
+}
+
+fn score_window(window: &[Option<usize>], player: usize) -> i32 {
+    let own = window.iter().filter(|cell| **cell == Some(player)).count();
+    let opponent = window
+        .iter()
+        .filter(|cell| cell.is_some_and(|piece| piece != player))
+        .count();
+    let empty = window.len() - own - opponent;
+
+    match (own, opponent, empty) {
+        (3, 0, 1) => 5,
+        (2, 0, 2) => 2,
+        (0, 3, 1) => -4,
+        _ => 0,
+    }
+}
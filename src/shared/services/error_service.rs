@@ -0,0 +1,163 @@
+use poise::{CreateReply, FrameworkError};
+use rand::prelude::*;
+
+use crate::shared::services::permission_service::PermissionDenied;
+use crate::shared::structs::{Context, ContextData, ContextError};
+use crate::shared::utility::format_duration;
+
+const ERROR_ID_LENGTH: usize = 8;
+const ERROR_ID_CHARACTERS: &[u8] = b"0123456789abcdef";
+
+/// A short random ID which is shown to the user and written to the logs, so that a reported error
+/// can be found again.
+pub fn new_error_id() -> String {
+    let mut rng = rand::rng();
+    (0..ERROR_ID_LENGTH)
+        .map(|_| *ERROR_ID_CHARACTERS.choose(&mut rng).unwrap_or(&b'0') as char)
+        .collect()
+}
+
+/// The message telling the user what went wrong, in the voice of the active persona.
+/// Returns `None` for errors which don't originate from a user's invocation.
+pub fn describe_framework_error(
+    framework_error: &FrameworkError<'_, ContextData, ContextError>,
+    is_kou: bool,
+) -> Option<String> {
+    let persona = |kou: &str, taiga: &str| {
+        if is_kou {
+            kou.to_string()
+        } else {
+            taiga.to_string()
+        }
+    };
+
+    let message = match framework_error {
+        FrameworkError::Setup { .. }
+        | FrameworkError::EventHandler { .. }
+        | FrameworkError::DynamicPrefix { .. }
+        | FrameworkError::UnknownCommand { .. }
+        | FrameworkError::UnknownInteraction { .. } => return None,
+        FrameworkError::Command { .. } => persona(
+            "Ah...something went wrong while I was doing that. I'm really sorry!",
+            "Tch, something broke while I was doing that. Don't look at me like that, it's not my fault.",
+        ),
+        FrameworkError::CommandPanic { .. } => persona(
+            "Uh oh...something went really, really wrong. Could you tell Tetsu about it?",
+            "Well, that blew up spectacularly. Go tell Tetsu before I do something I regret.",
+        ),
+        FrameworkError::SubcommandRequired { .. } => persona(
+            "You need to pick one of the subcommands for this one!",
+            "Pick a subcommand, genius. I can't read your mind.",
+        ),
+        FrameworkError::ArgumentParse { input, .. } => match input {
+            Some(input) => persona(
+                &format!(
+                    "Hmm...I couldn't understand `{input}`. Could you check it and try again?"
+                ),
+                &format!(
+                    "What's `{input}` supposed to mean? Try again with something that makes sense."
+                ),
+            ),
+            None => persona(
+                "Hmm...I think something is missing. Could you check the command and try again?",
+                "You forgot something, genius. Check the command and try again.",
+            ),
+        },
+        FrameworkError::CommandStructureMismatch { .. } => persona(
+            "This command seems to have changed just now... Could you try again in a moment?",
+            "This command changed under your feet. Give Discord a moment and try again.",
+        ),
+        FrameworkError::CooldownHit {
+            remaining_cooldown, ..
+        } => {
+            let remaining = format_duration(chrono::Duration::seconds(
+                remaining_cooldown.as_secs_f64().ceil() as i64,
+            ));
+            persona(
+                &format!("Let's take a little break! You can use this again in {remaining}."),
+                &format!("Slow down! You can use this again in {remaining}."),
+            )
+        }
+        FrameworkError::MissingBotPermissions {
+            missing_permissions,
+            ..
+        } => persona(
+            &format!("I'm sorry, but I need these permissions to do that: {missing_permissions}"),
+            &format!(
+                "I can't do that without these permissions: {missing_permissions}. Go bug an admin."
+            ),
+        ),
+        FrameworkError::MissingUserPermissions {
+            missing_permissions,
+            ..
+        } => match missing_permissions {
+            Some(missing_permissions) => persona(
+                &format!("Sorry, you need these permissions to use this: {missing_permissions}"),
+                &format!(
+                    "You need these permissions to use this: {missing_permissions}. Nice try, though."
+                ),
+            ),
+            None => persona(
+                "Sorry, I couldn't check your permissions. Could you try again?",
+                "I couldn't even check your permissions. Try again.",
+            ),
+        },
+        FrameworkError::NotAnOwner { .. } => persona(
+            "Sorry, only my owners can use this command!",
+            "Only my owners get to use this. You're not one of them.",
+        ),
+        FrameworkError::GuildOnly { .. } => persona(
+            "This command can only be used in a server!",
+            "Use this in a server, not here.",
+        ),
+        FrameworkError::DmOnly { .. } => persona(
+            "This command can only be used in my DMs!",
+            "Slide into my DMs if you want to use this. Not here.",
+        ),
+        FrameworkError::NsfwOnly { .. } => persona(
+            "This command can only be used in NSFW channels!",
+            "Not here. Find an NSFW channel for this.",
+        ),
+        FrameworkError::CommandCheckFailed { error, .. } => {
+            if error
+                .as_ref()
+                .is_some_and(|error| error.downcast_ref::<PermissionDenied>().is_some())
+            {
+                persona(
+                    "Sorry, you don't have permission to use this command!",
+                    "You're not allowed to use this command. Deal with it.",
+                )
+            } else if error.is_some() {
+                persona(
+                    "Ah...I couldn't check whether this command can be used here. I'm sorry!",
+                    "Something broke while checking this command. Try again later.",
+                )
+            } else {
+                persona(
+                    "Sorry, this command can't be used in this channel!",
+                    "This command isn't allowed in this channel. Go somewhere else.",
+                )
+            }
+        }
+        _ => persona(
+            "Ah...something went wrong. I'm really sorry!",
+            "Something went wrong. Don't ask me what.",
+        ),
+    };
+
+    Some(message)
+}
+
+/// Replies to the invocation with the error message and its ID, visible to the user only.
+pub async fn reply_with_error(ctx: Context<'_>, message: &str, error_id: &str) {
+    if let Err(e) = ctx
+        .send(
+            CreateReply::default()
+                .content(format!("{message}\n-# Error ID: `{error_id}`"))
+                .ephemeral(true),
+        )
+        .await
+    {
+        tracing::error!("[{}] Failed to reply to the error: {}", error_id, e);
+    }
+}
@@ -0,0 +1,98 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+use std::io::{BufWriter, Cursor};
+
+const GALLOWS_WIDTH: u32 = 240;
+const GALLOWS_HEIGHT: u32 = 240;
+const LINE_THICKNESS: i32 = 3;
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const GALLOWS_COLOR: Rgba<u8> = Rgba([110, 74, 40, 255]);
+const FIGURE_COLOR: Rgba<u8> = Rgba([33, 33, 33, 255]);
+
+#[derive(Copy, Clone, Debug)]
+enum Stroke {
+    Line((i32, i32), (i32, i32)),
+    Circle((i32, i32), i32),
+}
+
+const GALLOWS_STAGES: [Stroke; 10] = [
+    Stroke::Line((20, 220), (140, 220)),
+    Stroke::Line((50, 220), (50, 20)),
+    Stroke::Line((50, 20), (160, 20)),
+    Stroke::Line((160, 20), (160, 50)),
+    Stroke::Circle((160, 70), 20),
+    Stroke::Line((160, 90), (160, 150)),
+    Stroke::Line((160, 105), (130, 130)),
+    Stroke::Line((160, 105), (190, 130)),
+    Stroke::Line((160, 150), (135, 190)),
+    Stroke::Line((160, 150), (185, 190)),
+];
+const GALLOWS_STROKE_COUNT: usize = 4;
+
+/// Renders the gallows as a PNG image, drawing more of it the more wrong guesses have been made.
+pub fn render_gallows(wrong_guesses: i32, max_attempts: i32) -> anyhow::Result<Vec<u8>> {
+    let stage_count = if max_attempts <= 0 {
+        GALLOWS_STAGES.len()
+    } else {
+        let wrong_guesses = wrong_guesses.clamp(0, max_attempts) as usize;
+        (wrong_guesses * GALLOWS_STAGES.len()).div_ceil(max_attempts as usize)
+    };
+
+    let mut buffer = RgbaImage::from_pixel(GALLOWS_WIDTH, GALLOWS_HEIGHT, BACKGROUND_COLOR);
+    for (index, stroke) in GALLOWS_STAGES.iter().take(stage_count).enumerate() {
+        let color = if index < GALLOWS_STROKE_COUNT {
+            GALLOWS_COLOR
+        } else {
+            FIGURE_COLOR
+        };
+
+        match *stroke {
+            Stroke::Line(from, to) => draw_line(&mut buffer, from, to, color),
+            Stroke::Circle(center, radius) => draw_circle(&mut buffer, center, radius, color),
+        }
+    }
+
+    let buffer = DynamicImage::ImageRgba8(buffer);
+    let image = Vec::with_capacity(buffer.as_bytes().len());
+    let mut writer = BufWriter::new(Cursor::new(image));
+    buffer.write_to(&mut writer, image::ImageFormat::Png)?;
+    Ok(writer.into_inner().unwrap_or_default().into_inner())
+}
+
+fn draw_line(buffer: &mut RgbaImage, from: (i32, i32), to: (i32, i32), color: Rgba<u8>) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let steps = dx.abs().max(dy.abs()).max(1);
+    for step in 0..=steps {
+        let x = from.0 + dx * step / steps;
+        let y = from.1 + dy * step / steps;
+        stamp(buffer, x, y, color);
+    }
+}
+
+fn draw_circle(buffer: &mut RgbaImage, center: (i32, i32), radius: i32, color: Rgba<u8>) {
+    let steps = 360;
+    for step in 0..steps {
+        let angle = (step as f64).to_radians();
+        let x = center.0 + (radius as f64 * angle.cos()).round() as i32;
+        let y = center.1 + (radius as f64 * angle.sin()).round() as i32;
+        stamp(buffer, x, y, color);
+    }
+}
+
+fn stamp(buffer: &mut RgbaImage, x: i32, y: i32, color: Rgba<u8>) {
+    for offset_x in -LINE_THICKNESS..=LINE_THICKNESS {
+        for offset_y in -LINE_THICKNESS..=LINE_THICKNESS {
+            if offset_x * offset_x + offset_y * offset_y > LINE_THICKNESS * LINE_THICKNESS {
+                continue;
+            }
+
+            let (pixel_x, pixel_y) = (x + offset_x, y + offset_y);
+            if pixel_x >= 0
+                && pixel_y >= 0
+                && (pixel_x as u32) < buffer.width()
+                && (pixel_y as u32) < buffer.height()
+            {
+                buffer.put_pixel(pixel_x as u32, pixel_y as u32, color);
+            }
+        }
+    }
+}
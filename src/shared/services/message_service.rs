@@ -91,6 +91,14 @@ pub async fn get_messages(
     ctx: &Context,
     message: &Message,
     data: &ContextData,
+) -> anyhow::Result<Vec<MessageRecordSimple>> {
+    get_channel_messages(ctx, message.channel_id.get(), data).await
+}
+
+pub async fn get_channel_messages(
+    ctx: &Context,
+    channel_id: u64,
+    data: &ContextData,
 ) -> anyhow::Result<Vec<MessageRecordSimple>> {
     login(data).await?;
 
@@ -98,7 +106,7 @@ pub async fn get_messages(
 
     let payload = GetMessageRequest {
         bot_id: bot_user.id.get().to_string(),
-        channel_id: message.channel_id.get().to_string(),
+        channel_id: channel_id.to_string(),
     };
     let endpoint = format!("{}/message/record/list", &data.config.server_endpoint);
 
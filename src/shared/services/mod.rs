@@ -1,8 +1,20 @@
+pub mod anti_spam_service;
+pub mod connect_four_service;
 pub mod credit_service;
 pub mod dialog_service;
+pub mod error_service;
+pub mod hangman_service;
 pub mod image_service;
 pub mod judge_zero_service;
 pub mod message_service;
+pub mod moderation_service;
 pub mod open_router_service;
 pub mod openai_service;
+pub mod permission_service;
+pub mod rate_limit_service;
+pub mod reversi_service;
+pub mod scheduler_service;
 pub mod ship_service;
+pub mod tictactoe_service;
+pub mod translation_memory_service;
+pub mod translation_report_service;
@@ -0,0 +1,287 @@
+use chrono::{Duration, Utc};
+use serenity::all::{
+    ChannelId, CreateEmbed, CreateMessage, EditMember, EditMessage, GuildId, Http, MessageId,
+    UserId,
+};
+
+use crate::shared::constants::{KOU_COLOR, TAIGA_COLOR};
+use crate::shared::structs::moderation::moderation_case::{CaseAction, CaseTarget, ModerationCase};
+use crate::shared::structs::moderation::moderation_settings::{EscalationAction, EscalationRule};
+use crate::shared::structs::smite::smite_user;
+use crate::shared::structs::{Context, ContextData};
+use crate::shared::utility::parse_duration;
+
+/// Discord doesn't allow timeouts longer than 28 days.
+const MAX_TIMEOUT_DAYS: i64 = 28;
+
+/// Stores the case with the next case ID of its guild and posts it to the guild's mod-log channel, if any.
+/// Returns the assigned case ID.
+pub async fn record_case(
+    http: &Http,
+    data: &ContextData,
+    mut case: ModerationCase,
+) -> anyhow::Result<u64> {
+    let mod_log_channel_id = data
+        .moderation
+        .settings
+        .read()
+        .await
+        .get(case.guild_id)
+        .mod_log_channel_id;
+
+    case.id = {
+        let mut cases_write_lock = data.moderation.cases.write().await;
+        let id = cases_write_lock.add(case.clone());
+        cases_write_lock.write_moderation_cases()?;
+        id
+    };
+
+    if let Some(channel_id) = mod_log_channel_id {
+        let message = ChannelId::new(channel_id)
+            .send_message(
+                http,
+                CreateMessage::new().embed(build_case_embed(&case, data.kou)),
+            )
+            .await?;
+
+        let mut cases_write_lock = data.moderation.cases.write().await;
+        if let Some(stored_case) = cases_write_lock.get_mut(case.guild_id, case.id) {
+            stored_case.log_channel_id = Some(channel_id);
+            stored_case.log_message_id = Some(message.id.get());
+        }
+        cases_write_lock.write_moderation_cases()?;
+    }
+
+    Ok(case.id)
+}
+
+/// Records a case for a command invocation. Failing to record a case doesn't fail the action itself,
+/// so errors are only logged. Actions taken outside of guilds are not recorded.
+pub async fn log_case(ctx: Context<'_>, case: ModerationCase) -> Option<u64> {
+    if case.guild_id == 0 {
+        return None;
+    }
+
+    match record_case(ctx.http(), ctx.data(), case).await {
+        Ok(id) => Some(id),
+        Err(e) => {
+            tracing::error!("Failed to record moderation case: {}", e);
+            None
+        }
+    }
+}
+
+/// Starts a case for an action taken by the invoking user in the current guild.
+pub fn new_case(ctx: Context<'_>, action: CaseAction) -> ModerationCase {
+    ModerationCase::new(
+        ctx.guild_id().map(|id| id.get()).unwrap_or_default(),
+        ctx.author().id.get(),
+        action,
+    )
+}
+
+/// Updates the case's message in the mod-log channel after the case has been edited.
+pub async fn update_case_log_message(
+    http: &Http,
+    case: &ModerationCase,
+    is_kou: bool,
+) -> anyhow::Result<()> {
+    if let (Some(channel_id), Some(message_id)) = (case.log_channel_id, case.log_message_id) {
+        ChannelId::new(channel_id)
+            .edit_message(
+                http,
+                MessageId::new(message_id),
+                EditMessage::new().embed(build_case_embed(case, is_kou)),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+pub fn build_case_embed(case: &ModerationCase, is_kou: bool) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(format!("Case #{} | {}", case.id, case.action))
+        .color(if is_kou { KOU_COLOR } else { TAIGA_COLOR })
+        .field("Moderator", format!("<@{}>", case.actor_id), true)
+        .field(
+            "Target",
+            case.target
+                .as_ref()
+                .map(|target| target.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            true,
+        )
+        .field(
+            "Time",
+            format!("<t:{}:F>", case.created_at.timestamp()),
+            true,
+        )
+        .field(
+            "Reason",
+            case.reason.as_deref().unwrap_or("No reason given."),
+            false,
+        );
+
+    if let Some(details) = case.details.as_ref() {
+        embed = embed.field("Details", details, false);
+    }
+
+    embed
+}
+
+/// Applies the guild's escalation rule matching the user's recent warnings, if any, and records it as a case.
+/// When several rules match, the one with the most warnings wins. Returns the applied rule.
+pub async fn escalate_warnings(
+    http: &Http,
+    data: &ContextData,
+    guild_id: u64,
+    user_id: u64,
+    actor_id: u64,
+) -> anyhow::Result<Option<EscalationRule>> {
+    let escalation_rules = data
+        .moderation
+        .settings
+        .read()
+        .await
+        .get(guild_id)
+        .escalation_rules;
+    let rule = {
+        let warnings_read_lock = data.moderation.warnings.read().await;
+        escalation_rules
+            .into_iter()
+            .filter(|rule| {
+                warnings_read_lock.count_recent(guild_id, user_id, Duration::days(rule.within_days))
+                    == rule.warnings
+            })
+            .max_by_key(|rule| rule.warnings)
+    };
+    let Some(rule) = rule else {
+        return Ok(None);
+    };
+
+    let reason = Some(format!(
+        "Automatic escalation: {} warnings within {} days.",
+        rule.warnings, rule.within_days
+    ));
+    let case = ModerationCase::new(guild_id, actor_id, CaseAction::Smite)
+        .with_target(CaseTarget::User(user_id))
+        .with_reason(reason);
+    let case = punish_member(
+        http,
+        data,
+        guild_id,
+        user_id,
+        rule.action,
+        &rule.duration,
+        case,
+    )
+    .await?;
+    record_case(http, data, case).await?;
+
+    Ok(Some(rule))
+}
+
+/// Smites or times out the member for the duration and returns the case completed with the applied
+/// punishment. The case isn't recorded, so that callers can amend it first. If the punishment fails,
+/// the case is recorded with the failure in its details before the error is returned.
+pub async fn punish_member(
+    http: &Http,
+    data: &ContextData,
+    guild_id: u64,
+    user_id: u64,
+    action: EscalationAction,
+    duration_text: &str,
+    case: ModerationCase,
+) -> anyhow::Result<ModerationCase> {
+    match apply_punishment(
+        http,
+        data,
+        guild_id,
+        user_id,
+        action,
+        duration_text,
+        case.reason.clone(),
+    )
+    .await
+    {
+        Ok((case_action, details)) => Ok(ModerationCase {
+            action: case_action,
+            ..case
+        }
+        .with_details(details)),
+        Err(e) => {
+            let failure = match action {
+                EscalationAction::Smite => format!("Failed to smite: {e}"),
+                EscalationAction::Timeout => format!("Failed to time out: {e}"),
+            };
+            let details = match case.details.as_ref() {
+                Some(details) => format!("{details}\n{failure}"),
+                None => failure,
+            };
+            if let Err(record_error) = record_case(http, data, case.with_details(details)).await {
+                tracing::error!("Failed to record moderation case: {}", record_error);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Applies the punishment and returns the case action and details describing it.
+async fn apply_punishment(
+    http: &Http,
+    data: &ContextData,
+    guild_id: u64,
+    user_id: u64,
+    action: EscalationAction,
+    duration_text: &str,
+    reason: Option<String>,
+) -> anyhow::Result<(CaseAction, String)> {
+    let duration = parse_duration(duration_text)
+        .ok_or_else(|| anyhow::anyhow!("Invalid punishment duration: {}", duration_text))?;
+
+    match action {
+        EscalationAction::Smite => {
+            let role_id = data
+                .smite
+                .smite_settings
+                .read()
+                .await
+                .smote_role_id(guild_id)
+                .ok_or_else(|| anyhow::anyhow!("Guild {} has no smote role.", guild_id))?;
+            let (smote_user, _) = smite_user(
+                http,
+                &data.smite,
+                guild_id,
+                user_id,
+                role_id,
+                duration,
+                reason,
+            )
+            .await?;
+            Ok((
+                CaseAction::Smite,
+                format!(
+                    "Smitten for {duration_text}, due <t:{}:F>.",
+                    smote_user.due_time.timestamp()
+                ),
+            ))
+        }
+        EscalationAction::Timeout => {
+            let until = Utc::now() + duration.min(Duration::days(MAX_TIMEOUT_DAYS));
+            GuildId::new(guild_id)
+                .edit_member(
+                    http,
+                    UserId::new(user_id),
+                    EditMember::new().disable_communication_until(until.to_rfc3339()),
+                )
+                .await?;
+            Ok((
+                CaseAction::Timeout,
+                format!(
+                    "Timed out for {duration_text}, until <t:{}:F>.",
+                    until.timestamp()
+                ),
+            ))
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::commands::utility::translate::{LanguageModel, Novel};
+use crate::commands::utility::translate::{LanguageModel, Novel, TargetLanguage};
+use crate::shared::services::rate_limit_service::acquire_llm_token;
+use crate::shared::structs::utility::translation::{
+    GeneralTranslation, JudgeScore, ModelTranslation, TranslationJudgement, TranslationOutput,
+};
 use crate::shared::structs::{ContextData, OpenAICompatibleClients};
 use crate::shared::utility::build_author_name_map;
 use async_openai::Client;
@@ -10,9 +14,11 @@ use async_openai::types::{
     ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
     CreateChatCompletionRequestArgs, ReasoningEffort,
 };
+use poise::ChoiceParameter;
 use serenity::all::{Attachment, GetMessages, Message};
 use serenity::client::Context;
 use std::sync::Arc;
+use tokio::task::JoinSet;
 
 const DEEP_SEEK_MODEL: &str = "deepseek/deepseek-chat-v3-0324";
 const GPT_41_MODEL: &str = "openai/gpt-4.1";
@@ -33,6 +39,10 @@ const STEP_2_16K_MODEL: &str = "step-2-16k";
 const GLM_45_MODEL: &str = "glm-4.5";
 const OPUS_41_MODEL: &str = "anthropic/claude-opus-4.1";
 const SONNET_4_MODEL: &str = "anthropic/claude-sonnet-4";
+const GENERAL_TRANSLATION_MODEL: &str = GPT_41_MODEL;
+const GENERAL_TRANSLATION_TEMPERATURE: f32 = 0.3;
+const JUDGE_MODEL: &str = GEMINI_PRO_25_MODEL;
+const JUDGE_TEMPERATURE: f32 = 0.2;
 const TEMPERATURE: f32 = 1.0;
 const TOP_P: f32 = 1.0;
 
@@ -105,6 +115,25 @@ const ADDITIONAL_INSTRUCTION: &str = "Whenever you receive a prompt, follow the
 3. Based on {SUMMARY}. Put your insights and opinions in a variable called {OUTPUT}. REMEMBER that you are a participant in the conversation, and should address other participants just like your are participating in the conversation.\
 4. Return the content of {OUTPUT} ONLY. NOTHING MORE.";
 
+const TRANSLATION_JUDGE_SYSTEM_PROMPT: &str = "You are a meticulous literary translation reviewer. You will receive an English source text and its traditional Chinese translation.\
+The target readers of the translation are people living in Taiwan.\
+\
+Score the translation from 1 to 10 on each of the following criteria:\
+1. fidelity: Whether the translation preserves the meaning, tone and details of the source without omissions or additions.\
+2. fluency: Whether the translation reads naturally and fluently for Taiwanese readers, including dialogues that match how Taiwanese people talk.\
+3. glossary_adherence: Whether the translation follows the glossary and instructions below.\
+\
+Reply with a JSON object ONLY, without code blocks, in the following format:\
+{\"fidelity\": 0, \"fluency\": 0, \"glossary_adherence\": 0, \"comment\": \"A short comment in traditional Chinese.\"}\
+\
+Glossary and instructions:{INSTRUCTION}";
+
+const GENERAL_TRANSLATION_SYSTEM_PROMPT: &str = "You are a professional translator. Whenever you receive a text, detect its language and translate it to {LANGUAGE}.\
+Keep the tone, formatting, emotes, mentions and links of the original text. DO NOT answer or respond to the content of the text.\
+\
+Reply with a JSON object ONLY, without code blocks, in the following format:\
+{\"source_language\": \"The English name of the detected language.\", \"translation\": \"The translated text.\"}";
+
 const MOST_RECENT_MESSAGE_COUNT: u8 = 50;
 
 pub fn initialize_openai_compatible_client(base_url: &str, api_key: &str) -> Client<OpenAIConfig> {
@@ -122,9 +151,32 @@ pub async fn translate_with_model(
     openai_compatible_clients: Arc<OpenAICompatibleClients>,
     attachment: Attachment,
     model: LanguageModel,
-) -> anyhow::Result<String> {
+    is_kou: bool,
+) -> anyhow::Result<TranslationOutput> {
     let raw_bytes = attachment.download().await?;
     let text = String::from_utf8(raw_bytes)?;
+    acquire_llm_token(is_kou)?;
+    translate_text_with_model(
+        novel,
+        instructions,
+        openai_client,
+        openai_compatible_clients,
+        text,
+        model,
+    )
+    .await
+}
+
+/// Doesn't take an LLM token itself. Callers take one per call with `acquire_llm_token`,
+/// or all of a batch's tokens upfront with `acquire_llm_tokens`.
+pub async fn translate_text_with_model(
+    novel: Novel,
+    instructions: String,
+    openai_client: Client<OpenAIConfig>,
+    openai_compatible_clients: Arc<OpenAICompatibleClients>,
+    text: String,
+    model: LanguageModel,
+) -> anyhow::Result<TranslationOutput> {
     let replacement = format!("\n{instructions}");
     let system_prompt = match novel {
         Novel::ForgedInStarlight => {
@@ -245,16 +297,169 @@ pub async fn translate_with_model(
     };
 
     match result {
-        Ok(response) => response.choices[0]
-            .message
-            .content
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("Sorry, but I can't seem to translate that!")),
+        Ok(response) => {
+            let text = response.choices[0]
+                .message
+                .content
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Sorry, but I can't seem to translate that!"))?;
+            let (prompt_tokens, completion_tokens) = response
+                .usage
+                .map(|usage| (usage.prompt_tokens, usage.completion_tokens))
+                .unwrap_or_default();
+            Ok(TranslationOutput {
+                text,
+                prompt_tokens,
+                completion_tokens,
+            })
+        }
+        Err(e) => Err(anyhow::anyhow!("Failed to send Open Router request: {}", e)),
+    }
+}
+
+/// Judges every successful translation. The caller takes the LLM tokens for the judgements upfront.
+pub async fn judge_translations(
+    instructions: String,
+    openai_compatible_clients: Arc<OpenAICompatibleClients>,
+    source: &str,
+    translations: &[ModelTranslation],
+) -> Vec<TranslationJudgement> {
+    let mut join_set = JoinSet::new();
+    for translation in translations.iter() {
+        if let Ok(ref output) = translation.result {
+            let model = translation.model;
+            let instructions = instructions.clone();
+            let openai_compatible_clients = openai_compatible_clients.clone();
+            let source = source.to_string();
+            let translated = output.text.clone();
+            join_set.spawn(async move {
+                let result =
+                    judge_translation(instructions, openai_compatible_clients, source, translated)
+                        .await;
+                (model, result)
+            });
+        }
+    }
+
+    let mut judgements = join_set
+        .join_all()
+        .await
+        .into_iter()
+        .map(|(model, result)| TranslationJudgement {
+            model,
+            result: result.map_err(|e| {
+                tracing::error!("Failed to judge translation of {}: {:?}", model, e);
+                e.to_string()
+            }),
+        })
+        .collect::<Vec<_>>();
+
+    // Judged translations are ranked by their total, the ones which couldn't be judged come last.
+    judgements.sort_by_key(|judgement| std::cmp::Reverse(judgement.total()));
+    judgements
+}
+
+async fn judge_translation(
+    instructions: String,
+    openai_compatible_clients: Arc<OpenAICompatibleClients>,
+    source: String,
+    translated: String,
+) -> anyhow::Result<JudgeScore> {
+    let system_prompt =
+        TRANSLATION_JUDGE_SYSTEM_PROMPT.replace("{INSTRUCTION}", &format!("\n{instructions}"));
+
+    let messages = vec![
+        ChatCompletionRequestMessage::System(ChatCompletionRequestSystemMessage {
+            content: ChatCompletionRequestSystemMessageContent::Text(system_prompt),
+            name: None,
+        }),
+        ChatCompletionRequestMessage::User(ChatCompletionRequestUserMessage {
+            content: ChatCompletionRequestUserMessageContent::Text(format!(
+                "<source>\n{source}\n</source>\n<translation>\n{translated}\n</translation>"
+            )),
+            name: None,
+        }),
+    ];
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model(JUDGE_MODEL)
+        .temperature(JUDGE_TEMPERATURE)
+        .messages(messages)
+        .build()?;
+
+    match openai_compatible_clients
+        .open_router_client
+        .chat()
+        .create(request)
+        .await
+    {
+        Ok(response) => {
+            let content = response.choices[0]
+                .message
+                .content
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Failed to judge the translation."))?;
+            serde_json::from_str::<JudgeScore>(strip_code_block(&content))?.validate()
+        }
+        Err(e) => Err(anyhow::anyhow!("Failed to send Open Router request: {}", e)),
+    }
+}
+
+pub async fn translate_general_text(
+    data: &ContextData,
+    text: String,
+    target_language: TargetLanguage,
+) -> anyhow::Result<GeneralTranslation> {
+    acquire_llm_token(data.kou)?;
+
+    let system_prompt =
+        GENERAL_TRANSLATION_SYSTEM_PROMPT.replace("{LANGUAGE}", target_language.name());
+
+    let messages = vec![
+        ChatCompletionRequestMessage::System(ChatCompletionRequestSystemMessage {
+            content: ChatCompletionRequestSystemMessageContent::Text(system_prompt),
+            name: None,
+        }),
+        ChatCompletionRequestMessage::User(ChatCompletionRequestUserMessage {
+            content: ChatCompletionRequestUserMessageContent::Text(text),
+            name: None,
+        }),
+    ];
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model(GENERAL_TRANSLATION_MODEL)
+        .temperature(GENERAL_TRANSLATION_TEMPERATURE)
+        .messages(messages)
+        .build()?;
+
+    match data
+        .openai_compatible_clients
+        .open_router_client
+        .chat()
+        .create(request)
+        .await
+    {
+        Ok(response) => {
+            let content = response.choices[0]
+                .message
+                .content
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Sorry, but I can't seem to translate that!"))?;
+            let json = strip_code_block(&content);
+            Ok(
+                serde_json::from_str(json).unwrap_or_else(|_| GeneralTranslation {
+                    source_language: "Unknown".into(),
+                    translation: content.trim().to_string(),
+                }),
+            )
+        }
         Err(e) => Err(anyhow::anyhow!("Failed to send Open Router request: {}", e)),
     }
 }
 
 pub async fn opine_specific(data: &ContextData, prompt: String) -> anyhow::Result<String> {
+    acquire_llm_token(data.kou)?;
+
     let system_prompt = if data.kou {
         OPINE_SYSTEM_PROMPT_KOU
             .replace("{ADDITIONAL_INSTRUCTION}", "")
@@ -301,6 +506,8 @@ pub async fn opine_specific(data: &ContextData, prompt: String) -> anyhow::Resul
 }
 
 pub async fn categorize_question(data: &ContextData, message: String) -> anyhow::Result<String> {
+    acquire_llm_token(data.kou)?;
+
     let messages = vec![
         ChatCompletionRequestMessage::System(ChatCompletionRequestSystemMessage {
             content: CATEGORIZE_QUESTION_SYSTEM_PROMPT.into(),
@@ -386,6 +593,8 @@ pub async fn build_reply_to_message_chain(
     message_chain: Vec<String>,
     bot_nick: String,
 ) -> anyhow::Result<String> {
+    acquire_llm_token(data.kou)?;
+
     let system_prompt = if data.kou {
         REPLY_MESSAGE_CHAIN_SYSTEM_PROMPT_KOU.replace("{BOT_NAME}", bot_nick.as_str())
     } else {
@@ -429,6 +638,8 @@ async fn do_opine_conversation(
     data: &ContextData,
     messages: Vec<Message>,
 ) -> anyhow::Result<String> {
+    acquire_llm_token(data.kou)?;
+
     let author_name_map = build_author_name_map(&messages);
 
     let previous_messages = messages
@@ -497,3 +708,12 @@ async fn do_opine_conversation(
         Err(e) => Err(anyhow::anyhow!("Failed to send Open Router request: {}", e)),
     }
 }
+
+fn strip_code_block(content: &str) -> &str {
+    content
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim()
+}
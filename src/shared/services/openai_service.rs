@@ -18,6 +18,7 @@ use time::format_description::well_known::Rfc3339;
 
 use crate::shared::constants::IMAGE_TYPES;
 use crate::shared::services::message_service::get_messages;
+use crate::shared::services::rate_limit_service::acquire_llm_token;
 use crate::shared::structs::ContextData;
 use crate::shared::structs::authentication::login;
 use crate::shared::structs::config::configuration::Configuration;
@@ -53,6 +54,8 @@ pub async fn build_openai_message(
     message: &Message,
     data: &ContextData,
 ) -> anyhow::Result<String> {
+    acquire_llm_token(data.kou)?;
+
     let attachment = message.attachments.first().filter(|&attachment| {
         if let Some(ref content_type) = attachment.content_type {
             IMAGE_TYPES.contains(&content_type.as_str())
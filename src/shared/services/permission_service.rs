@@ -0,0 +1,169 @@
+use std::fmt::{Display, Formatter};
+
+use serenity::all::Member;
+
+use crate::shared::structs::config::channel_control::CommandOptions;
+use crate::shared::structs::config::command_permission::CATEGORY_SCOPE_PREFIX;
+use crate::shared::structs::{Context, ContextData, ContextError};
+
+/// Returned by the command check when the invoker isn't allowed to use the command.
+#[derive(Debug)]
+pub struct PermissionDenied;
+
+impl Display for PermissionDenied {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "You don't have permission to use this command!")
+    }
+}
+
+impl std::error::Error for PermissionDenied {}
+
+/// Whether the invoker may use the command. Bot admins, i.e. members with the Administrator permission
+/// or one of the guild's admin roles, may use everything. Otherwise the guild's command permissions decide,
+/// falling back to denying restricted commands and allowing the rest.
+pub async fn has_command_permission(ctx: Context<'_>) -> bool {
+    let Some(guild_id) = ctx.guild_id() else {
+        return true;
+    };
+    let Some(member) = ctx.author_member().await else {
+        return !is_restricted(ctx);
+    };
+
+    if is_member_bot_admin(ctx.data(), guild_id.get(), &member) {
+        return true;
+    }
+
+    let role_ids = member
+        .roles
+        .iter()
+        .map(|role_id| role_id.get())
+        .collect::<Vec<_>>();
+
+    ctx.data()
+        .command_permissions
+        .read()
+        .await
+        .resolve(
+            guild_id.get(),
+            ctx.author().id.get(),
+            &role_ids,
+            &command_scopes(ctx),
+        )
+        .unwrap_or_else(|| !is_restricted(ctx))
+}
+
+/// Whether the invoker is a bot admin of the guild, regardless of the command permissions.
+pub async fn is_bot_admin(ctx: Context<'_>) -> bool {
+    let Some(guild_id) = ctx.guild_id() else {
+        return false;
+    };
+    ctx.author_member()
+        .await
+        .is_some_and(|member| is_member_bot_admin(ctx.data(), guild_id.get(), &member))
+}
+
+fn is_member_bot_admin(data: &ContextData, guild_id: u64, member: &Member) -> bool {
+    let role_ids = member
+        .roles
+        .iter()
+        .map(|role_id| role_id.get())
+        .collect::<Vec<_>>();
+    let is_administrator = member
+        .permissions
+        .is_some_and(|permissions| permissions.administrator());
+    is_administrator || is_bot_admin_role(data, guild_id, &role_ids)
+}
+
+pub fn permission_denied() -> ContextError {
+    Box::new(PermissionDenied)
+}
+
+/// The scopes a permission rule can target for the invoked command, from the most specific to the least
+/// specific, e.g. `admin purge`, `admin` and `category:admin`.
+pub fn command_scopes(ctx: Context<'_>) -> Vec<String> {
+    let commands = ctx
+        .parent_commands()
+        .iter()
+        .copied()
+        .chain(std::iter::once(ctx.command()))
+        .collect::<Vec<_>>();
+    scopes_of(&commands)
+}
+
+/// The scopes of the invoked command given it and its parent commands, ordered from the root command.
+/// Categories are taken from every one of them, so a category grant also covers the subcommands.
+fn scopes_of(commands: &[&poise::Command<ContextData, ContextError>]) -> Vec<String> {
+    let mut scopes = commands
+        .iter()
+        .rev()
+        .map(|command| command.qualified_name.to_lowercase())
+        .collect::<Vec<_>>();
+    let categories = commands
+        .iter()
+        .rev()
+        .filter_map(|command| command.category.as_ref())
+        .map(|category| format!("{CATEGORY_SCOPE_PREFIX}{}", category.to_lowercase()))
+        .collect::<Vec<_>>();
+    for category in categories {
+        if !scopes.contains(&category) {
+            scopes.push(category);
+        }
+    }
+    scopes
+}
+
+/// Whether one of the roles is configured as an admin role of the guild.
+pub fn is_bot_admin_role(data: &ContextData, guild_id: u64, role_ids: &[u64]) -> bool {
+    data.server_infos
+        .server_infos
+        .iter()
+        .find(|server_info| server_info.server_id == guild_id)
+        .is_some_and(|server_info| {
+            server_info
+                .admin_role_ids
+                .iter()
+                .any(|admin_role_id| role_ids.contains(admin_role_id))
+        })
+}
+
+/// Commands marked with `custom_data = "RESTRICTED"`, or whose parent command is, are only usable by
+/// bot admins unless granted.
+fn is_restricted(ctx: Context<'_>) -> bool {
+    ctx.parent_commands()
+        .iter()
+        .chain(std::iter::once(&ctx.command()))
+        .any(|command| {
+            command
+                .custom_data
+                .downcast_ref::<CommandOptions>()
+                .is_some_and(|options| options.restricted)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::structs::config::command_permission::{
+        CommandPermissions, PermissionTarget,
+    };
+
+    #[test]
+    fn category_grant_covers_subcommands() {
+        let admin = poise::Command::<ContextData, ContextError> {
+            qualified_name: "admin".to_string(),
+            category: Some("Admin".to_string()),
+            ..Default::default()
+        };
+        let purge = poise::Command::<ContextData, ContextError> {
+            qualified_name: "admin purge".to_string(),
+            ..Default::default()
+        };
+        let mut command_permissions = CommandPermissions::default();
+        command_permissions.set(1, PermissionTarget::Role(2), "category:admin", true);
+
+        let scopes = scopes_of(&[&admin, &purge]);
+        assert_eq!(scopes, ["admin purge", "admin", "category:admin"]);
+        assert_eq!(command_permissions.resolve(1, 3, &[2], &scopes), Some(true));
+        assert_eq!(command_permissions.resolve(1, 3, &[4], &scopes), None);
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use poise::CooldownConfig;
+
+use crate::shared::structs::config::rate_limit::{LlmRateLimit, RateLimits};
+use crate::shared::structs::{ContextData, ContextError};
+
+static LLM_TOKEN_BUCKET: Lazy<Mutex<TokenBucket>> =
+    Lazy::new(|| Mutex::new(TokenBucket::new(LlmRateLimit::default())));
+
+/// Returned instead of calling the LLM when the token bucket is empty.
+#[derive(Debug)]
+pub struct LlmRateLimited {
+    pub is_kou: bool,
+}
+
+impl Display for LlmRateLimited {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_kou {
+            write!(
+                f,
+                "Sorry, I've been talking so much that I need to catch my breath... Could you ask me again in a minute?"
+            )
+        } else {
+            write!(
+                f,
+                "I'm not a machine that talks nonstop, you know. Give me a minute."
+            )
+        }
+    }
+}
+
+impl std::error::Error for LlmRateLimited {}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_second: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_limit: LlmRateLimit) -> Self {
+        TokenBucket {
+            capacity: rate_limit.capacity as f64,
+            tokens: rate_limit.capacity as f64,
+            refill_per_second: rate_limit.refill_per_minute as f64 / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes the tokens all at once, or none of them. Asking for more than the capacity takes a full bucket.
+    fn try_acquire(&mut self, count: u32) -> bool {
+        let now = Instant::now();
+        let elapsed_seconds = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed_seconds * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+
+        let count = (count as f64).min(self.capacity);
+        if self.tokens >= count {
+            self.tokens -= count;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Replaces the LLM token bucket with a full one of the given size.
+pub fn configure_llm_rate_limit(rate_limit: LlmRateLimit) {
+    *lock_llm_token_bucket() = TokenBucket::new(rate_limit);
+}
+
+/// Takes a token for an outbound LLM call. Fails with [`LlmRateLimited`] if the bucket is empty.
+pub fn acquire_llm_token(is_kou: bool) -> anyhow::Result<()> {
+    acquire_llm_tokens(is_kou, 1)
+}
+
+/// Takes the tokens for a batch of LLM calls upfront, so that the batch is rejected before it starts
+/// instead of failing partway. Fails with [`LlmRateLimited`] if there aren't enough tokens.
+pub fn acquire_llm_tokens(is_kou: bool, count: u32) -> anyhow::Result<()> {
+    if lock_llm_token_bucket().try_acquire(count) {
+        Ok(())
+    } else {
+        tracing::warn!("Outbound LLM calls throttled: {} requested.", count);
+        Err(LlmRateLimited { is_kou }.into())
+    }
+}
+
+/// The persona's message if the error is because the LLM calls are throttled.
+pub fn throttled_message(error: &anyhow::Error) -> Option<String> {
+    error
+        .downcast_ref::<LlmRateLimited>()
+        .map(|rate_limited| rate_limited.to_string())
+}
+
+/// Sets the configured cooldowns on the commands and their subcommands.
+/// Poise checks them before running a command and reports a `CooldownHit` otherwise.
+pub fn apply_command_cooldowns(
+    commands: &[poise::Command<ContextData, ContextError>],
+    rate_limits: &RateLimits,
+) {
+    for command in commands.iter() {
+        let cooldown = rate_limits
+            .cooldowns
+            .get(&command.qualified_name.to_lowercase())
+            .cloned()
+            .unwrap_or_default();
+        let mut cooldown_config = command
+            .cooldown_config
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *cooldown_config = CooldownConfig {
+            user: cooldown.user.map(Duration::from_secs),
+            channel: cooldown.channel.map(Duration::from_secs),
+            guild: cooldown.guild.map(Duration::from_secs),
+            ..Default::default()
+        };
+        drop(cooldown_config);
+
+        apply_command_cooldowns(&command.subcommands, rate_limits);
+    }
+}
+
+fn lock_llm_token_bucket() -> std::sync::MutexGuard<'static, TokenBucket> {
+    LLM_TOKEN_BUCKET
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
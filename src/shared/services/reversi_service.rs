@@ -0,0 +1,179 @@
+pub const BOARD_SIZE: usize = 8;
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Positional weights that favour corners and edges and avoid the squares next to corners.
+const SQUARE_WEIGHTS: [[i32; BOARD_SIZE]; BOARD_SIZE] = [
+    [100, -20, 10, 5, 5, 10, -20, 100],
+    [-20, -50, -2, -2, -2, -2, -50, -20],
+    [10, -2, 1, 1, 1, 1, -2, 10],
+    [5, -2, 1, 0, 0, 1, -2, 5],
+    [5, -2, 1, 0, 0, 1, -2, 5],
+    [10, -2, 1, 1, 1, 1, -2, 10],
+    [-20, -50, -2, -2, -2, -2, -50, -20],
+    [100, -20, 10, 5, 5, 10, -20, 100],
+];
+
+/// A Reversi board. Discs are stored as the index of the player (0 or 1) who owns them.
+/// Moves are addressed by `row * BOARD_SIZE + column`.
+#[derive(Clone, Debug)]
+pub struct ReversiBoard {
+    cells: [Option<usize>; BOARD_SIZE * BOARD_SIZE],
+    current_player: usize,
+}
+
+impl Default for ReversiBoard {
+    fn default() -> Self {
+        let mut cells = [None; BOARD_SIZE * BOARD_SIZE];
+        let center = BOARD_SIZE / 2;
+        cells[(center - 1) * BOARD_SIZE + center - 1] = Some(1);
+        cells[(center - 1) * BOARD_SIZE + center] = Some(0);
+        cells[center * BOARD_SIZE + center - 1] = Some(0);
+        cells[center * BOARD_SIZE + center] = Some(1);
+
+        ReversiBoard {
+            cells,
+            current_player: 0,
+        }
+    }
+}
+
+impl ReversiBoard {
+    pub fn cell(&self, index: usize) -> Option<usize> {
+        self.cells[index]
+    }
+
+    pub fn current_player(&self) -> usize {
+        self.current_player
+    }
+
+    pub fn disc_count(&self, player: usize) -> usize {
+        self.cells
+            .iter()
+            .filter(|cell| **cell == Some(player))
+            .count()
+    }
+
+    pub fn valid_moves(&self) -> Vec<usize> {
+        self.valid_moves_of(self.current_player)
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.valid_moves_of(0).is_empty() && self.valid_moves_of(1).is_empty()
+    }
+
+    /// Places a disc for the current player and flips the captured discs.
+    /// If the next player has no valid move, their turn is passed back.
+    /// Returns `false` if the move is not valid.
+    pub fn play(&mut self, index: usize) -> bool {
+        let flips = self.flips(index, self.current_player);
+        if flips.is_empty() {
+            return false;
+        }
+
+        self.cells[index] = Some(self.current_player);
+        for flip in flips {
+            self.cells[flip] = Some(self.current_player);
+        }
+
+        let opponent = 1 - self.current_player;
+        if !self.valid_moves_of(opponent).is_empty() {
+            self.current_player = opponent;
+        }
+        true
+    }
+
+    /// Picks the move that leaves the opponent with the worst best reply, judged by position and disc count.
+    pub fn best_move(&self) -> Option<usize> {
+        let player = self.current_player;
+        self.valid_moves().into_iter().max_by_key(|index| {
+            let mut board = self.clone();
+            board.play(*index);
+            if board.current_player == player || board.is_game_over() {
+                return board.evaluate(player);
+            }
+
+            board
+                .valid_moves()
+                .into_iter()
+                .map(|reply| {
+                    let mut reply_board = board.clone();
+                    reply_board.play(reply);
+                    reply_board.evaluate(player)
+                })
+                .min()
+                .unwrap_or_else(|| board.evaluate(player))
+        })
+    }
+
+    fn valid_moves_of(&self, player: usize) -> Vec<usize> {
+        (0..self.cells.len())
+            .filter(|index| !self.flips(*index, player).is_empty())
+            .collect()
+    }
+
+    /// Returns the discs that would be flipped if the player placed a disc at the index.
+    fn flips(&self, index: usize, player: usize) -> Vec<usize> {
+        if index >= self.cells.len() || self.cells[index].is_some() {
+            return vec![];
+        }
+
+        let row = (index / BOARD_SIZE) as isize;
+        let column = (index % BOARD_SIZE) as isize;
+        let mut flips = vec![];
+        for (row_step, column_step) in DIRECTIONS.iter() {
+            let mut captured = vec![];
+            let (mut current_row, mut current_column) = (row + row_step, column + column_step);
+            while let Some(position) = position_of(current_row, current_column) {
+                match self.cells[position] {
+                    Some(owner) if owner == player => {
+                        flips.append(&mut captured);
+                        break;
+                    }
+                    Some(_) => captured.push(position),
+                    None => break,
+                }
+                current_row += row_step;
+                current_column += column_step;
+            }
+        }
+
+        flips
+    }
+
+    fn evaluate(&self, player: usize) -> i32 {
+        if self.is_game_over() {
+            let own = self.disc_count(player) as i32;
+            let opponent = self.disc_count(1 - player) as i32;
+            return (own - opponent) * 1000;
+        }
+
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| {
+                cell.map(|owner| {
+                    let weight = SQUARE_WEIGHTS[index / BOARD_SIZE][index % BOARD_SIZE];
+                    if owner == player { weight } else { -weight }
+                })
+            })
+            .sum()
+    }
+}
+
+fn position_of(row: isize, column: isize) -> Option<usize> {
+    if row < 0 || column < 0 || row >= BOARD_SIZE as isize || column >= BOARD_SIZE as isize {
+        None
+    } else {
+        Some(row as usize * BOARD_SIZE + column as usize)
+    }
+}
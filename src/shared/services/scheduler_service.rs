@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serenity::prelude::Context;
+use tokio::sync::Notify;
+
+use crate::event_handler::presences::set_activity;
+use crate::shared::constants::RECORD_DIRECTORY;
+use crate::shared::structs::ContextData;
+use crate::shared::structs::smite::remove_smote_user;
+
+const SCHEDULED_JOBS_FILE_NAME: &str = "/scheduled_jobs.json";
+const MAX_ATTEMPTS: u32 = 5;
+const MAX_RETRY_DELAY_MINUTES: i64 = 60;
+
+static SCHEDULED_JOBS: Lazy<Mutex<HashMap<String, ScheduledJob>>> =
+    Lazy::new(|| Mutex::new(initialize_scheduled_jobs()));
+static SCHEDULER_CONTEXT: Lazy<Mutex<Option<Context>>> = Lazy::new(|| Mutex::new(None));
+static SCHEDULER_NOTIFY: Lazy<Notify> = Lazy::new(Notify::new);
+static SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// An action the scheduler performs when its job fires.
+/// Handlers must be idempotent, since a job may run again if the bot restarts before it is marked done.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduledAction {
+    Unsmite { guild_id: u64, user_id: u64 },
+    ExpireQotd { thread_channel_id: u64 },
+    RotatePresence,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScheduledJob {
+    pub action: ScheduledAction,
+    pub fire_at: DateTime<Utc>,
+    /// Recurring jobs are rescheduled by this many seconds after each run.
+    #[serde(default)]
+    pub interval_seconds: Option<i64>,
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl ScheduledAction {
+    /// Jobs are keyed by their action, so scheduling the same action again replaces the existing job.
+    fn key(&self) -> String {
+        match self {
+            ScheduledAction::Unsmite { guild_id, user_id } => {
+                format!("unsmite:{guild_id}:{user_id}")
+            }
+            ScheduledAction::ExpireQotd { thread_channel_id } => {
+                format!("expire_qotd:{thread_channel_id}")
+            }
+            ScheduledAction::RotatePresence => "rotate_presence".to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &Context, data: &ContextData) -> anyhow::Result<()> {
+        match self {
+            ScheduledAction::Unsmite { guild_id, user_id } => {
+                remove_smote_user(
+                    &ctx.http,
+                    data.smite.smote_user_list.clone(),
+                    *guild_id,
+                    *user_id,
+                    false,
+                )
+                .await?;
+            }
+            ScheduledAction::ExpireQotd { .. } => {
+                let mut qotd_infos_write_lock = data.qotd_infos.write().await;
+                qotd_infos_write_lock.purge_expired_qotds();
+                qotd_infos_write_lock.write_qotd_infos()?;
+            }
+            ScheduledAction::RotatePresence => {
+                set_activity(ctx, &data.common_settings.activities).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Schedules the action to run once at the given time, replacing any job with the same action.
+pub fn schedule_job(action: ScheduledAction, fire_at: DateTime<Utc>) {
+    insert_job(ScheduledJob {
+        action,
+        fire_at,
+        interval_seconds: None,
+        attempts: 0,
+    });
+}
+
+/// Schedules the action to run every interval. If the job already exists, e.g. from before a restart,
+/// its next fire time is kept.
+pub fn schedule_recurring_job(action: ScheduledAction, interval: Duration) {
+    let mut scheduled_jobs = lock_scheduled_jobs();
+    let job = scheduled_jobs
+        .entry(action.key())
+        .or_insert_with(|| ScheduledJob {
+            action,
+            fire_at: Utc::now() + interval,
+            interval_seconds: None,
+            attempts: 0,
+        });
+    job.interval_seconds = Some(interval.num_seconds());
+    persist_scheduled_jobs(&scheduled_jobs);
+    SCHEDULER_NOTIFY.notify_one();
+}
+
+/// Removes the job of the action, if any.
+pub fn cancel_job(action: &ScheduledAction) {
+    let mut scheduled_jobs = lock_scheduled_jobs();
+    if scheduled_jobs.remove(&action.key()).is_some() {
+        persist_scheduled_jobs(&scheduled_jobs);
+    }
+}
+
+/// Starts the scheduler loop. Calling this again, e.g. on a reconnect, only updates the context
+/// the jobs are run with, so jobs are never run by more than one loop.
+pub fn start_scheduler(ctx: &Context, data: &ContextData) {
+    *SCHEDULER_CONTEXT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(ctx.clone());
+    if SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let data = data.clone();
+    tokio::spawn(async move {
+        loop {
+            let next_fire_time = lock_scheduled_jobs().values().map(|job| job.fire_at).min();
+            let notified = SCHEDULER_NOTIFY.notified();
+            match next_fire_time {
+                Some(fire_at) => {
+                    let delay = (fire_at - Utc::now()).to_std().unwrap_or_default();
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = notified => continue,
+                    }
+                }
+                None => {
+                    notified.await;
+                    continue;
+                }
+            }
+
+            let ctx = SCHEDULER_CONTEXT
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone();
+            if let Some(ctx) = ctx {
+                run_due_jobs(&ctx, &data).await;
+            }
+        }
+    });
+}
+
+async fn run_due_jobs(ctx: &Context, data: &ContextData) {
+    let now = Utc::now();
+    let due_jobs = lock_scheduled_jobs()
+        .iter()
+        .filter(|(_, job)| job.fire_at <= now)
+        .map(|(key, job)| (key.clone(), job.clone()))
+        .collect::<Vec<_>>();
+
+    for (key, job) in due_jobs.into_iter() {
+        let result = job.action.run(ctx, data).await;
+
+        let mut scheduled_jobs = lock_scheduled_jobs();
+        // The job may have been replaced or cancelled while it was running.
+        let Some(current_job) = scheduled_jobs
+            .get_mut(&key)
+            .filter(|current_job| current_job.fire_at == job.fire_at)
+        else {
+            continue;
+        };
+
+        match result {
+            Ok(_) => match current_job.interval_seconds {
+                Some(interval_seconds) => {
+                    current_job.fire_at = Utc::now() + Duration::seconds(interval_seconds);
+                    current_job.attempts = 0;
+                }
+                None => {
+                    scheduled_jobs.remove(&key);
+                }
+            },
+            Err(e) => {
+                current_job.attempts += 1;
+                tracing::error!(
+                    "Scheduled job {} failed (attempt {}): {}",
+                    &key,
+                    current_job.attempts,
+                    e
+                );
+                if current_job.attempts >= MAX_ATTEMPTS && current_job.interval_seconds.is_none() {
+                    tracing::error!("Giving up on scheduled job {}.", &key);
+                    scheduled_jobs.remove(&key);
+                } else {
+                    let retry_delay_minutes =
+                        (1_i64 << current_job.attempts.min(6)).min(MAX_RETRY_DELAY_MINUTES);
+                    current_job.fire_at = Utc::now() + Duration::minutes(retry_delay_minutes);
+                }
+            }
+        }
+        persist_scheduled_jobs(&scheduled_jobs);
+    }
+}
+
+fn insert_job(job: ScheduledJob) {
+    let mut scheduled_jobs = lock_scheduled_jobs();
+    scheduled_jobs.insert(job.action.key(), job);
+    persist_scheduled_jobs(&scheduled_jobs);
+    SCHEDULER_NOTIFY.notify_one();
+}
+
+fn lock_scheduled_jobs() -> std::sync::MutexGuard<'static, HashMap<String, ScheduledJob>> {
+    SCHEDULED_JOBS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn initialize_scheduled_jobs() -> HashMap<String, ScheduledJob> {
+    let scheduled_jobs_path = String::from(RECORD_DIRECTORY) + SCHEDULED_JOBS_FILE_NAME;
+    if !std::path::Path::new(&scheduled_jobs_path).exists() {
+        return HashMap::new();
+    }
+
+    let result = std::fs::read(&scheduled_jobs_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| Ok(serde_json::from_slice::<Vec<ScheduledJob>>(&json)?));
+    match result {
+        Ok(jobs) => jobs
+            .into_iter()
+            .map(|job| (job.action.key(), job))
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to load scheduled jobs: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+fn persist_scheduled_jobs(scheduled_jobs: &HashMap<String, ScheduledJob>) {
+    let jobs = scheduled_jobs.values().collect::<Vec<_>>();
+    let result = serde_json::to_string_pretty(&jobs)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| {
+            if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+                std::fs::create_dir(RECORD_DIRECTORY)?;
+            }
+            let scheduled_jobs_path = String::from(RECORD_DIRECTORY) + SCHEDULED_JOBS_FILE_NAME;
+            std::fs::write(scheduled_jobs_path, json)?;
+            Ok(())
+        });
+
+    if let Err(e) = result {
+        tracing::error!("Failed to persist scheduled jobs: {}", e);
+    }
+}
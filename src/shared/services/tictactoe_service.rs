@@ -0,0 +1,100 @@
+pub const BOARD_SIZE: usize = 3;
+
+const WINNING_LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Mark {
+    Circle,
+    Cross,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BoardState {
+    Ongoing,
+    Win(Mark),
+    Draw,
+}
+
+pub type Board = [Option<Mark>; BOARD_SIZE * BOARD_SIZE];
+
+impl Mark {
+    pub fn opponent(self) -> Mark {
+        match self {
+            Mark::Circle => Mark::Cross,
+            Mark::Cross => Mark::Circle,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Mark::Circle => "○",
+            Mark::Cross => "×",
+        }
+    }
+}
+
+pub fn check_board(board: &Board) -> BoardState {
+    let winner = WINNING_LINES.iter().find_map(|[a, b, c]| match board[*a] {
+        Some(mark) if board[*b] == Some(mark) && board[*c] == Some(mark) => Some(mark),
+        _ => None,
+    });
+
+    match winner {
+        Some(mark) => BoardState::Win(mark),
+        None if board.iter().all(|slot| slot.is_some()) => BoardState::Draw,
+        None => BoardState::Ongoing,
+    }
+}
+
+/// Finds the best move for `mark` with minimax, preferring quicker wins and slower losses.
+pub fn best_move(board: &Board, mark: Mark) -> Option<usize> {
+    let mut board = *board;
+    let mut best: Option<(usize, i32)> = None;
+    for index in 0..board.len() {
+        if board[index].is_some() {
+            continue;
+        }
+
+        board[index] = Some(mark);
+        let score = -minimax(&mut board, mark.opponent(), 1);
+        board[index] = None;
+
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((index, score));
+        }
+    }
+
+    best.map(|(index, _)| index)
+}
+
+/// Scores the board from the perspective of the player who is about to move.
+fn minimax(board: &mut Board, to_move: Mark, depth: i32) -> i32 {
+    match check_board(board) {
+        BoardState::Win(mark) if mark == to_move => return 10 - depth,
+        BoardState::Win(_) => return depth - 10,
+        BoardState::Draw => return 0,
+        BoardState::Ongoing => {}
+    }
+
+    let mut best_score = i32::MIN;
+    for index in 0..board.len() {
+        if board[index].is_some() {
+            continue;
+        }
+
+        board[index] = Some(to_move);
+        best_score = best_score.max(-minimax(board, to_move.opponent(), depth + 1));
+        board[index] = None;
+    }
+
+    best_score
+}
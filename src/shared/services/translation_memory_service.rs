@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use async_openai::Client;
+use async_openai::config::OpenAIConfig;
+use tokio::sync::RwLock;
+
+use crate::commands::utility::translate::{LanguageModel, Novel};
+use crate::shared::services::open_router_service::translate_text_with_model;
+use crate::shared::services::rate_limit_service::acquire_llm_token;
+use crate::shared::structs::OpenAICompatibleClients;
+use crate::shared::structs::utility::translation::split_paragraphs;
+use crate::shared::structs::utility::translation_memory::TranslationMemory;
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTranslation {
+    pub text: String,
+    pub cache_hits: usize,
+    pub total: usize,
+}
+
+/// Reuses translations accepted in a review for unchanged paragraphs and only translates the rest.
+/// Machine translations are never remembered here; only `remember` on the review's accept path does that.
+pub async fn translate_with_memory(
+    novel: Novel,
+    instructions: String,
+    openai_client: Client<OpenAIConfig>,
+    openai_compatible_clients: Arc<OpenAICompatibleClients>,
+    translation_memory: Arc<RwLock<TranslationMemory>>,
+    text: String,
+    model: LanguageModel,
+    is_kou: bool,
+) -> anyhow::Result<MemoryTranslation> {
+    let paragraphs = split_paragraphs(&text);
+    let total = paragraphs.len();
+
+    let cached = {
+        let translation_memory_read_lock = translation_memory.read().await;
+        paragraphs
+            .iter()
+            .map(|paragraph| {
+                translation_memory_read_lock
+                    .get(novel, model, paragraph)
+                    .map(|entry| entry.translation.clone())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let missing = paragraphs
+        .iter()
+        .zip(cached.iter())
+        .filter(|(_, translation)| translation.is_none())
+        .map(|(paragraph, _)| paragraph.clone())
+        .collect::<Vec<_>>();
+    let cache_hits = total - missing.len();
+
+    if missing.is_empty() {
+        return Ok(MemoryTranslation {
+            text: cached
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            cache_hits,
+            total,
+        });
+    }
+
+    acquire_llm_token(is_kou)?;
+    let output = translate_text_with_model(
+        novel,
+        instructions.clone(),
+        openai_client.clone(),
+        openai_compatible_clients.clone(),
+        missing.join("\n\n"),
+        model,
+    )
+    .await?;
+    let translated = split_paragraphs(&output.text);
+
+    if translated.len() != missing.len() {
+        tracing::warn!(
+            "Paragraph count mismatch when translating with {}: expected {}, got {}. Translating the whole text instead.",
+            model,
+            missing.len(),
+            translated.len()
+        );
+
+        let output = if cache_hits == 0 {
+            output
+        } else {
+            acquire_llm_token(is_kou)?;
+            translate_text_with_model(
+                novel,
+                instructions,
+                openai_client,
+                openai_compatible_clients,
+                text,
+                model,
+            )
+            .await?
+        };
+
+        return Ok(MemoryTranslation {
+            text: output.text,
+            cache_hits: 0,
+            total,
+        });
+    }
+
+    let mut translated_iter = translated.into_iter();
+    let assembled = cached
+        .into_iter()
+        .map(|translation| {
+            translation
+                .or_else(|| translated_iter.next())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Ok(MemoryTranslation {
+        text: assembled,
+        cache_hits,
+        total,
+    })
+}
+
+/// Stores reviewed translations so that later translations of the same paragraphs reuse them.
+pub async fn remember(
+    translation_memory: &Arc<RwLock<TranslationMemory>>,
+    novel: Novel,
+    model: LanguageModel,
+    pairs: Vec<(String, String)>,
+) -> anyhow::Result<()> {
+    let mut translation_memory_write_lock = translation_memory.write().await;
+    for (source, translation) in pairs.into_iter() {
+        translation_memory_write_lock.insert(novel, model, source, translation);
+    }
+    translation_memory_write_lock.write_translation_memory()
+}
@@ -0,0 +1,272 @@
+use std::fmt::Write;
+
+use crate::commands::utility::batch_translate::ReportFormat;
+use crate::commands::utility::translate::LanguageModel;
+use crate::shared::structs::utility::translation::{
+    JudgeScore, ModelTranslation, TranslationJudgement, split_paragraphs,
+};
+
+pub fn build_comparison_report(
+    source: &str,
+    translations: &[ModelTranslation],
+    judgements: Option<&[TranslationJudgement]>,
+    format: ReportFormat,
+) -> String {
+    let source_paragraphs = split_paragraphs(source);
+    let succeeded = translations
+        .iter()
+        .filter_map(|translation| {
+            translation
+                .result
+                .as_ref()
+                .ok()
+                .map(|output| (translation.model, split_paragraphs(&output.text)))
+        })
+        .collect::<Vec<_>>();
+
+    let row_count = succeeded
+        .iter()
+        .map(|(_, paragraphs)| paragraphs.len())
+        .chain(std::iter::once(source_paragraphs.len()))
+        .max()
+        .unwrap_or_default();
+
+    let headers = std::iter::once("Source".to_string())
+        .chain(succeeded.iter().map(|(model, _)| model.to_string()))
+        .collect::<Vec<_>>();
+
+    let rows = (0..row_count)
+        .map(|index| {
+            std::iter::once(source_paragraphs.get(index).cloned().unwrap_or_default())
+                .chain(
+                    succeeded
+                        .iter()
+                        .map(|(_, paragraphs)| paragraphs.get(index).cloned().unwrap_or_default()),
+                )
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    match format {
+        ReportFormat::Markdown => build_markdown_report(translations, judgements, &headers, &rows),
+        ReportFormat::Html => build_html_report(translations, judgements, &headers, &rows),
+    }
+}
+
+fn build_markdown_report(
+    translations: &[ModelTranslation],
+    judgements: Option<&[TranslationJudgement]>,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> String {
+    let mut output = String::new();
+    let _ = writeln!(output, "# Translation Comparison\n");
+    let _ = writeln!(output, "## Summary\n");
+    let _ = writeln!(
+        output,
+        "| Model | Status | Latency | Prompt Tokens | Completion Tokens |"
+    );
+    let _ = writeln!(output, "|---|---|---|---|---|");
+    for translation in translations.iter() {
+        let (status, prompt_tokens, completion_tokens) = summarize(translation);
+        let _ = writeln!(
+            output,
+            "| {} | {} | {:.2}s | {} | {} |",
+            translation.model,
+            escape_markdown(&status),
+            translation.latency.as_secs_f64(),
+            prompt_tokens,
+            completion_tokens
+        );
+    }
+
+    if let Some(judgements) = judgements {
+        let _ = writeln!(output, "\n## Judge Ranking\n");
+        let _ = writeln!(
+            output,
+            "| Rank | Model | Fidelity | Fluency | Glossary | Total | Comment |"
+        );
+        let _ = writeln!(output, "|---|---|---|---|---|---|---|");
+        for (rank, (model, score)) in judged(judgements).enumerate() {
+            let _ = writeln!(
+                output,
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                rank + 1,
+                model,
+                score.fidelity,
+                score.fluency,
+                score.glossary_adherence,
+                score.total(),
+                escape_markdown(&score.comment)
+            );
+        }
+        for (model, error) in not_judged(judgements) {
+            let _ = writeln!(
+                output,
+                "| - | {} | - | - | - | - | Not judged: {} |",
+                model,
+                escape_markdown(error)
+            );
+        }
+    }
+
+    let _ = writeln!(output, "\n## Paragraphs\n");
+    let _ = writeln!(
+        output,
+        "| # | {} |",
+        headers
+            .iter()
+            .map(|header| escape_markdown(header))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    );
+    let _ = writeln!(output, "|---|{}", "---|".repeat(headers.len()));
+    for (index, row) in rows.iter().enumerate() {
+        let _ = writeln!(
+            output,
+            "| {} | {} |",
+            index + 1,
+            row.iter()
+                .map(|cell| escape_markdown(cell))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        );
+    }
+
+    output
+}
+
+fn build_html_report(
+    translations: &[ModelTranslation],
+    judgements: Option<&[TranslationJudgement]>,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> String {
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Translation Comparison</title>\n\
+        <style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #999; padding: 4px 8px; vertical-align: top; }} .failed {{ color: #c00; }}</style>\n\
+        </head>\n<body>"
+    );
+    let _ = writeln!(output, "<h1>Translation Comparison</h1>\n<h2>Summary</h2>");
+    let _ = writeln!(
+        output,
+        "<table>\n<tr><th>Model</th><th>Status</th><th>Latency</th><th>Prompt Tokens</th><th>Completion Tokens</th></tr>"
+    );
+    for translation in translations.iter() {
+        let (status, prompt_tokens, completion_tokens) = summarize(translation);
+        let _ = writeln!(
+            output,
+            "<tr{}><td>{}</td><td>{}</td><td>{:.2}s</td><td>{}</td><td>{}</td></tr>",
+            if translation.result.is_err() {
+                " class=\"failed\""
+            } else {
+                ""
+            },
+            escape_html(&translation.model.to_string()),
+            escape_html(&status),
+            translation.latency.as_secs_f64(),
+            prompt_tokens,
+            completion_tokens
+        );
+    }
+    let _ = writeln!(output, "</table>");
+
+    if let Some(judgements) = judgements {
+        let _ = writeln!(
+            output,
+            "<h2>Judge Ranking</h2>\n<table>\n<tr><th>Rank</th><th>Model</th><th>Fidelity</th><th>Fluency</th><th>Glossary</th><th>Total</th><th>Comment</th></tr>"
+        );
+        for (rank, (model, score)) in judged(judgements).enumerate() {
+            let _ = writeln!(
+                output,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                rank + 1,
+                escape_html(&model.to_string()),
+                score.fidelity,
+                score.fluency,
+                score.glossary_adherence,
+                score.total(),
+                escape_html(&score.comment)
+            );
+        }
+        for (model, error) in not_judged(judgements) {
+            let _ = writeln!(
+                output,
+                "<tr class=\"failed\"><td>-</td><td>{}</td><td>-</td><td>-</td><td>-</td><td>-</td><td>Not judged: {}</td></tr>",
+                escape_html(&model.to_string()),
+                escape_html(error)
+            );
+        }
+        let _ = writeln!(output, "</table>");
+    }
+
+    let _ = writeln!(output, "<h2>Paragraphs</h2>\n<table>");
+    let _ = writeln!(
+        output,
+        "<tr><th>#</th>{}</tr>",
+        headers
+            .iter()
+            .map(|header| format!("<th>{}</th>", escape_html(header)))
+            .collect::<String>()
+    );
+    for (index, row) in rows.iter().enumerate() {
+        let _ = writeln!(
+            output,
+            "<tr><td>{}</td>{}</tr>",
+            index + 1,
+            row.iter()
+                .map(|cell| format!("<td>{}</td>", escape_html(cell)))
+                .collect::<String>()
+        );
+    }
+    let _ = writeln!(output, "</table>\n</body>\n</html>");
+
+    output
+}
+
+fn judged(
+    judgements: &[TranslationJudgement],
+) -> impl Iterator<Item = (LanguageModel, &JudgeScore)> {
+    judgements.iter().filter_map(|judgement| {
+        judgement
+            .result
+            .as_ref()
+            .ok()
+            .map(|score| (judgement.model, score))
+    })
+}
+
+fn not_judged(judgements: &[TranslationJudgement]) -> impl Iterator<Item = (LanguageModel, &str)> {
+    judgements.iter().filter_map(|judgement| {
+        judgement
+            .result
+            .as_ref()
+            .err()
+            .map(|error| (judgement.model, error.as_str()))
+    })
+}
+
+fn summarize(translation: &ModelTranslation) -> (String, u32, u32) {
+    match translation.result {
+        Ok(ref output) => (
+            "OK".to_string(),
+            output.prompt_tokens,
+            output.completion_tokens,
+        ),
+        Err(ref e) => (format!("Failed: {e}"), 0, 0),
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "<br>")
+}
@@ -1,13 +1,60 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::shared::constants::CONFIG_DIRECTORY;
 
 const CHANNEL_CONTROL_FILE_NAME: &str = "/channel_control.toml";
+/// Pseudo category restricting where the bot replies to messages on its own, including AI replies.
+pub const RESPONSES_CATEGORY: &str = "responses";
+
+/// Options attached to commands through poise's `custom_data` attribute.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CommandOptions {
+    /// The command can be used in every channel regardless of the channel control.
+    pub always_allowed: bool,
+    /// Only bot admins and the roles or members granted the command can use it.
+    pub restricted: bool,
+}
+
+pub const ALWAYS_ALLOWED: CommandOptions = CommandOptions {
+    always_allowed: true,
+    restricted: false,
+};
+
+pub const RESTRICTED: CommandOptions = CommandOptions {
+    always_allowed: false,
+    restricted: true,
+};
+
+pub const RESTRICTED_ALWAYS_ALLOWED: CommandOptions = CommandOptions {
+    always_allowed: true,
+    restricted: true,
+};
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GuildChannelControl {
+    /// Channels where commands of every category without a channel list can be used.
+    #[serde(default)]
+    pub enabled_channels: Vec<u64>,
+    /// Channels where random responses are not sent.
+    #[serde(default)]
+    pub ignored_channels: Vec<u64>,
+    /// Channels a category of commands is restricted to, keyed by the lowercase category name.
+    #[serde(default)]
+    pub category_channels: HashMap<String, Vec<u64>>,
+}
 
+/// The top-level channel lists predate per-guild channel control. They're still honored,
+/// but new channels are only added to the guild's lists.
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct ChannelControl {
+    #[serde(default)]
     pub enabled_channels: Vec<u64>,
+    #[serde(default)]
     pub ignored_channels: Vec<u64>,
+    #[serde(default)]
+    pub guilds: HashMap<String, GuildChannelControl>,
 }
 
 impl ChannelControl {
@@ -15,7 +62,121 @@ impl ChannelControl {
         ChannelControl {
             enabled_channels: vec![],
             ignored_channels: vec![],
+            guilds: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, guild_id: u64) -> GuildChannelControl {
+        self.guilds
+            .get(&guild_id.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn get_mut(&mut self, guild_id: u64) -> &mut GuildChannelControl {
+        self.guilds.entry(guild_id.to_string()).or_default()
+    }
+
+    pub fn is_enabled(&self, guild_id: u64, channel_id: u64) -> bool {
+        self.enabled_channels.contains(&channel_id)
+            || self.get(guild_id).enabled_channels.contains(&channel_id)
+    }
+
+    pub fn is_ignored(&self, guild_id: u64, channel_id: u64) -> bool {
+        self.ignored_channels.contains(&channel_id)
+            || self.get(guild_id).ignored_channels.contains(&channel_id)
+    }
+
+    /// Whether the category may be used in the channel. Categories without a channel list
+    /// are allowed everywhere.
+    pub fn is_category_allowed(&self, guild_id: u64, channel_id: u64, category: &str) -> bool {
+        self.get(guild_id)
+            .category_channels
+            .get(category)
+            .filter(|channel_ids| !channel_ids.is_empty())
+            .is_none_or(|channel_ids| channel_ids.contains(&channel_id))
+    }
+
+    /// Whether a command of the category may be used in the channel. A category with a channel list
+    /// is only usable in those channels, everything else only in enabled channels.
+    pub fn is_command_allowed(
+        &self,
+        guild_id: u64,
+        channel_id: u64,
+        category: Option<&str>,
+    ) -> bool {
+        let guild_channel_control = self.get(guild_id);
+        let category_channels = category
+            .and_then(|category| guild_channel_control.category_channels.get(category))
+            .filter(|channel_ids| !channel_ids.is_empty());
+        match category_channels {
+            Some(channel_ids) => channel_ids.contains(&channel_id),
+            None => self.is_enabled(guild_id, channel_id),
+        }
+    }
+
+    /// Enables the channel for all commands, or only for the category if given.
+    /// Returns whether the channel wasn't enabled yet.
+    pub fn enable(&mut self, guild_id: u64, channel_id: u64, category: Option<&str>) -> bool {
+        if category.is_none() && self.enabled_channels.contains(&channel_id) {
+            return false;
+        }
+
+        let guild_channel_control = self.get_mut(guild_id);
+        let channel_ids = match category {
+            Some(category) => guild_channel_control
+                .category_channels
+                .entry(category.to_string())
+                .or_default(),
+            None => &mut guild_channel_control.enabled_channels,
+        };
+        if channel_ids.contains(&channel_id) {
+            false
+        } else {
+            channel_ids.push(channel_id);
+            true
+        }
+    }
+
+    /// Disables the channel for all commands, or only for the category if given.
+    /// Returns whether the channel was enabled.
+    pub fn disable(&mut self, guild_id: u64, channel_id: u64, category: Option<&str>) -> bool {
+        let mut removed = false;
+        if category.is_none() {
+            removed |= remove_channel(&mut self.enabled_channels, channel_id);
+        }
+
+        let guild_channel_control = self.get_mut(guild_id);
+        match category {
+            Some(category) => {
+                if let Some(channel_ids) = guild_channel_control.category_channels.get_mut(category)
+                {
+                    removed |= remove_channel(channel_ids, channel_id);
+                    if channel_ids.is_empty() {
+                        guild_channel_control.category_channels.remove(category);
+                    }
+                }
+            }
+            None => {
+                removed |= remove_channel(&mut guild_channel_control.enabled_channels, channel_id);
+            }
+        }
+        removed
+    }
+
+    /// Returns whether the channel wasn't ignored yet.
+    pub fn ignore(&mut self, guild_id: u64, channel_id: u64) -> bool {
+        if self.is_ignored(guild_id, channel_id) {
+            return false;
         }
+        self.get_mut(guild_id).ignored_channels.push(channel_id);
+        true
+    }
+
+    /// Returns whether the channel was ignored.
+    pub fn unignore(&mut self, guild_id: u64, channel_id: u64) -> bool {
+        let removed = remove_channel(&mut self.ignored_channels, channel_id);
+        remove_channel(&mut self.get_mut(guild_id).ignored_channels, channel_id) || removed
     }
 
     pub fn write_channel_control(&self) -> anyhow::Result<()> {
@@ -26,6 +187,12 @@ impl ChannelControl {
     }
 }
 
+fn remove_channel(channel_ids: &mut Vec<u64>, channel_id: u64) -> bool {
+    let count = channel_ids.len();
+    channel_ids.retain(|id| *id != channel_id);
+    channel_ids.len() != count
+}
+
 pub fn initialize() -> anyhow::Result<ChannelControl> {
     if !std::path::Path::new(CONFIG_DIRECTORY).exists() {
         std::fs::create_dir(CONFIG_DIRECTORY)?;
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::constants::CONFIG_DIRECTORY;
+
+const COMMAND_PERMISSIONS_FILE_NAME: &str = "/command_permissions.toml";
+pub const CATEGORY_SCOPE_PREFIX: &str = "category:";
+
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum PermissionTarget {
+    Role(u64),
+    User(u64),
+}
+
+/// Grants or denies a command, or a whole category of commands as `category:<name>`, to a role or user.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CommandPermission {
+    pub target: PermissionTarget,
+    pub scope: String,
+    pub allowed: bool,
+}
+
+/// Command permissions of every guild, keyed by guild ID.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct CommandPermissions {
+    #[serde(default)]
+    pub guilds: HashMap<String, Vec<CommandPermission>>,
+}
+
+impl Display for PermissionTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PermissionTarget::Role(id) => write!(f, "<@&{id}>"),
+            PermissionTarget::User(id) => write!(f, "<@{id}>"),
+        }
+    }
+}
+
+impl CommandPermissions {
+    pub fn list(&self, guild_id: u64) -> &[CommandPermission] {
+        self.guilds
+            .get(&guild_id.to_string())
+            .map(|permissions| permissions.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Grants or denies the scope to the target, replacing the target's previous rule for the scope.
+    pub fn set(&mut self, guild_id: u64, target: PermissionTarget, scope: &str, allowed: bool) {
+        let permissions = self.guilds.entry(guild_id.to_string()).or_default();
+        permissions.retain(|permission| permission.target != target || permission.scope != scope);
+        permissions.push(CommandPermission {
+            target,
+            scope: scope.to_string(),
+            allowed,
+        });
+    }
+
+    /// Removes the target's rule for the scope. Returns whether there was one.
+    pub fn reset(&mut self, guild_id: u64, target: PermissionTarget, scope: &str) -> bool {
+        let Some(permissions) = self.guilds.get_mut(&guild_id.to_string()) else {
+            return false;
+        };
+        let count = permissions.len();
+        permissions.retain(|permission| permission.target != target || permission.scope != scope);
+        permissions.len() != count
+    }
+
+    /// Resolves whether a member may use a command, given the command's scopes from the most specific to the
+    /// least specific. The first scope with a matching rule decides. Within a scope, a rule for the user
+    /// takes precedence over rules for roles, and a denying role over an allowing one.
+    /// Returns `None` if no rule applies.
+    pub fn resolve(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        role_ids: &[u64],
+        scopes: &[String],
+    ) -> Option<bool> {
+        let permissions = self.list(guild_id);
+        scopes.iter().find_map(|scope| {
+            let scoped_permissions = permissions
+                .iter()
+                .filter(|permission| &permission.scope == scope)
+                .collect::<Vec<_>>();
+
+            let user_permission = scoped_permissions
+                .iter()
+                .find(|permission| permission.target == PermissionTarget::User(user_id));
+            if let Some(permission) = user_permission {
+                return Some(permission.allowed);
+            }
+
+            let role_permissions = scoped_permissions
+                .iter()
+                .filter(|permission| {
+                    matches!(permission.target, PermissionTarget::Role(role_id) if role_ids.contains(&role_id))
+                })
+                .collect::<Vec<_>>();
+            if role_permissions.is_empty() {
+                None
+            } else {
+                Some(role_permissions.iter().all(|permission| permission.allowed))
+            }
+        })
+    }
+
+    pub fn write_command_permissions(&self) -> anyhow::Result<()> {
+        let command_permissions_path =
+            String::from(CONFIG_DIRECTORY) + COMMAND_PERMISSIONS_FILE_NAME;
+        let serialized_toml = toml::to_string_pretty(self)?;
+        std::fs::write(command_permissions_path, serialized_toml)?;
+        Ok(())
+    }
+}
+
+pub fn initialize_command_permissions() -> anyhow::Result<CommandPermissions> {
+    if !std::path::Path::new(CONFIG_DIRECTORY).exists() {
+        std::fs::create_dir(CONFIG_DIRECTORY)?;
+    }
+
+    let command_permissions_path = String::from(CONFIG_DIRECTORY) + COMMAND_PERMISSIONS_FILE_NAME;
+    if !std::path::Path::new(&command_permissions_path).exists() {
+        let new_command_permissions = CommandPermissions::default();
+        new_command_permissions.write_command_permissions()?;
+        Ok(new_command_permissions)
+    } else {
+        let toml = std::fs::read_to_string(&command_permissions_path)?;
+        Ok(toml::from_str(&toml)?)
+    }
+}
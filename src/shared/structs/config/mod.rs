@@ -1,5 +1,7 @@
 pub mod channel_control;
+pub mod command_permission;
 pub mod common_settings;
 pub mod configuration;
 pub mod random_response;
+pub mod rate_limit;
 pub mod server_info;
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::constants::CONFIG_DIRECTORY;
+
+const RATE_LIMITS_FILE_NAME: &str = "/rate_limits.toml";
+/// Enough for a batch translation with every model and a judgement of each translation.
+const DEFAULT_LLM_BUCKET_CAPACITY: u32 = 40;
+const DEFAULT_LLM_REFILL_PER_MINUTE: u32 = 10;
+
+/// Cooldowns of a command in seconds. A command can be on cooldown for the user, the channel
+/// and the guild at the same time.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CommandCooldown {
+    #[serde(default)]
+    pub user: Option<u64>,
+    #[serde(default)]
+    pub channel: Option<u64>,
+    #[serde(default)]
+    pub guild: Option<u64>,
+}
+
+/// Limits outbound LLM calls with a token bucket shared by everything the bot says on its own.
+#[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+pub struct LlmRateLimit {
+    /// How many calls can be made in a burst.
+    pub capacity: u32,
+    /// How many calls are refilled every minute.
+    pub refill_per_minute: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RateLimits {
+    /// Cooldowns keyed by the qualified command name, e.g. `image` or `game hangman`.
+    #[serde(default)]
+    pub cooldowns: HashMap<String, CommandCooldown>,
+    #[serde(default)]
+    pub llm: LlmRateLimit,
+}
+
+impl Default for LlmRateLimit {
+    fn default() -> Self {
+        LlmRateLimit {
+            capacity: DEFAULT_LLM_BUCKET_CAPACITY,
+            refill_per_minute: DEFAULT_LLM_REFILL_PER_MINUTE,
+        }
+    }
+}
+
+impl Default for RateLimits {
+    /// Commands calling external APIs get a per-user cooldown out of the box.
+    fn default() -> Self {
+        let cooldowns = [
+            ("image", 10),
+            ("meal", 10),
+            ("time", 5),
+            ("dialog", 10),
+            ("ship", 5),
+        ]
+        .into_iter()
+        .map(|(command_name, seconds)| {
+            (
+                command_name.to_string(),
+                CommandCooldown {
+                    user: Some(seconds),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+        RateLimits {
+            cooldowns,
+            llm: LlmRateLimit::default(),
+        }
+    }
+}
+
+impl RateLimits {
+    pub fn write_rate_limits(&self) -> anyhow::Result<()> {
+        let rate_limits_path = String::from(CONFIG_DIRECTORY) + RATE_LIMITS_FILE_NAME;
+        let serialized_toml = toml::to_string_pretty(self)?;
+        std::fs::write(rate_limits_path, serialized_toml)?;
+        Ok(())
+    }
+}
+
+pub fn initialize_rate_limits() -> anyhow::Result<RateLimits> {
+    if !std::path::Path::new(CONFIG_DIRECTORY).exists() {
+        std::fs::create_dir(CONFIG_DIRECTORY)?;
+    }
+
+    let rate_limits_path = String::from(CONFIG_DIRECTORY) + RATE_LIMITS_FILE_NAME;
+    if !std::path::Path::new(&rate_limits_path).exists() {
+        let new_rate_limits = RateLimits::default();
+        new_rate_limits.write_rate_limits()?;
+        Ok(new_rate_limits)
+    } else {
+        let toml = std::fs::read_to_string(&rate_limits_path)?;
+        Ok(toml::from_str(&toml)?)
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, Http};
+use tokio::sync::watch;
+
+use crate::shared::constants::RECORD_DIRECTORY;
+
+const GAME_SESSIONS_FILE_NAME: &str = "/game_sessions.json";
+const STALE_SESSION_MINUTES: i64 = 15;
+
+static GAME_SESSIONS: Lazy<Mutex<HashMap<u64, GameSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The persisted part of a game session, used to clean up games interrupted by a restart.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GameSessionRecord {
+    pub channel_id: u64,
+    pub guild_id: Option<u64>,
+    pub game: String,
+    pub started_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+}
+
+struct GameSession {
+    id: u64,
+    record: GameSessionRecord,
+    cancel_sender: watch::Sender<bool>,
+}
+
+/// Holds the game lock of a channel. The lock is released when the guard is dropped,
+/// so a game that errors out or panics never leaves its channel locked.
+#[derive(Debug)]
+pub struct GameSessionGuard {
+    id: u64,
+    channel_id: u64,
+    cancel_receiver: watch::Receiver<bool>,
+}
+
+impl GameSessionGuard {
+    /// Resolves when the session is aborted by an admin or replaced after going stale.
+    pub async fn cancelled(&self) {
+        let mut cancel_receiver = self.cancel_receiver.clone();
+        if cancel_receiver
+            .wait_for(|cancelled| *cancelled)
+            .await
+            .is_err()
+        {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+impl Drop for GameSessionGuard {
+    fn drop(&mut self) {
+        let mut game_sessions = lock_game_sessions();
+        if game_sessions
+            .get(&self.channel_id)
+            .is_some_and(|session| session.id == self.id)
+        {
+            game_sessions.remove(&self.channel_id);
+            persist_game_sessions(&game_sessions);
+        }
+    }
+}
+
+/// Starts a game session in the channel. Returns `None` if another game is still running there.
+/// A session without activity for a while is considered stale; it is cancelled and replaced.
+pub fn start_game_session(
+    channel_id: u64,
+    guild_id: Option<u64>,
+    game: &str,
+) -> Option<GameSessionGuard> {
+    let mut game_sessions = lock_game_sessions();
+    let now = Utc::now();
+    if let Some(existing_session) = game_sessions.get(&channel_id) {
+        if now - existing_session.record.last_activity < Duration::minutes(STALE_SESSION_MINUTES) {
+            return None;
+        }
+
+        tracing::warn!(
+            "Replacing stale {} session in channel {}.",
+            &existing_session.record.game,
+            channel_id
+        );
+        let _ = existing_session.cancel_sender.send(true);
+    }
+
+    let id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+    let (cancel_sender, cancel_receiver) = watch::channel(false);
+    game_sessions.insert(
+        channel_id,
+        GameSession {
+            id,
+            record: GameSessionRecord {
+                channel_id,
+                guild_id,
+                game: game.to_string(),
+                started_at: now,
+                last_activity: now,
+            },
+            cancel_sender,
+        },
+    );
+    persist_game_sessions(&game_sessions);
+
+    Some(GameSessionGuard {
+        id,
+        channel_id,
+        cancel_receiver,
+    })
+}
+
+/// Marks the game in the channel as active so that it won't be considered stale.
+pub fn touch_game_session(channel_id: u64) {
+    if let Some(session) = lock_game_sessions().get_mut(&channel_id) {
+        session.record.last_activity = Utc::now();
+    }
+}
+
+/// Cancels the game running in the channel and releases its lock. Returns the name of the aborted game.
+pub fn abort_game_session(channel_id: u64) -> Option<String> {
+    let mut game_sessions = lock_game_sessions();
+    let session = game_sessions.remove(&channel_id)?;
+    let _ = session.cancel_sender.send(true);
+    persist_game_sessions(&game_sessions);
+    Some(session.record.game)
+}
+
+/// Notifies the channels whose games were interrupted by a restart, then forgets those games.
+pub async fn cancel_interrupted_game_sessions(http: &Http) -> anyhow::Result<()> {
+    let game_sessions_path = String::from(RECORD_DIRECTORY) + GAME_SESSIONS_FILE_NAME;
+    if !std::path::Path::new(&game_sessions_path).exists() {
+        return Ok(());
+    }
+
+    let json = std::fs::read(&game_sessions_path)?;
+    let persisted_records: Vec<GameSessionRecord> = serde_json::from_slice(&json)?;
+    let interrupted_records = {
+        let game_sessions = lock_game_sessions();
+        let interrupted_records = persisted_records
+            .into_iter()
+            .filter(|record| {
+                game_sessions
+                    .get(&record.channel_id)
+                    .is_none_or(|session| session.record.started_at != record.started_at)
+            })
+            .collect::<Vec<_>>();
+        persist_game_sessions(&game_sessions);
+        interrupted_records
+    };
+
+    for record in interrupted_records.iter() {
+        if let Err(e) = ChannelId::new(record.channel_id)
+            .say(
+                http,
+                format!(
+                    "Sorry, the {} game in this channel was interrupted by a restart and has been cancelled.",
+                    &record.game
+                ),
+            )
+            .await
+        {
+            tracing::error!(
+                "Failed to notify channel {} of an interrupted game: {}",
+                record.channel_id,
+                e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn lock_game_sessions() -> std::sync::MutexGuard<'static, HashMap<u64, GameSession>> {
+    GAME_SESSIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn persist_game_sessions(game_sessions: &HashMap<u64, GameSession>) {
+    let records = game_sessions
+        .values()
+        .map(|session| &session.record)
+        .collect::<Vec<_>>();
+    let result = serde_json::to_string_pretty(&records)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| {
+            if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+                std::fs::create_dir(RECORD_DIRECTORY)?;
+            }
+            let game_sessions_path = String::from(RECORD_DIRECTORY) + GAME_SESSIONS_FILE_NAME;
+            std::fs::write(game_sessions_path, json)?;
+            Ok(())
+        });
+
+    if let Err(e) = result {
+        tracing::error!("Failed to persist game sessions: {}", e);
+    }
+}
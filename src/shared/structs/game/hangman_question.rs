@@ -1,5 +1,8 @@
-use crate::shared::constants::ASSET_DIRECTORY;
+use std::collections::HashMap;
+
+use crate::shared::constants::{ASSET_DIRECTORY, RECORD_DIRECTORY};
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 
 pub static HANGMAN_QUESTIONS: Lazy<Vec<String>> = Lazy::new(|| {
     let hangman_questions_path = String::from(ASSET_DIRECTORY) + HANGMAN_QUESTIONS_FILE_NAME;
@@ -12,3 +15,110 @@ pub static HANGMAN_QUESTIONS: Lazy<Vec<String>> = Lazy::new(|| {
 });
 
 const HANGMAN_QUESTIONS_FILE_NAME: &str = "/game/words.json";
+const HANGMAN_WORDS_FILE_NAME: &str = "/hangman_words.json";
+pub const DEFAULT_HANGMAN_CATEGORY: &str = "Camp Buddy";
+const MAX_WORD_LENGTH: usize = 100;
+const MAX_HINT_LENGTH: usize = 200;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HangmanWord {
+    #[serde(default)]
+    pub id: u32,
+    pub word: String,
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[serde(default)]
+    pub hint: Option<String>,
+}
+
+impl HangmanWord {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.word.chars().any(|c| c.is_ascii_alphabetic()) {
+            return Err("The word has to contain at least one English letter.".to_string());
+        }
+
+        if self.word.chars().count() > MAX_WORD_LENGTH {
+            return Err(format!(
+                "The word cannot be longer than {MAX_WORD_LENGTH} characters."
+            ));
+        }
+
+        if let Some(c) = self
+            .word
+            .chars()
+            .find(|c| !c.is_ascii_graphic() && *c != ' ')
+        {
+            return Err(format!(
+                "The word can only contain English letters, digits, spaces and punctuation, but `{c}` was found."
+            ));
+        }
+
+        if self.category.trim().is_empty() {
+            return Err("The category cannot be empty.".to_string());
+        }
+
+        if self
+            .hint
+            .as_ref()
+            .is_some_and(|hint| hint.chars().count() > MAX_HINT_LENGTH)
+        {
+            return Err(format!(
+                "The hint cannot be longer than {MAX_HINT_LENGTH} characters."
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the custom words of the guild, or the built-in Camp Buddy words if the guild has none.
+pub fn get_hangman_words(
+    hangman_words: &HashMap<String, Vec<HangmanWord>>,
+    guild_id: Option<u64>,
+) -> Vec<HangmanWord> {
+    guild_id
+        .and_then(|guild_id| hangman_words.get(&guild_id.to_string()))
+        .filter(|words| !words.is_empty())
+        .cloned()
+        .unwrap_or_else(|| {
+            HANGMAN_QUESTIONS
+                .iter()
+                .enumerate()
+                .map(|(index, word)| HangmanWord {
+                    id: index as u32 + 1,
+                    word: word.clone(),
+                    category: default_category(),
+                    hint: None,
+                })
+                .collect()
+        })
+}
+
+pub fn initialize_hangman_words() -> anyhow::Result<HashMap<String, Vec<HangmanWord>>> {
+    if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+        std::fs::create_dir(RECORD_DIRECTORY)?;
+    }
+
+    let hangman_words_path = String::from(RECORD_DIRECTORY) + HANGMAN_WORDS_FILE_NAME;
+    if !std::path::Path::new(&hangman_words_path).exists() {
+        let new_hangman_words = HashMap::new();
+        write_hangman_words(&new_hangman_words)?;
+        Ok(new_hangman_words)
+    } else {
+        let json = std::fs::read(&hangman_words_path)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+pub fn write_hangman_words(
+    hangman_words: &HashMap<String, Vec<HangmanWord>>,
+) -> anyhow::Result<()> {
+    let hangman_words_path = String::from(RECORD_DIRECTORY) + HANGMAN_WORDS_FILE_NAME;
+    let serialized_json = serde_json::to_string_pretty(hangman_words)?;
+    std::fs::write(hangman_words_path, serialized_json)?;
+    Ok(())
+}
+
+fn default_category() -> String {
+    DEFAULT_HANGMAN_CATEGORY.to_string()
+}
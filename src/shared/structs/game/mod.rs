@@ -1,2 +1,3 @@
+pub mod game_session;
 pub mod hangman_question;
 pub mod quiz_question;
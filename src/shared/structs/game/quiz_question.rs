@@ -1,27 +1,354 @@
+use std::fmt::{Display, Formatter};
+
 use serde::{Deserialize, Serialize};
 
-use crate::shared::constants::ASSET_DIRECTORY;
+use crate::shared::constants::{ASSET_DIRECTORY, RECORD_DIRECTORY};
 
 const QUIZ_QUESTIONS_FILE_NAME_KOU: &str = "/game/quiz_kou.json";
 const QUIZ_QUESTIONS_FILE_NAME_TAIGA: &str = "/game/quiz_taiga.json";
+const QUIZ_BANK_FILE_NAME_KOU: &str = "/quiz_bank_kou.json";
+const QUIZ_BANK_FILE_NAME_TAIGA: &str = "/quiz_bank_taiga.json";
+const MERGED_QUESTIONS_FILE_NAME_KOU: &str = "/quiz_bank_merged_kou.json";
+const MERGED_QUESTIONS_FILE_NAME_TAIGA: &str = "/quiz_bank_merged_taiga.json";
+pub const DEFAULT_CATEGORY: &str = "General";
+const MAX_QUESTION_LENGTH: usize = 1000;
+const MAX_CHOICES: usize = 25;
+const MAX_ORDERING_ITEMS: usize = 9;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct QuizQuestion {
-    #[serde(rename = "type")]
-    pub question_type: String,
+    #[serde(default)]
+    pub id: u32,
     pub question: String,
-    pub answers: Vec<String>,
-    pub wrong: Vec<String>,
+    #[serde(flatten)]
+    pub kind: QuizQuestionKind,
+    #[serde(default)]
+    pub difficulty: QuizDifficulty,
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[serde(default)]
+    pub spoiler: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QuizFilter {
+    pub category: Option<String>,
+    pub difficulty: Option<QuizDifficulty>,
+    pub spoiler_free: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum QuizQuestionKind {
+    #[serde(rename = "FILL")]
+    Fill { answers: Vec<String> },
+    #[serde(rename = "MULTIPLE")]
+    MultipleChoice {
+        answers: Vec<String>,
+        wrong: Vec<String>,
+    },
+    #[serde(rename = "TRUE_FALSE")]
+    TrueFalse { answer: bool },
+    #[serde(rename = "ORDERING")]
+    Ordering { items: Vec<String> },
+    #[serde(rename = "IMAGE")]
+    Image {
+        image_url: String,
+        answers: Vec<String>,
+    },
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, poise::ChoiceParameter)]
+pub enum QuizQuestionType {
+    #[name = "Fill in the blank"]
+    Fill,
+    #[name = "Multiple choice"]
+    MultipleChoice,
+    #[name = "True or false"]
+    TrueFalse,
+    #[name = "Ordering"]
+    Ordering,
+    #[name = "Image"]
+    Image,
+}
+
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize, Serialize, poise::ChoiceParameter,
+)]
+pub enum QuizDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Display for QuizDifficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", poise::ChoiceParameter::name(self))
+    }
+}
+
+impl QuizQuestionKind {
+    pub fn from_parts(
+        question_type: QuizQuestionType,
+        answers: Vec<String>,
+        wrong: Vec<String>,
+        image_url: Option<String>,
+    ) -> Result<Self, String> {
+        match question_type {
+            QuizQuestionType::Fill => Ok(QuizQuestionKind::Fill { answers }),
+            QuizQuestionType::MultipleChoice => {
+                Ok(QuizQuestionKind::MultipleChoice { answers, wrong })
+            }
+            QuizQuestionType::TrueFalse => {
+                let answer = answers
+                    .first()
+                    .map(|s| s.to_lowercase())
+                    .unwrap_or_default();
+                match answer.as_str() {
+                    "true" | "t" | "yes" => Ok(QuizQuestionKind::TrueFalse { answer: true }),
+                    "false" | "f" | "no" => Ok(QuizQuestionKind::TrueFalse { answer: false }),
+                    _ => Err("The answer of a true or false question has to be either `true` or `false`.".to_string()),
+                }
+            }
+            QuizQuestionType::Ordering => Ok(QuizQuestionKind::Ordering { items: answers }),
+            QuizQuestionType::Image => match image_url {
+                Some(image_url) => Ok(QuizQuestionKind::Image { image_url, answers }),
+                None => Err("An image question requires an image URL.".to_string()),
+            },
+        }
+    }
+
+    pub fn to_parts(&self) -> (QuizQuestionType, Vec<String>, Vec<String>, Option<String>) {
+        match self {
+            QuizQuestionKind::Fill { answers } => {
+                (QuizQuestionType::Fill, answers.clone(), vec![], None)
+            }
+            QuizQuestionKind::MultipleChoice { answers, wrong } => (
+                QuizQuestionType::MultipleChoice,
+                answers.clone(),
+                wrong.clone(),
+                None,
+            ),
+            QuizQuestionKind::TrueFalse { answer } => (
+                QuizQuestionType::TrueFalse,
+                vec![answer.to_string()],
+                vec![],
+                None,
+            ),
+            QuizQuestionKind::Ordering { items } => {
+                (QuizQuestionType::Ordering, items.clone(), vec![], None)
+            }
+            QuizQuestionKind::Image { image_url, answers } => (
+                QuizQuestionType::Image,
+                answers.clone(),
+                vec![],
+                Some(image_url.clone()),
+            ),
+        }
+    }
+}
+
+impl QuizFilter {
+    pub fn matches(&self, question: &QuizQuestion) -> bool {
+        self.category
+            .as_ref()
+            .is_none_or(|category| question.category.eq_ignore_ascii_case(category))
+            && self
+                .difficulty
+                .is_none_or(|difficulty| question.difficulty == difficulty)
+            && !(self.spoiler_free && question.spoiler)
+    }
+}
+
+impl Display for QuizFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Category: {}\nDifficulty: {}\nSpoiler-free: {}",
+            self.category.as_deref().unwrap_or("Any"),
+            self.difficulty
+                .map(|difficulty| difficulty.to_string())
+                .unwrap_or_else(|| "Any".to_string()),
+            if self.spoiler_free { "Yes" } else { "No" }
+        )
+    }
+}
+
+impl QuizQuestion {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.question.trim().is_empty() {
+            return Err("The question cannot be empty.".to_string());
+        }
+
+        if self.question.chars().count() > MAX_QUESTION_LENGTH {
+            return Err(format!(
+                "The question cannot be longer than {MAX_QUESTION_LENGTH} characters."
+            ));
+        }
+
+        if self.category.trim().is_empty() {
+            return Err("The category cannot be empty.".to_string());
+        }
+
+        let has_blank_answer =
+            |answers: &[String]| answers.iter().any(|answer| answer.trim().is_empty());
+
+        match &self.kind {
+            QuizQuestionKind::Fill { answers } | QuizQuestionKind::Image { answers, .. }
+                if answers.is_empty() || has_blank_answer(answers) =>
+            {
+                Err("At least one non-empty answer is required.".to_string())
+            }
+            QuizQuestionKind::MultipleChoice { answers, wrong } => {
+                if answers.len() != 1 || has_blank_answer(answers) {
+                    Err(
+                        "A multiple choice question requires exactly one correct answer."
+                            .to_string(),
+                    )
+                } else if wrong.is_empty() || has_blank_answer(wrong) {
+                    Err(
+                        "A multiple choice question requires at least one wrong answer."
+                            .to_string(),
+                    )
+                } else if wrong.len() + 1 > MAX_CHOICES {
+                    Err(format!(
+                        "A multiple choice question can have at most {MAX_CHOICES} choices."
+                    ))
+                } else if wrong.contains(&answers[0]) {
+                    Err("The correct answer cannot also be a wrong answer.".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            QuizQuestionKind::Ordering { items } => {
+                if items.len() < 2 || items.len() > MAX_ORDERING_ITEMS {
+                    Err(format!(
+                        "An ordering question requires between 2 and {MAX_ORDERING_ITEMS} items."
+                    ))
+                } else if has_blank_answer(items) {
+                    Err("The items of an ordering question cannot be empty.".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            QuizQuestionKind::Image { image_url, .. }
+                if !image_url.starts_with("https://") && !image_url.starts_with("http://") =>
+            {
+                Err("The image URL has to start with `http://` or `https://`.".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Loads the editable quiz bank from the records and merges the shipped questions into it.
+/// Shipped questions are merged once, identified by their text, so that questions added to the asset later
+/// are picked up while the ones removed from the bank stay removed. Editing a shipped question in the asset
+/// therefore adds it again as a new question.
+pub fn initialize_quiz_questions(is_kou: bool) -> anyhow::Result<Vec<QuizQuestion>> {
+    if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+        std::fs::create_dir(RECORD_DIRECTORY)?;
+    }
+
+    let json = std::fs::read(get_quiz_questions_path(is_kou))?;
+    let shipped_questions: Vec<QuizQuestion> = serde_json::from_slice(&json)?;
+
+    let quiz_bank_path = get_quiz_bank_path(is_kou);
+    let bank_exists = std::path::Path::new(&quiz_bank_path).exists();
+    let mut quiz_questions: Vec<QuizQuestion> = if bank_exists {
+        serde_json::from_slice(&std::fs::read(&quiz_bank_path)?)?
+    } else {
+        vec![]
+    };
+
+    let merged_questions_path = get_merged_questions_path(is_kou);
+    let mut merged_questions: Vec<String> = if std::path::Path::new(&merged_questions_path).exists()
+    {
+        serde_json::from_slice(&std::fs::read(&merged_questions_path)?)?
+    } else if bank_exists {
+        // Banks predating the merge already contain every shipped question that wasn't removed.
+        shipped_questions
+            .iter()
+            .map(|question| question.question.clone())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let new_questions = shipped_questions
+        .into_iter()
+        .filter(|question| !merged_questions.contains(&question.question))
+        .collect::<Vec<_>>();
+    merged_questions.extend(
+        new_questions
+            .iter()
+            .map(|question| question.question.clone()),
+    );
+    quiz_questions.extend(new_questions);
+    assign_question_ids(&mut quiz_questions);
+
+    write_quiz_questions(is_kou, &quiz_questions)?;
+    std::fs::write(
+        merged_questions_path,
+        serde_json::to_string_pretty(&merged_questions)?,
+    )?;
+    Ok(quiz_questions)
+}
+
+pub fn write_quiz_questions(is_kou: bool, quiz_questions: &[QuizQuestion]) -> anyhow::Result<()> {
+    if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+        std::fs::create_dir(RECORD_DIRECTORY)?;
+    }
+
+    let serialized_json = serde_json::to_string_pretty(quiz_questions)?;
+    std::fs::write(get_quiz_bank_path(is_kou), serialized_json)?;
+    Ok(())
 }
 
-pub fn initialize_quiz_questions(is_kou: bool) -> Vec<QuizQuestion> {
-    let quiz_questions_path = String::from(ASSET_DIRECTORY)
+/// Gives every question without an ID (such as the ones from the original asset files) a new unique ID.
+pub fn assign_question_ids(quiz_questions: &mut [QuizQuestion]) {
+    let mut next_id = quiz_questions
+        .iter()
+        .map(|question| question.id)
+        .max()
+        .unwrap_or_default();
+    for question in quiz_questions
+        .iter_mut()
+        .filter(|question| question.id == 0)
+    {
+        next_id += 1;
+        question.id = next_id;
+    }
+}
+
+fn get_quiz_questions_path(is_kou: bool) -> String {
+    String::from(ASSET_DIRECTORY)
         + (if is_kou {
             QUIZ_QUESTIONS_FILE_NAME_KOU
         } else {
             QUIZ_QUESTIONS_FILE_NAME_TAIGA
-        });
+        })
+}
 
-    let json = std::fs::read(quiz_questions_path).expect("Failed to read quiz questions.");
-    serde_json::from_slice(&json).expect("Failed to deserialize quiz questions.")
+fn default_category() -> String {
+    DEFAULT_CATEGORY.to_string()
+}
+
+fn get_quiz_bank_path(is_kou: bool) -> String {
+    String::from(RECORD_DIRECTORY)
+        + (if is_kou {
+            QUIZ_BANK_FILE_NAME_KOU
+        } else {
+            QUIZ_BANK_FILE_NAME_TAIGA
+        })
+}
+
+fn get_merged_questions_path(is_kou: bool) -> String {
+    String::from(RECORD_DIRECTORY)
+        + (if is_kou {
+            MERGED_QUESTIONS_FILE_NAME_KOU
+        } else {
+            MERGED_QUESTIONS_FILE_NAME_TAIGA
+        })
 }
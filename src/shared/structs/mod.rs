@@ -4,19 +4,24 @@ use std::sync::Arc;
 use crate::shared::services::open_router_service::initialize_openai_compatible_client;
 use crate::shared::structs::authentication::Authentication;
 use crate::shared::structs::config::channel_control::ChannelControl;
+use crate::shared::structs::config::command_permission::CommandPermissions;
 use crate::shared::structs::config::common_settings::CommonSettings;
 use crate::shared::structs::config::configuration::Configuration;
 use crate::shared::structs::config::random_response::RandomResponse;
+use crate::shared::structs::config::rate_limit::RateLimits;
 use crate::shared::structs::config::server_info::ServerInfos;
 use crate::shared::structs::fun::emote::EmoteList;
 use crate::shared::structs::fun::qotd::QotdInfos;
 use crate::shared::structs::fun::ship_message::ShipMessage;
+use crate::shared::structs::game::hangman_question::HangmanWord;
 use crate::shared::structs::game::quiz_question::QuizQuestion;
 use crate::shared::structs::information::character::Character;
 use crate::shared::structs::information::oracle::Oracle;
+use crate::shared::structs::moderation::Moderation;
 use crate::shared::structs::record::user_record::UserRecord;
 use crate::shared::structs::smite::Smite;
 use crate::shared::structs::utility::convert::conversion_table::ConversionTable;
+use crate::shared::structs::utility::translation_memory::TranslationMemory;
 use async_openai::config::OpenAIConfig;
 use reqwest::Client;
 use tokio::sync::RwLock;
@@ -26,6 +31,7 @@ pub mod config;
 pub mod fun;
 pub mod game;
 pub mod information;
+pub mod moderation;
 pub mod record;
 pub mod smite;
 pub mod utility;
@@ -41,6 +47,8 @@ pub struct ContextData {
     pub config: Configuration,
     pub kou: bool,
     pub channel_control: Arc<RwLock<ChannelControl>>,
+    pub command_permissions: Arc<RwLock<CommandPermissions>>,
+    pub rate_limits: Arc<RwLock<RateLimits>>,
     pub user_records: Arc<RwLock<HashMap<String, UserRecord>>>,
     pub routes: Vec<Character>,
     pub valentines: Vec<Character>,
@@ -53,13 +61,16 @@ pub struct ContextData {
     pub qotd_infos: Arc<RwLock<QotdInfos>>,
     pub common_settings: CommonSettings,
     pub ship_messages: Vec<ShipMessage>,
-    pub quiz_questions: Vec<QuizQuestion>,
+    pub quiz_questions: Arc<RwLock<Vec<QuizQuestion>>>,
+    pub hangman_words: Arc<RwLock<HashMap<String, Vec<HangmanWord>>>>,
     pub smite: Smite,
+    pub moderation: Moderation,
     pub openai_client: async_openai::Client<OpenAIConfig>,
     pub random_response: RandomResponse,
     pub forged_in_starlight_instructions: String,
     pub chronosplit_instructions: String,
     pub openai_compatible_clients: Arc<OpenAICompatibleClients>,
+    pub translation_memory: Arc<RwLock<TranslationMemory>>,
 }
 
 #[derive(Debug, Clone)]
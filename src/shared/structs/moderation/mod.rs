@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use crate::shared::structs::moderation::moderation_case::{
+    ModerationCases, initialize_moderation_cases,
+};
+use crate::shared::structs::moderation::moderation_settings::{
+    ModerationSettings, initialize_moderation_settings,
+};
+use crate::shared::structs::moderation::warning::{Warnings, initialize_warnings};
+
+pub mod moderation_case;
+pub mod moderation_settings;
+pub mod warning;
+
+#[derive(Debug, Clone)]
+pub struct Moderation {
+    pub cases: Arc<RwLock<ModerationCases>>,
+    pub settings: Arc<RwLock<ModerationSettings>>,
+    pub warnings: Arc<RwLock<Warnings>>,
+}
+
+pub fn initialize_moderation() -> anyhow::Result<Moderation> {
+    Ok(Moderation {
+        cases: Arc::new(RwLock::new(initialize_moderation_cases()?)),
+        settings: Arc::new(RwLock::new(initialize_moderation_settings()?)),
+        warnings: Arc::new(RwLock::new(initialize_warnings()?)),
+    })
+}
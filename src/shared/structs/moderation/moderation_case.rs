@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::constants::RECORD_DIRECTORY;
+
+const MODERATION_CASES_FILE_NAME: &str = "/moderation_cases.json";
+
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseAction {
+    Smite,
+    Unsmite,
+    SetSmoteRole,
+    Purge,
+    EnableChannel,
+    DisableChannel,
+    AllowChannel,
+    DisallowChannel,
+    AddEmote,
+    RemoveEmote,
+    Warn,
+    RemoveWarning,
+    Timeout,
+    UpdateEscalationRules,
+    UpdateCommandPermissions,
+    SpamDetected,
+    RaidDetected,
+    UpdateAntiSpam,
+}
+
+/// Who or what a moderation action was taken against.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum CaseTarget {
+    User(u64),
+    Channel(u64),
+    Role(u64),
+    Emote(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModerationCase {
+    pub id: u64,
+    pub guild_id: u64,
+    pub action: CaseAction,
+    pub actor_id: u64,
+    #[serde(default)]
+    pub target: Option<CaseTarget>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// The message of this case in the mod-log channel, so that it can be updated later.
+    #[serde(default)]
+    pub log_channel_id: Option<u64>,
+    #[serde(default)]
+    pub log_message_id: Option<u64>,
+}
+
+/// Moderation cases of every guild, keyed by guild ID. Case IDs are numbered per guild.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct ModerationCases {
+    pub guilds: HashMap<String, Vec<ModerationCase>>,
+}
+
+impl Display for CaseAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CaseAction::Smite => "Smite",
+            CaseAction::Unsmite => "Unsmite",
+            CaseAction::SetSmoteRole => "Set Smote Role",
+            CaseAction::Purge => "Purge",
+            CaseAction::EnableChannel => "Enable Channel",
+            CaseAction::DisableChannel => "Disable Channel",
+            CaseAction::AllowChannel => "Allow Channel",
+            CaseAction::DisallowChannel => "Disallow Channel",
+            CaseAction::AddEmote => "Add Emote",
+            CaseAction::RemoveEmote => "Remove Emote",
+            CaseAction::Warn => "Warn",
+            CaseAction::RemoveWarning => "Remove Warning",
+            CaseAction::Timeout => "Timeout",
+            CaseAction::UpdateEscalationRules => "Update Escalation Rules",
+            CaseAction::UpdateCommandPermissions => "Update Command Permissions",
+            CaseAction::SpamDetected => "Spam Detected",
+            CaseAction::RaidDetected => "Raid Detected",
+            CaseAction::UpdateAntiSpam => "Update Anti-Spam",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Display for CaseTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaseTarget::User(id) => write!(f, "<@{id}>"),
+            CaseTarget::Channel(id) => write!(f, "<#{id}>"),
+            CaseTarget::Role(id) => write!(f, "<@&{id}>"),
+            CaseTarget::Emote(name) => write!(f, "`{name}`"),
+        }
+    }
+}
+
+impl ModerationCase {
+    pub fn new(guild_id: u64, actor_id: u64, action: CaseAction) -> Self {
+        ModerationCase {
+            id: 0,
+            guild_id,
+            action,
+            actor_id,
+            target: None,
+            reason: None,
+            details: None,
+            created_at: Utc::now(),
+            log_channel_id: None,
+            log_message_id: None,
+        }
+    }
+
+    pub fn with_target(mut self, target: CaseTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: Option<String>) -> Self {
+        self.reason = reason;
+        self
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+impl ModerationCases {
+    pub fn get(&self, guild_id: u64, case_id: u64) -> Option<&ModerationCase> {
+        self.guilds
+            .get(&guild_id.to_string())
+            .and_then(|cases| cases.iter().find(|case| case.id == case_id))
+    }
+
+    pub fn get_mut(&mut self, guild_id: u64, case_id: u64) -> Option<&mut ModerationCase> {
+        self.guilds
+            .get_mut(&guild_id.to_string())
+            .and_then(|cases| cases.iter_mut().find(|case| case.id == case_id))
+    }
+
+    pub fn list(&self, guild_id: u64) -> &[ModerationCase] {
+        self.guilds
+            .get(&guild_id.to_string())
+            .map(|cases| cases.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Assigns the next case ID of the guild to the case and stores it. Returns the assigned ID.
+    pub fn add(&mut self, mut case: ModerationCase) -> u64 {
+        let cases = self.guilds.entry(case.guild_id.to_string()).or_default();
+        case.id = cases.iter().map(|case| case.id).max().unwrap_or_default() + 1;
+        let id = case.id;
+        cases.push(case);
+        id
+    }
+
+    pub fn write_moderation_cases(&self) -> anyhow::Result<()> {
+        if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+            std::fs::create_dir(RECORD_DIRECTORY)?;
+        }
+
+        let moderation_cases_path = String::from(RECORD_DIRECTORY) + MODERATION_CASES_FILE_NAME;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(moderation_cases_path, json)?;
+        Ok(())
+    }
+}
+
+pub fn initialize_moderation_cases() -> anyhow::Result<ModerationCases> {
+    let moderation_cases_path = String::from(RECORD_DIRECTORY) + MODERATION_CASES_FILE_NAME;
+    if !std::path::Path::new(&moderation_cases_path).exists() {
+        let new_moderation_cases = ModerationCases::default();
+        new_moderation_cases.write_moderation_cases()?;
+        Ok(new_moderation_cases)
+    } else {
+        let json = std::fs::read(&moderation_cases_path)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
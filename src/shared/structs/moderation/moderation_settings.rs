@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::constants::CONFIG_DIRECTORY;
+
+const MODERATION_SETTINGS_FILE_NAME: &str = "/moderation_settings.toml";
+
+/// Moderation settings of every guild, keyed by guild ID.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ModerationSettings {
+    #[serde(default)]
+    pub guilds: HashMap<String, GuildModerationSettings>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct GuildModerationSettings {
+    /// The channel where moderation cases are posted.
+    #[serde(default)]
+    pub mod_log_channel_id: Option<u64>,
+    /// Punishments applied automatically when a member reaches a number of warnings.
+    #[serde(default)]
+    pub escalation_rules: Vec<EscalationRule>,
+    #[serde(default)]
+    pub anti_spam: AntiSpamSettings,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, poise::ChoiceParameter)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationAction {
+    Smite,
+    Timeout,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, poise::ChoiceParameter)]
+#[serde(rename_all = "snake_case")]
+pub enum SpamPunishment {
+    #[name = "No punishment"]
+    NoPunishment,
+    Smite,
+    Timeout,
+}
+
+/// Thresholds and actions of the anti-spam module. A threshold of 0 disables its check.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AntiSpamSettings {
+    pub enabled: bool,
+    /// Identical messages of a member within the window that count as spam.
+    pub duplicate_messages: usize,
+    pub duplicate_window_seconds: u64,
+    /// Mentions in a single message that count as mass mentioning.
+    pub max_mentions: usize,
+    pub block_invite_links: bool,
+    /// Joins within the window that count as a raid.
+    pub raid_joins: usize,
+    pub raid_window_seconds: u64,
+    /// Greetings stay suppressed for this long after the last join of a raid.
+    pub raid_cooldown_minutes: u64,
+    pub delete_messages: bool,
+    pub punishment: SpamPunishment,
+    /// How long the punishment lasts, e.g. `10m`.
+    pub punishment_duration: String,
+    /// Whether detected spam and raids are recorded as cases, which are posted to the mod-log channel.
+    pub alert_mod_log: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EscalationRule {
+    /// The number of warnings that triggers the rule.
+    pub warnings: usize,
+    /// Only warnings given within this many days are counted.
+    pub within_days: i64,
+    pub action: EscalationAction,
+    /// How long the punishment lasts, e.g. `12h`.
+    pub duration: String,
+}
+
+impl Display for EscalationRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let action = match self.action {
+            EscalationAction::Smite => "Smite",
+            EscalationAction::Timeout => "Timeout",
+        };
+        write!(
+            f,
+            "{} warnings within {} days: {} for {}",
+            self.warnings, self.within_days, action, &self.duration
+        )
+    }
+}
+
+impl SpamPunishment {
+    pub fn escalation_action(self) -> Option<EscalationAction> {
+        match self {
+            SpamPunishment::NoPunishment => None,
+            SpamPunishment::Smite => Some(EscalationAction::Smite),
+            SpamPunishment::Timeout => Some(EscalationAction::Timeout),
+        }
+    }
+}
+
+impl Default for AntiSpamSettings {
+    fn default() -> Self {
+        AntiSpamSettings {
+            enabled: false,
+            duplicate_messages: 5,
+            duplicate_window_seconds: 10,
+            max_mentions: 8,
+            block_invite_links: true,
+            raid_joins: 10,
+            raid_window_seconds: 60,
+            raid_cooldown_minutes: 10,
+            delete_messages: true,
+            punishment: SpamPunishment::NoPunishment,
+            punishment_duration: "10m".to_string(),
+            alert_mod_log: true,
+        }
+    }
+}
+
+impl ModerationSettings {
+    pub fn get(&self, guild_id: u64) -> GuildModerationSettings {
+        self.guilds
+            .get(&guild_id.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn get_mut(&mut self, guild_id: u64) -> &mut GuildModerationSettings {
+        self.guilds.entry(guild_id.to_string()).or_default()
+    }
+
+    pub fn write_moderation_settings(&self) -> anyhow::Result<()> {
+        let moderation_settings_path =
+            String::from(CONFIG_DIRECTORY) + MODERATION_SETTINGS_FILE_NAME;
+        let serialized_toml = toml::to_string_pretty(self)?;
+        std::fs::write(moderation_settings_path, serialized_toml)?;
+        Ok(())
+    }
+}
+
+pub fn initialize_moderation_settings() -> anyhow::Result<ModerationSettings> {
+    if !std::path::Path::new(CONFIG_DIRECTORY).exists() {
+        std::fs::create_dir(CONFIG_DIRECTORY)?;
+    }
+
+    let moderation_settings_path = String::from(CONFIG_DIRECTORY) + MODERATION_SETTINGS_FILE_NAME;
+    if !std::path::Path::new(&moderation_settings_path).exists() {
+        let new_moderation_settings = ModerationSettings::default();
+        new_moderation_settings.write_moderation_settings()?;
+        Ok(new_moderation_settings)
+    } else {
+        let toml = std::fs::read_to_string(&moderation_settings_path)?;
+        Ok(toml::from_str(&toml)?)
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::constants::RECORD_DIRECTORY;
+
+const WARNINGS_FILE_NAME: &str = "/warnings.json";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Warning {
+    pub id: u64,
+    pub user_id: u64,
+    pub moderator_id: u64,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Warnings of every guild, keyed by guild ID. Warning IDs are numbered per guild.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct Warnings {
+    pub guilds: HashMap<String, Vec<Warning>>,
+}
+
+impl Warnings {
+    pub fn of_user(&self, guild_id: u64, user_id: u64) -> Vec<Warning> {
+        self.guilds
+            .get(&guild_id.to_string())
+            .map(|warnings| {
+                warnings
+                    .iter()
+                    .filter(|warning| warning.user_id == user_id)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Counts the user's warnings within the given period up to now.
+    pub fn count_recent(&self, guild_id: u64, user_id: u64, period: Duration) -> usize {
+        let since = Utc::now() - period;
+        self.of_user(guild_id, user_id)
+            .iter()
+            .filter(|warning| warning.created_at >= since)
+            .count()
+    }
+
+    /// Stores a new warning with the next warning ID of the guild.
+    pub fn add(
+        &mut self,
+        guild_id: u64,
+        user_id: u64,
+        moderator_id: u64,
+        reason: String,
+    ) -> Warning {
+        let warnings = self.guilds.entry(guild_id.to_string()).or_default();
+        let warning = Warning {
+            id: warnings
+                .iter()
+                .map(|warning| warning.id)
+                .max()
+                .unwrap_or_default()
+                + 1,
+            user_id,
+            moderator_id,
+            reason,
+            created_at: Utc::now(),
+        };
+        warnings.push(warning.clone());
+        warning
+    }
+
+    pub fn remove(&mut self, guild_id: u64, warning_id: u64) -> Option<Warning> {
+        let warnings = self.guilds.get_mut(&guild_id.to_string())?;
+        let index = warnings
+            .iter()
+            .position(|warning| warning.id == warning_id)?;
+        Some(warnings.remove(index))
+    }
+
+    pub fn write_warnings(&self) -> anyhow::Result<()> {
+        if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+            std::fs::create_dir(RECORD_DIRECTORY)?;
+        }
+
+        let warnings_path = String::from(RECORD_DIRECTORY) + WARNINGS_FILE_NAME;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(warnings_path, json)?;
+        Ok(())
+    }
+}
+
+pub fn initialize_warnings() -> anyhow::Result<Warnings> {
+    let warnings_path = String::from(RECORD_DIRECTORY) + WARNINGS_FILE_NAME;
+    if !std::path::Path::new(&warnings_path).exists() {
+        let new_warnings = Warnings::default();
+        new_warnings.write_warnings()?;
+        Ok(new_warnings)
+    } else {
+        let json = std::fs::read(&warnings_path)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::shared::structs::record::user_record::{UserRecord, write_user_records};
+
+/// A user's game stats over all guilds. The same stats are also kept for every guild,
+/// keyed by guild ID, so that leaderboards only count games played in their guild.
+/// Per-guild entries don't have any `guilds` of their own.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct GameStats {
+    #[serde(default)]
+    pub quiz: QuizStats,
+    #[serde(default)]
+    pub hangman: HangmanStats,
+    #[serde(default)]
+    pub tictactoe: BoardGameStats,
+    #[serde(default)]
+    pub connect_four: BoardGameStats,
+    #[serde(default)]
+    pub reversi: BoardGameStats,
+    #[serde(default)]
+    pub guilds: HashMap<String, GameStats>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct QuizStats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub correct_answers: u32,
+    pub wrong_answers: u32,
+    pub fastest_answer_ms: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct HangmanStats {
+    pub games_played: u32,
+    pub words_solved: u32,
+    pub letters_guessed: u32,
+    pub correct_letters: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct BoardGameStats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl GameStats {
+    pub fn of_guild(&self, guild_id: u64) -> Option<&GameStats> {
+        self.guilds.get(&guild_id.to_string())
+    }
+}
+
+impl QuizStats {
+    pub fn accuracy(&self) -> f64 {
+        let total = self.correct_answers + self.wrong_answers;
+        if total == 0 {
+            0.0
+        } else {
+            self.correct_answers as f64 / total as f64 * 100.0
+        }
+    }
+}
+
+impl HangmanStats {
+    pub fn accuracy(&self) -> f64 {
+        if self.letters_guessed == 0 {
+            0.0
+        } else {
+            self.correct_letters as f64 / self.letters_guessed as f64 * 100.0
+        }
+    }
+}
+
+impl BoardGameStats {
+    pub fn win_rate(&self) -> f64 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games_played as f64 * 100.0
+        }
+    }
+}
+
+pub async fn update_game_stats<F>(
+    user_records: &Arc<RwLock<HashMap<String, UserRecord>>>,
+    guild_id: Option<u64>,
+    user_ids: &[u64],
+    mut update: F,
+) -> anyhow::Result<()>
+where
+    F: FnMut(u64, &mut GameStats),
+{
+    let mut user_records_lock = user_records.write().await;
+    for user_id in user_ids.iter() {
+        let user_record_entry = user_records_lock.entry(user_id.to_string()).or_default();
+        if let Some(guild_id) = guild_id {
+            let guild_stats = user_record_entry
+                .games
+                .guilds
+                .entry(guild_id.to_string())
+                .or_default();
+            update(*user_id, guild_stats);
+        }
+        update(*user_id, &mut user_record_entry.games);
+    }
+
+    write_user_records(&user_records_lock)
+}
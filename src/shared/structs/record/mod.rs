@@ -1,3 +1,4 @@
+pub mod game_stats;
 pub mod message;
 pub mod user_credit;
 pub mod user_record;
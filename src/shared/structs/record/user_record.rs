@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+use crate::commands::utility::translate::TargetLanguage;
 use crate::shared::constants::RECORD_DIRECTORY;
+use crate::shared::structs::record::game_stats::GameStats;
 
 const USER_RECORDS_FILE_NAME: &str = "/user_records.json";
 
@@ -12,6 +14,12 @@ pub struct UserRecord {
     pub valentine: HashMap<String, u16>,
     #[serde(default)]
     pub replies: u32,
+    #[serde(default)]
+    pub translation_language: Option<TargetLanguage>,
+    #[serde(default)]
+    pub games: GameStats,
+    #[serde(default)]
+    pub quote_game_guild_ids: HashSet<u64>,
 }
 
 impl UserRecord {
@@ -20,6 +28,9 @@ impl UserRecord {
             route: HashMap::new(),
             valentine: HashMap::new(),
             replies: 0,
+            translation_language: None,
+            games: GameStats::default(),
+            quote_game_guild_ids: HashSet::new(),
         }
     }
 }
@@ -1,21 +1,26 @@
 use crate::shared::constants::{
-    ASSET_DIRECTORY, CONFIG_DIRECTORY, KOU_SERVER_SMOTE_ROLE_ID, TAIGA_SERVER_SMOTE_ROLE_ID,
+    ASSET_DIRECTORY, CONFIG_DIRECTORY, KOU_SERVER_ID, KOU_SERVER_SMOTE_ROLE_ID, TAIGA_SERVER_ID,
+    TAIGA_SERVER_SMOTE_ROLE_ID,
 };
+use crate::shared::services::scheduler_service::{ScheduledAction, schedule_job};
 use crate::shared::structs::ContextData;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serenity::all::{GuildId, UserId};
+use serenity::all::{GuildId, Http, UserId};
 use serenity::model::prelude::RoleId;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 const SMITE_GIF_LINKS_FILE_NAME: &str = "/json/smite_links.json";
 const SMOTE_USER_LIST_FILE_NAME: &str = "/smote_users.toml";
+const SMITE_SETTINGS_FILE_NAME: &str = "/smite_settings.toml";
 
 #[derive(Debug, Clone)]
 pub struct Smite {
     pub smite_gif_links: Vec<String>,
     pub smote_user_list: Arc<RwLock<SmoteUserList>>,
+    pub smite_settings: Arc<RwLock<SmiteSettings>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -23,11 +28,23 @@ pub struct SmoteUserList {
     pub smote_users: Vec<SmoteUser>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SmoteUser {
     pub user_id: u64,
     pub due_time: DateTime<Utc>,
     pub guild_id: u64,
+    /// The role given to the user. Entries written before per-guild roles were introduced have none.
+    #[serde(default)]
+    pub role_id: Option<u64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Per-guild smite settings, keyed by guild ID.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct SmiteSettings {
+    #[serde(default)]
+    pub smote_role_ids: HashMap<String, u64>,
 }
 
 impl SmoteUserList {
@@ -37,6 +54,53 @@ impl SmoteUserList {
         }
     }
 
+    pub fn get(&self, guild_id: u64, user_id: u64) -> Option<&SmoteUser> {
+        self.smote_users
+            .iter()
+            .find(|u| u.guild_id == guild_id && u.user_id == user_id)
+    }
+
+    /// Smites the user for the duration. If the user is already smitten, the smite is extended
+    /// from its current due time instead of adding another entry. Returns the updated entry,
+    /// or `None` without changing anything if the due time would be out of range.
+    pub fn smite(
+        &mut self,
+        guild_id: u64,
+        user_id: u64,
+        role_id: u64,
+        duration: chrono::Duration,
+        reason: Option<String>,
+    ) -> Option<SmoteUser> {
+        let now = Utc::now();
+        let index = match self
+            .smote_users
+            .iter()
+            .position(|u| u.guild_id == guild_id && u.user_id == user_id)
+        {
+            Some(index) => {
+                let smote_user = &mut self.smote_users[index];
+                smote_user.due_time = smote_user.due_time.max(now).checked_add_signed(duration)?;
+                smote_user.role_id = Some(role_id);
+                if reason.is_some() {
+                    smote_user.reason = reason;
+                }
+                index
+            }
+            None => {
+                self.smote_users.push(SmoteUser {
+                    user_id,
+                    due_time: now.checked_add_signed(duration)?,
+                    guild_id,
+                    role_id: Some(role_id),
+                    reason,
+                });
+                self.smote_users.len() - 1
+            }
+        };
+
+        Some(self.smote_users[index].clone())
+    }
+
     pub fn write_smote_user_list(&self) -> anyhow::Result<()> {
         let smote_user_list_path = String::from(CONFIG_DIRECTORY) + SMOTE_USER_LIST_FILE_NAME;
         let serialized_toml = toml::to_string_pretty(self)?;
@@ -45,38 +109,122 @@ impl SmoteUserList {
     }
 }
 
+impl SmiteSettings {
+    /// Returns the smote role of the guild, falling back to the built-in roles of Taiga's and Kou's servers.
+    pub fn smote_role_id(&self, guild_id: u64) -> Option<u64> {
+        self.smote_role_ids
+            .get(&guild_id.to_string())
+            .copied()
+            .or(match guild_id {
+                TAIGA_SERVER_ID => Some(TAIGA_SERVER_SMOTE_ROLE_ID),
+                KOU_SERVER_ID => Some(KOU_SERVER_SMOTE_ROLE_ID),
+                _ => None,
+            })
+    }
+
+    pub fn write_smite_settings(&self) -> anyhow::Result<()> {
+        let smite_settings_path = String::from(CONFIG_DIRECTORY) + SMITE_SETTINGS_FILE_NAME;
+        let serialized_toml = toml::to_string_pretty(self)?;
+        std::fs::write(smite_settings_path, serialized_toml)?;
+        Ok(())
+    }
+}
+
 pub fn initialize_smite() -> anyhow::Result<Smite> {
     Ok(Smite {
         smite_gif_links: initialize_smite_gif_links(),
         smote_user_list: Arc::new(RwLock::new(initialize_smote_user_list()?)),
+        smite_settings: Arc::new(RwLock::new(initialize_smite_settings()?)),
     })
 }
 
-pub async fn schedule_unsmite(ctx: &serenity::prelude::Context, data: &ContextData) {
-    let smote_user_list = data.smite.smote_user_list.clone();
-    let smote_users = smote_user_list.read().await.smote_users.clone();
+/// Gives the smote role to the user and records the smite, extending the user's current smite if any.
+/// Returns the smite and whether an existing smite was extended.
+pub async fn smite_user(
+    http: &Http,
+    smite: &Smite,
+    guild_id: u64,
+    user_id: u64,
+    role_id: u64,
+    duration: chrono::Duration,
+    reason: Option<String>,
+) -> anyhow::Result<(SmoteUser, bool)> {
+    http.add_member_role(
+        GuildId::new(guild_id),
+        UserId::new(user_id),
+        RoleId::new(role_id),
+        reason.as_deref(),
+    )
+    .await?;
 
-    for smote_user in smote_users.into_iter() {
-        let context = ctx.clone();
-        let smote_user_list = data.smite.smote_user_list.clone();
-        tokio::spawn(async move {
-            let time_remained = smote_user.due_time - Utc::now();
+    let (smote_user, extended) = {
+        let mut smote_users_write_lock = smite.smote_user_list.write().await;
+        let extended = smote_users_write_lock.get(guild_id, user_id).is_some();
+        let smote_user = smote_users_write_lock
+            .smite(guild_id, user_id, role_id, duration, reason)
+            .ok_or_else(|| anyhow::anyhow!("The smite duration is out of range."))?;
+        smote_users_write_lock.write_smote_user_list()?;
+        (smote_user, extended)
+    };
+    schedule_smote_user_removal(&smote_user);
 
-            if time_remained.num_seconds() < 0 {
-                if let Err(e) = remove_smote_user(context, smote_user_list, smote_user).await {
-                    tracing::error!("Error occurred when removing smote user: {}", e);
-                }
-            } else {
-                let std_duration = time_remained
-                    .to_std()
-                    .expect("Failed to cast chrono duration to std duration.");
-                tokio::time::sleep(std_duration).await;
-                if let Err(e) = remove_smote_user(context, smote_user_list, smote_user).await {
-                    tracing::error!("Error occurred when removing smote user: {}", e);
-                }
+    Ok((smote_user, extended))
+}
+
+/// Registers the expiry of every smite with the scheduler. Jobs are keyed by guild and user,
+/// so registering them again on a reconnect doesn't duplicate them.
+pub async fn schedule_unsmite(data: &ContextData) {
+    let smote_users = data.smite.smote_user_list.read().await.smote_users.clone();
+    for smote_user in smote_users.iter() {
+        schedule_smote_user_removal(smote_user);
+    }
+}
+
+pub fn schedule_smote_user_removal(smote_user: &SmoteUser) {
+    schedule_job(
+        ScheduledAction::Unsmite {
+            guild_id: smote_user.guild_id,
+            user_id: smote_user.user_id,
+        },
+        smote_user.due_time,
+    );
+}
+
+/// Removes the smote role from the user and forgets the smite. Unless `force` is set,
+/// nothing happens if the smite is not due yet. Returns whether a smite was lifted.
+pub async fn remove_smote_user(
+    http: &Http,
+    smote_user_list: Arc<RwLock<SmoteUserList>>,
+    guild_id: u64,
+    user_id: u64,
+    force: bool,
+) -> anyhow::Result<bool> {
+    let mut smote_users_write_lock = smote_user_list.write().await;
+    let smote_user = match smote_users_write_lock.get(guild_id, user_id) {
+        Some(smote_user) if force || smote_user.due_time <= Utc::now() => smote_user.clone(),
+        _ => return Ok(false),
+    };
+
+    if let Ok(member) = http
+        .get_member(GuildId::new(guild_id), UserId::new(user_id))
+        .await
+    {
+        let smote_role_ids = smote_user
+            .role_id
+            .map(|role_id| vec![role_id])
+            .unwrap_or_else(|| vec![TAIGA_SERVER_SMOTE_ROLE_ID, KOU_SERVER_SMOTE_ROLE_ID]);
+        for role_id in smote_role_ids.into_iter() {
+            if member.roles.contains(&RoleId::new(role_id)) {
+                member.remove_role(http, RoleId::new(role_id)).await?;
             }
-        });
+        }
     }
+
+    smote_users_write_lock
+        .smote_users
+        .retain(|u| !(u.guild_id == guild_id && u.user_id == user_id));
+    smote_users_write_lock.write_smote_user_list()?;
+    Ok(true)
 }
 
 fn initialize_smite_gif_links() -> Vec<String> {
@@ -102,45 +250,14 @@ fn initialize_smote_user_list() -> anyhow::Result<SmoteUserList> {
     }
 }
 
-async fn remove_smote_user(
-    ctx: serenity::prelude::Context,
-    smote_user_list: Arc<RwLock<SmoteUserList>>,
-    smote_user: SmoteUser,
-) -> anyhow::Result<()> {
-    if let Ok(member) = ctx
-        .http
-        .get_member(
-            GuildId::new(smote_user.guild_id),
-            UserId::new(smote_user.user_id),
-        )
-        .await
-    {
-        let smote_role_ids = vec![TAIGA_SERVER_SMOTE_ROLE_ID, KOU_SERVER_SMOTE_ROLE_ID];
-        for role_id in smote_role_ids.into_iter() {
-            if !member.roles.contains(&RoleId::new(role_id)) {
-                continue;
-            }
-
-            match member
-                .remove_role(ctx.http.clone(), RoleId::new(role_id))
-                .await
-            {
-                Ok(_) => {
-                    let mut smote_users_write_lock = smote_user_list.write().await;
-                    let smote_users = smote_users_write_lock.smote_users.clone();
-                    let filtered_smote_users = smote_users
-                        .into_iter()
-                        .filter(|u| u.user_id != smote_user.user_id)
-                        .collect::<Vec<_>>();
-                    smote_users_write_lock.smote_users = filtered_smote_users;
-                    smote_users_write_lock.write_smote_user_list()?;
-                    break;
-                }
-                Err(e) => {
-                    tracing::error!("Error when removing smote role from user: {}", e);
-                }
-            }
-        }
+fn initialize_smite_settings() -> anyhow::Result<SmiteSettings> {
+    let smite_settings_path = String::from(CONFIG_DIRECTORY) + SMITE_SETTINGS_FILE_NAME;
+    if !std::path::Path::new(&smite_settings_path).exists() {
+        let new_smite_settings = SmiteSettings::default();
+        new_smite_settings.write_smite_settings()?;
+        Ok(new_smite_settings)
+    } else {
+        let toml = std::fs::read_to_string(&smite_settings_path)?;
+        Ok(toml::from_str(&toml)?)
     }
-    Ok(())
 }
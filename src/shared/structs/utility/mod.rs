@@ -1,3 +1,5 @@
 pub mod convert;
 pub mod judge_zero;
 pub mod save_file;
+pub mod translation;
+pub mod translation_memory;
@@ -0,0 +1,83 @@
+use crate::commands::utility::translate::LanguageModel;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+pub struct TranslationOutput {
+    pub text: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelTranslation {
+    pub model: LanguageModel,
+    pub latency: Duration,
+    pub result: Result<TranslationOutput, String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct JudgeScore {
+    pub fidelity: u8,
+    pub fluency: u8,
+    pub glossary_adherence: u8,
+    #[serde(default)]
+    pub comment: String,
+}
+
+/// The judge is asked to score each criterion in this range.
+pub const MIN_JUDGE_SCORE: u8 = 1;
+pub const MAX_JUDGE_SCORE: u8 = 10;
+
+#[derive(Debug, Clone)]
+pub struct TranslationJudgement {
+    pub model: LanguageModel,
+    pub result: Result<JudgeScore, String>,
+}
+
+impl JudgeScore {
+    pub fn total(&self) -> u32 {
+        self.fidelity as u32 + self.fluency as u32 + self.glossary_adherence as u32
+    }
+
+    /// Rejects scores the judge wasn't asked to give, so that they can't skew the ranking.
+    pub fn validate(self) -> anyhow::Result<Self> {
+        let scores = [
+            ("fidelity", self.fidelity),
+            ("fluency", self.fluency),
+            ("glossary_adherence", self.glossary_adherence),
+        ];
+        for (criterion, score) in scores.into_iter() {
+            if !(MIN_JUDGE_SCORE..=MAX_JUDGE_SCORE).contains(&score) {
+                return Err(anyhow::anyhow!(
+                    "The judge scored {} with {}, which is outside of {}-{}.",
+                    criterion,
+                    score,
+                    MIN_JUDGE_SCORE,
+                    MAX_JUDGE_SCORE
+                ));
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl TranslationJudgement {
+    pub fn total(&self) -> Option<u32> {
+        self.result.as_ref().ok().map(|score| score.total())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GeneralTranslation {
+    pub source_language: String,
+    pub translation: String,
+}
+
+pub fn split_paragraphs(text: &str) -> Vec<String> {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim().to_string())
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect()
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::utility::translate::{LanguageModel, Novel};
+use crate::shared::constants::RECORD_DIRECTORY;
+
+const TRANSLATION_MEMORY_FILE_NAME: &str = "/translation_memory.json";
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TranslationMemory {
+    pub entries: HashMap<String, TranslationMemoryEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TranslationMemoryEntry {
+    pub source: String,
+    pub translation: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TranslationMemory {
+    pub fn get(
+        &self,
+        novel: Novel,
+        model: LanguageModel,
+        paragraph: &str,
+    ) -> Option<&TranslationMemoryEntry> {
+        // The key is only a hash of the paragraph, so make sure the entry really is for this paragraph.
+        let normalized_paragraph = normalize_paragraph(paragraph);
+        self.entries
+            .get(&build_key(novel, model, paragraph))
+            .filter(|entry| normalize_paragraph(&entry.source) == normalized_paragraph)
+    }
+
+    pub fn insert(
+        &mut self,
+        novel: Novel,
+        model: LanguageModel,
+        source: String,
+        translation: String,
+    ) {
+        self.entries.insert(
+            build_key(novel, model, &source),
+            TranslationMemoryEntry {
+                source,
+                translation,
+                updated_at: Utc::now(),
+            },
+        );
+    }
+
+    pub fn write_translation_memory(&self) -> anyhow::Result<()> {
+        let translation_memory_path = String::from(RECORD_DIRECTORY) + TRANSLATION_MEMORY_FILE_NAME;
+        let serialized_json = serde_json::to_string_pretty(self)?;
+        std::fs::write(translation_memory_path, serialized_json)?;
+        Ok(())
+    }
+}
+
+pub fn initialize_translation_memory() -> anyhow::Result<TranslationMemory> {
+    if !std::path::Path::new(RECORD_DIRECTORY).exists() {
+        std::fs::create_dir(RECORD_DIRECTORY)?;
+    }
+
+    let translation_memory_path = String::from(RECORD_DIRECTORY) + TRANSLATION_MEMORY_FILE_NAME;
+    if !std::path::Path::new(&translation_memory_path).exists() {
+        let new_translation_memory = TranslationMemory::default();
+        new_translation_memory.write_translation_memory()?;
+        Ok(new_translation_memory)
+    } else {
+        let json = std::fs::read(&translation_memory_path)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+/// Normalizes a paragraph so that paragraphs only differing in whitespace, letter case or
+/// typographic punctuation share the same translation memory entry.
+pub fn normalize_paragraph(paragraph: &str) -> String {
+    paragraph
+        .chars()
+        .map(|c| match c {
+            '“' | '”' | '„' => '"',
+            '‘' | '’' => '\'',
+            '—' | '–' => '-',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn build_key(novel: Novel, model: LanguageModel, paragraph: &str) -> String {
+    format!(
+        "{:?}:{:?}:{:016x}",
+        novel,
+        model,
+        hash_paragraph(&normalize_paragraph(paragraph))
+    )
+}
+
+// FNV-1a is used instead of `DefaultHasher` because the keys are persisted and must stay stable across Rust versions.
+fn hash_paragraph(paragraph: &str) -> u64 {
+    paragraph.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
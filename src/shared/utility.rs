@@ -49,3 +49,65 @@ pub fn build_author_name_map(messages: &[Message]) -> HashMap<UserId, String> {
 
     author_name_map
 }
+
+/// The longest duration `parse_duration` accepts.
+pub const MAX_DURATION_DAYS: i64 = 365;
+
+/// Parses a duration such as `30m`, `2h` or `1d12h`. Supported units are `s`, `m`, `h`, `d` and `w`.
+/// Durations longer than [`MAX_DURATION_DAYS`] are rejected.
+pub fn parse_duration(text: &str) -> Option<chrono::Duration> {
+    let mut total = chrono::Duration::zero();
+    let mut amount = String::new();
+    for c in text.trim().to_lowercase().chars() {
+        if c.is_ascii_digit() {
+            amount.push(c);
+            continue;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let value = amount.parse::<i64>().ok()?;
+        amount.clear();
+        let duration = match c {
+            's' => chrono::Duration::try_seconds(value)?,
+            'm' => chrono::Duration::try_minutes(value)?,
+            'h' => chrono::Duration::try_hours(value)?,
+            'd' => chrono::Duration::try_days(value)?,
+            'w' => chrono::Duration::try_weeks(value)?,
+            _ => return None,
+        };
+        total = total.checked_add(&duration)?;
+    }
+
+    if !amount.is_empty()
+        || total <= chrono::Duration::zero()
+        || total > chrono::Duration::days(MAX_DURATION_DAYS)
+    {
+        None
+    } else {
+        Some(total)
+    }
+}
+
+/// Formats a duration as e.g. `1d 2h 5m`, omitting zero units.
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let total_seconds = duration.num_seconds().max(0);
+    let units = [
+        (total_seconds / 86400, "d"),
+        (total_seconds % 86400 / 3600, "h"),
+        (total_seconds % 3600 / 60, "m"),
+    ];
+    let formatted = units
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if formatted.is_empty() {
+        format!("{total_seconds}s")
+    } else {
+        formatted
+    }
+}